@@ -0,0 +1,218 @@
+//! Esse módulo faz a leitura dos argumentos de linha de comando.
+
+#[cfg(test)]
+mod test;
+
+use lambda::compiler::expansor::LetLowering;
+use lambda::strategy::Strategy;
+use std::{error::Error, fmt};
+
+/// Texto de ajuda, mostrado com `--help` ou após um erro nos argumentos.
+pub const USAGE: &str = "\
+Uso: pinguim-lambda [OPÇÕES] [ARQUIVO]
+
+Compila e executa um programa do cálculo lambda. Use `-` como arquivo para
+ler o programa da entrada padrão. Sem arquivo, abre o modo interativo (REPL).
+
+Opções:
+  -s, --strategy <ESTRATÉGIA>  estratégia de avaliação: normal (padrão),
+                               applicative, name, value ou head
+  -n, --max-steps <N>          para após N passos de redução
+      --no-prelude             desabilita as definições do prelúdio padrão
+      --let-redex              mantém os `let` dentro de expressões como
+                               redexes em vez de substituí-los
+      --no-color               mostra os erros sem cores, mesmo no terminal
+  -h, --help                   mostra esta ajuda
+  -V, --version                mostra a versão e a do prelúdio
+";
+
+/// Ação pedida pelos argumentos de linha de comando.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Compila e executa um programa.
+    Run(RunArgs),
+    /// Abre o modo interativo.
+    Repl(ReplArgs),
+    /// Mostra a ajuda.
+    Help,
+    /// Mostra a versão.
+    Version,
+}
+
+/// Argumentos da execução de um programa.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunArgs {
+    /// Caminho do arquivo do programa, ou `-` para a entrada padrão.
+    pub path: String,
+    /// Estratégia de avaliação.
+    pub strategy: Strategy,
+    /// Número máximo de passos de redução, se houver.
+    pub max_steps: Option<u64>,
+    /// Se o prelúdio padrão está habilitado.
+    pub prelude: bool,
+    /// Conversão dos `let` dentro de expressões.
+    pub let_lowering: LetLowering,
+    /// Se os erros podem ser coloridos, quando mostrados em um terminal.
+    pub color: bool,
+}
+
+/// Argumentos do modo interativo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplArgs {
+    /// Estratégia de avaliação inicial.
+    pub strategy: Strategy,
+    /// Número máximo de passos de redução de cada avaliação, se houver.
+    pub max_steps: Option<u64>,
+    /// Se o prelúdio padrão está habilitado.
+    pub prelude: bool,
+    /// Conversão dos `let` dentro de expressões.
+    pub let_lowering: LetLowering,
+    /// Se os erros podem ser coloridos, quando mostrados em um terminal.
+    pub color: bool,
+}
+
+/// Erro na leitura dos argumentos de linha de comando.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgsError {
+    /// Uma opção que exige valor apareceu por último.
+    MissingValue { option: String },
+    /// O valor de uma opção é inválido.
+    InvalidValue { option: String, value: String },
+    /// Opção desconhecida.
+    UnknownOption { option: String },
+    /// Mais de um arquivo foi passado.
+    UnexpectedArgument { argument: String },
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgsError::MissingValue { option } => {
+                write!(formatter, "A opção {} exige um valor", option)
+            }
+            ArgsError::InvalidValue { option, value } => write!(
+                formatter,
+                "Valor {:?} é inválido para a opção {}",
+                value, option
+            ),
+            ArgsError::UnknownOption { option } => {
+                write!(formatter, "Opção {} desconhecida", option)
+            }
+            ArgsError::UnexpectedArgument { argument } => {
+                write!(formatter, "Argumento {:?} inesperado", argument)
+            }
+        }
+    }
+}
+
+impl Error for ArgsError {}
+
+/// Nomes aceitos para as estratégias de avaliação, na ordem mostrada ao
+/// usuário.
+pub const STRATEGY_NAMES: &[(&str, Strategy)] = &[
+    ("normal", Strategy::NormalOrder),
+    ("applicative", Strategy::ApplicativeOrder),
+    ("name", Strategy::CallByName),
+    ("value", Strategy::CallByValue),
+    ("head", Strategy::HeadReduction),
+];
+
+/// Converte o nome de uma estratégia (ver [`STRATEGY_NAMES`]) na estratégia.
+pub fn parse_strategy(name: &str) -> Option<Strategy> {
+    STRATEGY_NAMES
+        .iter()
+        .find(|(strategy_name, _)| *strategy_name == name)
+        .map(|&(_, strategy)| strategy)
+}
+
+/// Retorna o nome de uma estratégia (ver [`STRATEGY_NAMES`]).
+pub fn strategy_name(strategy: Strategy) -> &'static str {
+    STRATEGY_NAMES
+        .iter()
+        .find(|(_, named)| *named == strategy)
+        .map_or("normal", |&(name, _)| name)
+}
+
+/// Lê os argumentos de linha de comando, sem o nome do programa.
+///
+/// - `args`: argumentos de linha de comando
+pub fn parse<I>(args: I) -> Result<Command, ArgsError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let mut path = None;
+    let mut strategy = Strategy::default();
+    let mut max_steps = None;
+    let mut prelude = true;
+    let mut let_lowering = LetLowering::default();
+    let mut color = true;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "--no-prelude" => prelude = false,
+            "--let-redex" => let_lowering = LetLowering::Redex,
+            "--no-color" => color = false,
+
+            "-s" | "--strategy" => {
+                let value = require_value(&arg, args.next())?;
+                strategy = parse_strategy(&value)
+                    .ok_or(ArgsError::InvalidValue { option: arg, value })?;
+            }
+
+            "-n" | "--max-steps" => {
+                let value = require_value(&arg, args.next())?;
+                match value.parse() {
+                    Ok(steps) => max_steps = Some(steps),
+                    Err(_) => {
+                        return Err(ArgsError::InvalidValue {
+                            option: arg,
+                            value,
+                        })
+                    }
+                }
+            }
+
+            option if option.starts_with('-') && option != "-" => {
+                return Err(ArgsError::UnknownOption { option: arg.clone() })
+            }
+
+            _ if path.is_some() => {
+                return Err(ArgsError::UnexpectedArgument { argument: arg })
+            }
+
+            _ => path = Some(arg),
+        }
+    }
+
+    Ok(match path {
+        Some(path) => Command::Run(RunArgs {
+            path,
+            strategy,
+            max_steps,
+            prelude,
+            let_lowering,
+            color,
+        }),
+        None => Command::Repl(ReplArgs {
+            strategy,
+            max_steps,
+            prelude,
+            let_lowering,
+            color,
+        }),
+    })
+}
+
+/// Garante que uma opção que exige valor tenha recebido um.
+///
+/// - `option`: opção lida
+/// - `value`: próximo argumento, se houver
+fn require_value(
+    option: &str,
+    value: Option<String>,
+) -> Result<String, ArgsError> {
+    value.ok_or_else(|| ArgsError::MissingValue { option: option.to_owned() })
+}
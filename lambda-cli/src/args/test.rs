@@ -0,0 +1,106 @@
+use super::{
+    parse, parse_strategy, strategy_name, ArgsError, Command, ReplArgs, RunArgs,
+};
+use lambda::compiler::expansor::LetLowering;
+use lambda::strategy::Strategy;
+
+fn parse_strs(args: &[&str]) -> Result<Command, ArgsError> {
+    parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn only_file() {
+    assert_eq!(
+        parse_strs(&["fact.lam"]),
+        Ok(Command::Run(RunArgs {
+            path: String::from("fact.lam"),
+            strategy: Strategy::NormalOrder,
+            max_steps: None,
+            prelude: true,
+            let_lowering: LetLowering::Substitution,
+            color: true,
+        }))
+    );
+}
+
+#[test]
+fn all_options() {
+    assert_eq!(
+        parse_strs(&[
+            "-s",
+            "value",
+            "--max-steps",
+            "100",
+            "--no-prelude",
+            "--let-redex",
+            "--no-color",
+            "-"
+        ]),
+        Ok(Command::Run(RunArgs {
+            path: String::from("-"),
+            strategy: Strategy::CallByValue,
+            max_steps: Some(100),
+            prelude: false,
+            let_lowering: LetLowering::Redex,
+            color: false,
+        }))
+    );
+}
+
+#[test]
+fn repl_without_file() {
+    assert_eq!(
+        parse_strs(&["-s", "head"]),
+        Ok(Command::Repl(ReplArgs {
+            strategy: Strategy::HeadReduction,
+            max_steps: None,
+            prelude: true,
+            let_lowering: LetLowering::Substitution,
+            color: true,
+        }))
+    );
+}
+
+#[test]
+fn help_and_version() {
+    assert_eq!(parse_strs(&["fact.lam", "--help"]), Ok(Command::Help));
+    assert_eq!(parse_strs(&["-V"]), Ok(Command::Version));
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        parse_strs(&["a.lam", "-n"]),
+        Err(ArgsError::MissingValue { option: String::from("-n") })
+    );
+    assert_eq!(
+        parse_strs(&["a.lam", "--strategy", "lazy"]),
+        Err(ArgsError::InvalidValue {
+            option: String::from("--strategy"),
+            value: String::from("lazy"),
+        })
+    );
+    assert_eq!(
+        parse_strs(&["a.lam", "-n", "-1"]),
+        Err(ArgsError::InvalidValue {
+            option: String::from("-n"),
+            value: String::from("-1"),
+        })
+    );
+    assert_eq!(
+        parse_strs(&["--verbose", "a.lam"]),
+        Err(ArgsError::UnknownOption { option: String::from("--verbose") })
+    );
+    assert_eq!(
+        parse_strs(&["a.lam", "b.lam"]),
+        Err(ArgsError::UnexpectedArgument { argument: String::from("b.lam") })
+    );
+}
+
+#[test]
+fn strategy_names_round_trip() {
+    for &name in &["normal", "applicative", "name", "value", "head"] {
+        let strategy = parse_strategy(name).unwrap();
+        assert_eq!(strategy_name(strategy), name);
+    }
+}
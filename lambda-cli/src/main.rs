@@ -0,0 +1,121 @@
+//! Executa programas do cálculo lambda a partir do terminal.
+//!
+//! ```text
+//! pinguim-lambda [OPÇÕES] [ARQUIVO]
+//! ```
+//!
+//! Sem arquivo, abre o modo interativo (veja [`repl`]). Veja
+//! [`args::USAGE`] para a lista de opções.
+
+mod args;
+mod repl;
+mod report;
+
+use args::{Command, RunArgs, USAGE};
+use lambda::compiler::import::FsLoader;
+use lambda::compiler::report::render_diagnostics;
+use lambda::compiler::{compile_file, compile_with_loader, prelude, Options};
+use lambda::interpreter::{Interpreter, Limits};
+use std::io::{self, Read};
+use std::{env, fs, process};
+
+/// Código de saída quando o programa chega à forma normal.
+const EXIT_SUCCESS: i32 = 0;
+/// Código de saída para erros de argumentos, leitura ou compilação.
+const EXIT_ERROR: i32 = 1;
+/// Código de saída quando a execução para antes da forma normal.
+const EXIT_INCOMPLETE: i32 = 2;
+
+fn main() {
+    let code = match args::parse(env::args().skip(1)) {
+        Ok(Command::Help) => {
+            print!("{}", USAGE);
+            EXIT_SUCCESS
+        }
+        Ok(Command::Version) => {
+            println!(
+                "pinguim-lambda {} (prelúdio {})",
+                env!("CARGO_PKG_VERSION"),
+                prelude::VERSION
+            );
+            EXIT_SUCCESS
+        }
+        Ok(Command::Run(run_args)) => run(&run_args),
+        Ok(Command::Repl(repl_args)) => match repl::run(&repl_args) {
+            Ok(()) => EXIT_SUCCESS,
+            Err(error) => {
+                eprintln!("erro: {}", error);
+                EXIT_ERROR
+            }
+        },
+        Err(error) => {
+            eprintln!("erro: {}\n", error);
+            eprint!("{}", USAGE);
+            EXIT_ERROR
+        }
+    };
+
+    process::exit(code);
+}
+
+/// Compila e executa o programa, mostrando o resultado. Retorna o código de
+/// saída do processo.
+///
+/// - `run_args`: argumentos da execução
+fn run(run_args: &RunArgs) -> i32 {
+    let from_stdin = run_args.path == "-";
+
+    let source = if from_stdin {
+        let mut source = String::new();
+        io::stdin().read_to_string(&mut source).map(|_| source)
+    } else {
+        fs::read_to_string(&run_args.path)
+    };
+    let source = match source {
+        Ok(source) => source,
+        Err(error) => {
+            let path = &run_args.path;
+            eprintln!("erro: não foi possível ler {}: {}", path, error);
+            return EXIT_ERROR;
+        }
+    };
+
+    let options = Options {
+        prelude: run_args.prelude,
+        let_lowering: run_args.let_lowering,
+    };
+    let loader = FsLoader::default();
+    let compiled = if from_stdin {
+        compile_with_loader(&source, &loader, &options)
+    } else {
+        compile_file(&run_args.path, &loader, &options)
+    };
+    let value = match compiled {
+        Ok(value) => value,
+        Err(diagnostics) => {
+            let file = if from_stdin { None } else { Some(&*run_args.path) };
+            let options =
+                report::render_options(file, run_args.color, &io::stderr());
+            eprint!("{}", render_diagnostics(&source, &diagnostics, &options));
+            return EXIT_ERROR;
+        }
+    };
+
+    let mut interpreter = Interpreter::with_strategy(value, run_args.strategy);
+    // Sem limite de passos, um termo que se repete rodaria para sempre.
+    interpreter.set_cycle_detection(true);
+    let limits = Limits { max_steps: run_args.max_steps, ..Limits::default() };
+    let outcome = interpreter.run_bounded(&limits);
+
+    println!("{}", interpreter.output());
+    println!("passos: {}", interpreter.steps());
+    print!("{}", report::render_descriptions(interpreter.output()));
+
+    match report::outcome_warning(outcome) {
+        Some(warning) => {
+            eprintln!("aviso: {}", warning);
+            EXIT_INCOMPLETE
+        }
+        None => EXIT_SUCCESS,
+    }
+}
@@ -0,0 +1,513 @@
+//! Esse módulo implementa o modo interativo (REPL): definições são
+//! adicionadas uma a uma com `let nome = expr`, expressões são avaliadas
+//! diretamente e comandos como `:step` e `:run` controlam a redução do último
+//! termo avaliado.
+
+#[cfg(test)]
+mod test;
+
+use crate::args::{parse_strategy, strategy_name, ReplArgs, STRATEGY_NAMES};
+use crate::report::{outcome_warning, render_descriptions, render_options};
+use lambda::compiler::expansor::recursion::{sort_bindings, tie_recursion};
+use lambda::compiler::expansor::{
+    check_bindings, expand_with_lowering, LetLowering,
+};
+use lambda::compiler::import::{load_imports, FsLoader};
+use lambda::compiler::lexer::generate_tokens;
+use lambda::compiler::lexer::token::{Token, TokenType};
+use lambda::compiler::parser::ast::{Binding, Import};
+use lambda::compiler::parser::{parse, parse_bindings};
+use lambda::compiler::prelude;
+use lambda::compiler::report::{render_diagnostics, RenderOptions};
+use lambda::interpreter::{Interpreter, Limits, Outcome};
+use lambda::strategy::Strategy;
+use pinguim_language::error::Diagnostics;
+use pinguim_language::position::Span;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Write};
+
+/// Limite de passos de cada avaliação quando nenhum é passado nos
+/// argumentos, para que termos sem forma normal não travem o REPL.
+pub const DEFAULT_MAX_STEPS: u64 = 100_000;
+
+/// Texto de ajuda do comando `:help`.
+pub const HELP: &str = "\
+Entradas aceitas:
+  let nome = expr       adiciona uma definição (várias separadas por `;`)
+  let rec nome = expr   adiciona definições que podem usar umas às outras
+  expr                  avalia uma expressão usando as definições
+  :step                 dá um passo de redução no último termo avaliado
+  :run [N]              dá N passos (ou reduz até a forma normal)
+  :reset                volta o último termo ao início da redução
+  :trace                liga/desliga a exibição de cada passo
+  :strategy [nome]      mostra ou muda a estratégia de avaliação
+  :load arquivo         carrega as definições de um arquivo
+  :env                  lista as definições feitas nesta sessão
+  :help                 mostra esta ajuda
+  :quit                 sai do REPL
+";
+
+/// Resposta a uma entrada do REPL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    /// Texto a ser mostrado (possivelmente vazio).
+    Output(String),
+    /// O usuário pediu para sair.
+    Quit,
+}
+
+/// Uma definição feita na sessão, para o comando `:env`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    ///
+    /// - `name`: nome da definição
+    name: String,
+    ///
+    /// - `origin`: código da definição, ou de onde ela foi carregada
+    origin: String,
+}
+
+/// Estado de uma sessão do REPL.
+pub struct Session {
+    ///
+    /// - `bindings`: prelúdio e definições da sessão, na ordem de declaração
+    bindings: Vec<Binding>,
+    ///
+    /// - `entries`: definições feitas pelo usuário, na ordem de declaração
+    entries: Vec<Entry>,
+    ///
+    /// - `interpreter`: interpretador do último termo avaliado, se houver
+    interpreter: Option<Interpreter>,
+    ///
+    /// - `strategy`: estratégia de avaliação atual
+    strategy: Strategy,
+    ///
+    /// - `max_steps`: limite de passos de cada avaliação
+    max_steps: u64,
+    ///
+    /// - `trace`: se cada passo de redução deve ser mostrado
+    trace: bool,
+    ///
+    /// - `let_lowering`: conversão dos `let` dentro de expressões
+    let_lowering: LetLowering,
+    ///
+    /// - `render_options`: formatação dos erros
+    render_options: RenderOptions,
+}
+
+impl Session {
+    /// Cria uma sessão vazia (com o prelúdio, se habilitado).
+    ///
+    /// - `args`: argumentos do modo interativo
+    pub fn new(args: &ReplArgs) -> Self {
+        let bindings =
+            if args.prelude { prelude::bindings() } else { Vec::new() };
+
+        Self {
+            bindings,
+            entries: Vec::new(),
+            interpreter: None,
+            strategy: args.strategy,
+            max_steps: args.max_steps.unwrap_or(DEFAULT_MAX_STEPS),
+            trace: false,
+            let_lowering: args.let_lowering,
+            render_options: RenderOptions { file: None, colored: args.color },
+        }
+    }
+
+    /// Executa uma linha de entrada, retornando a resposta.
+    ///
+    /// - `line`: linha digitada pelo usuário
+    pub fn execute(&mut self, line: &str) -> Reply {
+        let line = line.trim();
+
+        if line.is_empty() {
+            return Reply::Output(String::new());
+        }
+
+        if let Some(command) = line.strip_prefix(':') {
+            let mut words = command.split_whitespace();
+            let name = words.next().unwrap_or("");
+            let args = words.collect::<Vec<_>>();
+            return self.command(name, &args);
+        }
+
+        Reply::Output(self.input(line))
+    }
+
+    /// Executa um comando (linha começando com `:`).
+    ///
+    /// - `name`: nome do comando, sem o `:`
+    /// - `args`: argumentos do comando
+    fn command(&mut self, name: &str, args: &[&str]) -> Reply {
+        let output = match (name, args) {
+            ("q", []) | ("quit", []) => return Reply::Quit,
+            ("h", []) | ("help", []) => String::from(HELP),
+            ("step", []) => self.step(),
+            ("run", []) => self.run(None),
+            ("run", [count]) => match count.parse() {
+                Ok(count) => self.run(Some(count)),
+                Err(_) => format!("Número de passos inválido: {}\n", count),
+            },
+            ("reset", []) => self.reset(),
+            ("trace", []) => {
+                self.trace = !self.trace;
+                let state = if self.trace { "ligada" } else { "desligada" };
+                format!("Exibição de passos {}\n", state)
+            }
+            ("strategy", []) => {
+                format!("Estratégia: {}\n", strategy_name(self.strategy))
+            }
+            ("strategy", [strategy]) => self.set_strategy(strategy),
+            ("load", [path]) => self.load(path),
+            ("env", []) => self.env(),
+            _ => format!(
+                "Comando :{} inválido ou com argumentos errados; use :help\n",
+                name
+            ),
+        };
+
+        Reply::Output(output)
+    }
+
+    /// Trata uma linha que não é comando: uma definição (`let` sem `in` no
+    /// nível mais externo) ou uma expressão a ser avaliada.
+    ///
+    /// - `line`: linha digitada pelo usuário
+    fn input(&mut self, line: &str) -> String {
+        let mut diagnostics = Diagnostics::new();
+        let tokens = generate_tokens(line, &mut diagnostics);
+        if diagnostics.is_err() {
+            return self.render_errors(line, &diagnostics);
+        }
+
+        let is_definition = tokens
+            .first()
+            .is_some_and(|token| token.token_type == TokenType::Let)
+            && !has_top_level_in(&tokens[1..]);
+
+        if is_definition {
+            self.define(line, tokens[1..].to_vec())
+        } else {
+            self.evaluate(line, tokens)
+        }
+    }
+
+    /// Adiciona as definições de uma linha `let nome = expr; ...` ou
+    /// `let rec nome = expr; ...`.
+    ///
+    /// - `line`: linha digitada pelo usuário
+    /// - `tokens`: tokens da linha, sem o `let`
+    fn define(&mut self, line: &str, mut tokens: Vec<Token>) -> String {
+        let mut diagnostics = Diagnostics::new();
+
+        let recursive = tokens
+            .first()
+            .is_some_and(|token| token.token_type == TokenType::Rec);
+        if recursive {
+            tokens.remove(0);
+        }
+
+        // Onde termina cada definição no código: no `;` seguinte ou no fim.
+        // Os `;` de um `let` aninhado, até o seu `in`, não contam.
+        let mut ends = Vec::new();
+        let mut nested_lets = 0u32;
+        for token in &tokens {
+            match token.token_type {
+                TokenType::Let => nested_lets += 1,
+                TokenType::In => nested_lets = nested_lets.saturating_sub(1),
+                TokenType::Semicolon if nested_lets == 0 => {
+                    ends.push(token.span.start.utf8_index)
+                }
+                _ => (),
+            }
+        }
+
+        let bindings = match parse_bindings(tokens, &mut diagnostics) {
+            Some(bindings) if diagnostics.is_ok() => bindings,
+            _ => return self.render_errors(line, &diagnostics),
+        };
+        let bindings = if recursive {
+            tie_recursion(&bindings)
+        } else {
+            match sort_bindings(&bindings, &mut diagnostics) {
+                Some(bindings) => bindings,
+                None => return self.render_errors(line, &diagnostics),
+            }
+        };
+
+        if !check_bindings(&bindings, &self.bindings, &mut diagnostics) {
+            return self.render_errors(line, &diagnostics);
+        }
+
+        let mut output = String::new();
+        for binding in bindings {
+            let start = binding.name.span.start.utf8_index;
+            let end = ends
+                .iter()
+                .copied()
+                .find(|&end| end > start)
+                .unwrap_or(line.len());
+            let name = binding.name.content.clone();
+            let origin = line[start..end].trim().to_owned();
+            let _ = writeln!(output, "{} definido", name);
+            self.entries.push(Entry { name, origin });
+            self.bindings.push(binding);
+        }
+
+        output
+    }
+
+    /// Compila e avalia uma expressão (ou um programa `let ... in`), que
+    /// passa a ser o termo controlado pelos comandos de redução.
+    ///
+    /// - `line`: linha digitada pelo usuário
+    /// - `tokens`: tokens da linha
+    fn evaluate(&mut self, line: &str, tokens: Vec<Token>) -> String {
+        let mut diagnostics = Diagnostics::new();
+
+        let program = match parse(tokens, &mut diagnostics) {
+            Some(program) if diagnostics.is_ok() => program,
+            _ => return self.render_errors(line, &diagnostics),
+        };
+
+        let bindings = load_imports(
+            &program.imports,
+            None,
+            &FsLoader::default(),
+            self.bindings.clone(),
+            &mut diagnostics,
+        );
+        let value = match expand_with_lowering(
+            &program,
+            &bindings,
+            self.let_lowering,
+            &mut diagnostics,
+        ) {
+            Some(value) if diagnostics.is_ok() => value,
+            _ => return self.render_errors(line, &diagnostics),
+        };
+
+        let mut interpreter = Interpreter::with_strategy(value, self.strategy);
+        interpreter.set_cycle_detection(true);
+        self.interpreter = Some(interpreter);
+        self.run(None)
+    }
+
+    /// Dá um passo de redução no termo atual.
+    fn step(&mut self) -> String {
+        let interpreter = match &mut self.interpreter {
+            Some(interpreter) => interpreter,
+            None => return String::from(NO_TERM),
+        };
+
+        if interpreter.run_step() {
+            format!("{}\n", interpreter.output())
+        } else {
+            String::from("O termo já está na forma normal\n")
+        }
+    }
+
+    /// Reduz o termo atual por um número de passos ou, se `count` for
+    /// `None`, até a forma normal (respeitando o limite de passos).
+    ///
+    /// - `count`: número de passos a serem dados
+    fn run(&mut self, count: Option<u64>) -> String {
+        let max_steps = self.max_steps;
+        let trace = self.trace;
+        let interpreter = match &mut self.interpreter {
+            Some(interpreter) => interpreter,
+            None => return String::from(NO_TERM),
+        };
+
+        let mut output = String::new();
+        let max_steps = count.unwrap_or(max_steps);
+        let outcome = if trace {
+            let mut steps = 0;
+            while steps < max_steps
+                && interpreter.cycle_period().is_none()
+                && interpreter.run_step()
+            {
+                steps += 1;
+                let _ = writeln!(output, "-> {}", interpreter.output());
+            }
+            let strategy = interpreter.strategy();
+            match interpreter.cycle_period() {
+                Some(period) => Outcome::Cycle { period },
+                None => match interpreter.output().find_redex(strategy) {
+                    Some(_) => Outcome::StepsExhausted,
+                    None => Outcome::NormalForm,
+                },
+            }
+        } else {
+            let limits =
+                Limits { max_steps: Some(max_steps), ..Limits::default() };
+            let outcome = interpreter.run_bounded(&limits);
+            let _ = writeln!(output, "{}", interpreter.output());
+            outcome
+        };
+
+        let _ = writeln!(output, "(passos: {})", interpreter.steps());
+        output.push_str(&render_descriptions(interpreter.output()));
+
+        // Com uma quantidade pedida de passos, parar antes da forma normal é
+        // o esperado.
+        if count.is_none() {
+            if let Some(warning) = outcome_warning(outcome) {
+                let _ = writeln!(output, "Aviso: {}", warning);
+            }
+        }
+
+        output
+    }
+
+    /// Volta o termo atual ao início da redução.
+    fn reset(&mut self) -> String {
+        match &mut self.interpreter {
+            Some(interpreter) => {
+                interpreter.reset();
+                format!("{}\n", interpreter.output())
+            }
+            None => String::from(NO_TERM),
+        }
+    }
+
+    /// Muda a estratégia de avaliação, inclusive do termo atual.
+    ///
+    /// - `name`: nome da estratégia
+    fn set_strategy(&mut self, name: &str) -> String {
+        match parse_strategy(name) {
+            Some(strategy) => {
+                self.strategy = strategy;
+                if let Some(interpreter) = &mut self.interpreter {
+                    interpreter.set_strategy(strategy);
+                }
+                format!("Estratégia: {}\n", name)
+            }
+            None => {
+                let names = STRATEGY_NAMES
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>();
+                format!(
+                    "Estratégia {} desconhecida; use uma de: {}\n",
+                    name,
+                    names.join(", ")
+                )
+            }
+        }
+    }
+
+    /// Carrega as definições de um arquivo (e dos arquivos que ele importa).
+    ///
+    /// - `path`: caminho do arquivo
+    fn load(&mut self, path: &str) -> String {
+        let mut diagnostics = Diagnostics::new();
+        let import = Import { path: path.to_owned(), span: Span::default() };
+        let previous = self.bindings.len();
+        let bindings = load_imports(
+            &[import],
+            None,
+            &FsLoader::default(),
+            self.bindings.clone(),
+            &mut diagnostics,
+        );
+
+        if diagnostics.is_err() {
+            // Os erros são de outro arquivo: não há trecho desta linha a
+            // mostrar.
+            return self.render_errors("", &diagnostics);
+        }
+
+        let mut output = String::new();
+        for binding in &bindings[previous..] {
+            let name = binding.name.content.clone();
+            let _ = writeln!(output, "{} definido", name);
+            let origin = format!("-- carregado de {}", path);
+            self.entries.push(Entry { name, origin });
+        }
+        self.bindings = bindings;
+
+        output
+    }
+
+    /// Formata os erros de uma linha digitada pelo usuário.
+    ///
+    /// - `line`: linha onde os erros foram encontrados
+    /// - `diagnostics`: erros encontrados
+    fn render_errors(&self, line: &str, diagnostics: &Diagnostics) -> String {
+        render_diagnostics(line, diagnostics, &self.render_options)
+    }
+
+    /// Lista as definições feitas na sessão.
+    fn env(&self) -> String {
+        if self.entries.is_empty() {
+            return String::from("Nenhuma definição nesta sessão\n");
+        }
+
+        let mut output = String::new();
+        for entry in &self.entries {
+            if entry.origin.starts_with("--") {
+                let _ = writeln!(output, "{} {}", entry.name, entry.origin);
+            } else {
+                let _ = writeln!(output, "{}", entry.origin);
+            }
+        }
+        output
+    }
+}
+
+/// Resposta dos comandos de redução quando nenhum termo foi avaliado.
+const NO_TERM: &str = "Nenhum termo avaliado ainda\n";
+
+/// Testa se os tokens de uma linha, depois do `let` inicial, têm um `in` que
+/// fecha esse `let`. Cada `let` aninhado consome o próximo `in`, então
+/// `let f = \n. let sq = mult n n; in plus sq sq` continua uma definição.
+///
+/// - `tokens`: tokens da linha, sem o `let` inicial
+fn has_top_level_in(tokens: &[Token]) -> bool {
+    let mut nested_lets = 0u32;
+
+    for token in tokens {
+        match token.token_type {
+            TokenType::Let => nested_lets += 1,
+            TokenType::In if nested_lets == 0 => return true,
+            TokenType::In => nested_lets -= 1,
+            _ => (),
+        }
+    }
+
+    false
+}
+
+/// Roda o REPL na entrada e saída padrão, até o fim da entrada ou `:quit`.
+///
+/// - `args`: argumentos do modo interativo
+pub fn run(args: &ReplArgs) -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    let color = render_options(None, args.color, &stdout).colored;
+    let mut session = Session::new(&ReplArgs { color, ..args.clone() });
+    let mut lines = stdin.lock().lines();
+
+    writeln!(stdout, "Pinguim Lambda {}", env!("CARGO_PKG_VERSION"))?;
+    writeln!(stdout, "Digite :help para ajuda, :quit para sair.")?;
+
+    loop {
+        write!(stdout, "λ> ")?;
+        stdout.flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+
+        match session.execute(&line) {
+            Reply::Output(output) => write!(stdout, "{}", output)?,
+            Reply::Quit => break,
+        }
+    }
+
+    writeln!(stdout)?;
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use super::{Reply, Session, NO_TERM};
+use crate::args::ReplArgs;
+use lambda::compiler::expansor::LetLowering;
+use lambda::strategy::Strategy;
+
+fn session() -> Session {
+    Session::new(&ReplArgs {
+        strategy: Strategy::NormalOrder,
+        max_steps: None,
+        prelude: true,
+        let_lowering: LetLowering::Substitution,
+        color: false,
+    })
+}
+
+fn output(session: &mut Session, line: &str) -> String {
+    match session.execute(line) {
+        Reply::Output(output) => output,
+        Reply::Quit => panic!("REPL saiu com a entrada {:?}", line),
+    }
+}
+
+#[test]
+fn define_and_evaluate() {
+    let mut session = session();
+
+    assert_eq!(
+        output(&mut session, "let two = \\f x. f (f x); three = succ two"),
+        "two definido\nthree definido\n"
+    );
+    let result = output(&mut session, "plus two three");
+    assert!(result.contains("numeral de Church 5"), "{}", result);
+}
+
+#[test]
+fn redefinition_keeps_earlier_uses() {
+    let mut session = session();
+
+    output(&mut session, "let a = 1");
+    output(&mut session, "let b = succ a");
+    output(&mut session, "let a = 5");
+
+    let result = output(&mut session, "b");
+    assert!(result.contains("numeral de Church 2"), "{}", result);
+}
+
+#[test]
+fn let_in_is_evaluated() {
+    let mut session = session();
+    let result = output(&mut session, "let x = 2; in mult x x");
+    assert!(result.contains("numeral de Church 4"), "{}", result);
+    assert_eq!(
+        output(&mut session, ":env"),
+        "Nenhuma definição nesta sessão\n"
+    );
+}
+
+#[test]
+fn nested_let_in_definition() {
+    let mut session = session();
+
+    assert_eq!(
+        output(&mut session, "let f = \\n. let sq = mult n n; in plus sq sq"),
+        "f definido\n"
+    );
+    let result = output(&mut session, "f 3");
+    assert!(result.contains("numeral de Church 18"), "{}", result);
+}
+
+#[test]
+fn recursive_definitions() {
+    let mut session = session();
+
+    assert_eq!(
+        output(
+            &mut session,
+            "let rec sum = \\n. if (is_zero n) 0 (plus n (sum (pred n)))"
+        ),
+        "sum definido\n"
+    );
+    let result = output(&mut session, "sum 3");
+    assert!(result.contains("numeral de Church 6"), "{}", result);
+
+    let error = output(&mut session, "let loop = \\x. loop x");
+    assert!(error.contains("fora de um `let rec`"), "{}", error);
+}
+
+#[test]
+fn stepping_commands() {
+    let mut session = session();
+
+    assert_eq!(output(&mut session, ":step"), NO_TERM);
+
+    assert_eq!(
+        output(&mut session, "(\\x. x) ((\\y. y) z)"),
+        "z\n(passos: 2)\n"
+    );
+    assert_eq!(output(&mut session, ":reset"), "(\\x. x) ((\\y. y) z)\n");
+    assert_eq!(output(&mut session, ":step"), "(\\y. y) z\n");
+    assert_eq!(output(&mut session, ":run 5"), "z\n(passos: 2)\n");
+    assert_eq!(
+        output(&mut session, ":step"),
+        "O termo já está na forma normal\n"
+    );
+}
+
+#[test]
+fn trace_and_strategy() {
+    let mut session = session();
+
+    assert_eq!(output(&mut session, ":strategy"), "Estratégia: normal\n");
+    assert_eq!(output(&mut session, ":strategy name"), "Estratégia: name\n");
+    assert_eq!(output(&mut session, ":trace"), "Exibição de passos ligada\n");
+
+    assert_eq!(
+        output(&mut session, "(\\x. \\y. x) ((\\z. z) a)"),
+        "-> \\y. (\\z. z) a\n(passos: 1)\n"
+    );
+
+    let error = output(&mut session, ":strategy lazy");
+    assert!(error.starts_with("Estratégia lazy desconhecida"), "{}", error);
+}
+
+#[test]
+fn step_limit_warning() {
+    let mut session = Session::new(&ReplArgs {
+        strategy: Strategy::NormalOrder,
+        max_steps: Some(10),
+        prelude: false,
+        let_lowering: LetLowering::Substitution,
+        color: false,
+    });
+
+    let result = output(&mut session, "(\\x. x x x) (\\x. x x x)");
+    assert!(
+        result.ends_with(
+            "(passos: 10)\nAviso: limite de passos atingido antes da forma \
+             normal\n"
+        ),
+        "{}",
+        result
+    );
+}
+
+#[test]
+fn cycle_warning() {
+    let mut session = session();
+
+    assert_eq!(
+        output(&mut session, "(\\x. x x) (\\x. x x)"),
+        "(\\x. x x) \\x. x x\n(passos: 1)\nAviso: a redução repete a \
+         cada 1 passos\n"
+    );
+
+    output(&mut session, ":reset");
+    output(&mut session, ":trace");
+    assert_eq!(
+        output(&mut session, ":run"),
+        "-> (\\x. x x) \\x. x x\n(passos: 1)\nAviso: a redução repete a \
+         cada 1 passos\n"
+    );
+}
+
+#[test]
+fn env_lists_definitions() {
+    let mut session = session();
+
+    output(&mut session, "let id2 = \\x. x; k = \\x y. x");
+    assert_eq!(output(&mut session, ":env"), "id2 = \\x. x\nk = \\x y. x\n");
+}
+
+#[test]
+fn env_keeps_nested_let() {
+    let mut session = session();
+
+    output(
+        &mut session,
+        "let f = \\n. let sq = mult n n; in plus sq sq; g = f",
+    );
+    assert_eq!(
+        output(&mut session, ":env"),
+        "f = \\n. let sq = mult n n; in plus sq sq\ng = f\n"
+    );
+}
+
+#[test]
+fn errors_are_rendered() {
+    let mut session = session();
+
+    let error = output(&mut session, "(x");
+    assert!(
+        error.starts_with("erro: Parentesis aberto mas não fechado"),
+        "{}",
+        error
+    );
+
+    let error = output(&mut session, ":load nao-existe.lam");
+    assert!(error.starts_with("erro: Não foi possível importar"), "{}", error);
+
+    let error = output(&mut session, ":run x");
+    assert_eq!(error, "Número de passos inválido: x\n");
+
+    let error = output(&mut session, ":frobnicate");
+    assert!(error.starts_with("Comando :frobnicate inválido"), "{}", error);
+}
+
+#[test]
+fn quit() {
+    let mut session = session();
+    assert_eq!(session.execute(":quit"), Reply::Quit);
+}
@@ -0,0 +1,70 @@
+//! Esse módulo formata para o terminal os resultados da execução. Os erros
+//! de compilação são formatados por [`lambda::compiler::report`].
+
+#[cfg(test)]
+mod test;
+
+use lambda::compiler::report::RenderOptions;
+use lambda::encoding::Description;
+use lambda::interpreter::Outcome;
+use lambda::value::Value;
+use std::fmt::Write;
+use std::io::IsTerminal;
+
+/// Opções de formatação dos erros para uma saída do processo: coloridos
+/// apenas se permitido pelos argumentos e se a saída for um terminal.
+///
+/// - `file`: nome do arquivo do código-fonte, se houver
+/// - `color`: se os argumentos permitem cores
+/// - `stream`: saída onde os erros serão escritos
+pub fn render_options(
+    file: Option<&str>,
+    color: bool,
+    stream: &impl IsTerminal,
+) -> RenderOptions {
+    RenderOptions {
+        file: file.map(str::to_owned),
+        colored: color && stream.is_terminal(),
+    }
+}
+
+/// Formata as leituras do termo como numeral ou booleano de Church, uma por
+/// linha (ou nada, se o termo não for nenhum dos dois).
+///
+/// - `value`: termo a ser lido
+pub fn render_descriptions(value: &Value) -> String {
+    let mut output = String::new();
+
+    for description in value.describe() {
+        match description {
+            Description::ChurchNumeral(_) | Description::ChurchBoolean(_) => {
+                let _ = writeln!(output, "{}", description);
+            }
+            _ => (),
+        }
+    }
+
+    output
+}
+
+/// Retorna um aviso explicando por que a execução parou, se ela parou antes
+/// da forma normal.
+///
+/// - `outcome`: resultado da execução
+pub fn outcome_warning(outcome: Outcome) -> Option<String> {
+    match outcome {
+        Outcome::NormalForm => None,
+        Outcome::StepsExhausted => Some(String::from(
+            "limite de passos atingido antes da forma normal",
+        )),
+        Outcome::SizeExceeded => {
+            Some(String::from("termo excedeu o tamanho máximo"))
+        }
+        Outcome::TimedOut => {
+            Some(String::from("tempo máximo esgotado antes da forma normal"))
+        }
+        Outcome::Cycle { period } => {
+            Some(format!("a redução repete a cada {} passos", period))
+        }
+    }
+}
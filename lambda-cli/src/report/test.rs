@@ -0,0 +1,22 @@
+use super::{render_descriptions, render_options};
+use lambda::compiler::compile;
+use std::io;
+
+#[test]
+fn render_numeral_and_boolean() {
+    let zero = compile("\\s z. z").unwrap();
+    assert_eq!(
+        render_descriptions(&zero),
+        "numeral de Church 0\nbooleano de Church falso\n"
+    );
+
+    let pair = compile("\\p. p a b").unwrap();
+    assert_eq!(render_descriptions(&pair), "");
+}
+
+#[test]
+fn render_options_without_color() {
+    let options = render_options(Some("main.lam"), false, &io::stderr());
+    assert_eq!(options.file.as_deref(), Some("main.lam"));
+    assert!(!options.colored);
+}
@@ -0,0 +1,415 @@
+//! Esse módulo analisa o código de um arquivo aberto no editor e implementa
+//! as funcionalidades do servidor sobre essa análise: diagnósticos, ir para
+//! definição, encontrar referências, hover e tokens semânticos.
+//!
+//! Nada aqui faz entrada ou saída; a comunicação com o editor fica no módulo
+//! [`crate::server`].
+
+#[cfg(test)]
+mod test;
+
+use lambda::compiler::expansor::expand_with_prelude;
+use lambda::compiler::import::{error::InFile, load_imports, Loader};
+use lambda::compiler::lexer::generate_tokens;
+use lambda::compiler::lexer::token::{Token, TokenType};
+use lambda::compiler::parser::ast::{Expr, Program, Symbol};
+use lambda::compiler::parser::parse;
+use lambda::compiler::{compile_with_loader, prelude, Options};
+use lambda::interpreter::{Interpreter, Limits, Outcome};
+use lsp_types::{
+    Diagnostic, DiagnosticSeverity, Position as LspPosition, Range,
+    SemanticToken, SemanticTokenModifier, SemanticTokenType,
+};
+use pinguim_language::error::Diagnostics;
+use pinguim_language::position::{Position, Span};
+use std::fmt::Write;
+
+/// Limite de passos usado para calcular a forma normal mostrada no hover.
+pub const HOVER_MAX_STEPS: u64 = 10_000;
+
+/// Tipos de tokens semânticos, na ordem da legenda enviada ao editor.
+pub const TOKEN_TYPES: &[SemanticTokenType] = &[
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::NUMBER,
+    SemanticTokenType::STRING,
+    SemanticTokenType::OPERATOR,
+    SemanticTokenType::COMMENT,
+];
+
+/// Modificadores de tokens semânticos, na ordem da legenda enviada ao editor.
+pub const TOKEN_MODIFIERS: &[SemanticTokenModifier] =
+    &[SemanticTokenModifier::DECLARATION];
+
+/// Índices em [`TOKEN_TYPES`].
+const KEYWORD: u32 = 0;
+const FUNCTION: u32 = 1;
+const VARIABLE: u32 = 2;
+const NUMBER: u32 = 3;
+const STRING: u32 = 4;
+const OPERATOR: u32 = 5;
+const COMMENT: u32 = 6;
+
+/// Bit do modificador `declaration` (ver [`TOKEN_MODIFIERS`]).
+const DECLARATION: u32 = 1;
+
+/// Converte posições do compilador em posições do LSP e vice-versa. O LSP
+/// conta colunas em unidades UTF-16 a partir de 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    ///
+    /// - `line_starts`: índice UTF-16 do início de cada linha
+    line_starts: Vec<usize>,
+    ///
+    /// - `length`: tamanho do texto em unidades UTF-16
+    length: usize,
+}
+
+impl LineIndex {
+    /// Indexa as linhas de um texto.
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut length = 0;
+
+        for character in text.chars() {
+            length += character.len_utf16();
+            if character == '\n' {
+                line_starts.push(length);
+            }
+        }
+
+        Self { line_starts, length }
+    }
+
+    /// Converte uma posição do compilador para o LSP.
+    pub fn to_lsp(&self, position: Position) -> LspPosition {
+        let line = position.line.saturating_sub(1);
+        let start = self.line_starts.get(line).copied().unwrap_or(0);
+        let character = position.utf16_index.saturating_sub(start);
+        LspPosition::new(line as u32, character as u32)
+    }
+
+    /// Converte um intervalo do compilador para o LSP.
+    pub fn to_range(&self, span: Span) -> Range {
+        Range::new(self.to_lsp(span.start), self.to_lsp(span.end))
+    }
+
+    /// Converte uma posição do LSP para um índice UTF-16 no texto.
+    pub fn to_utf16_index(&self, position: LspPosition) -> usize {
+        match self.line_starts.get(position.line as usize) {
+            Some(start) => {
+                (start + position.character as usize).min(self.length)
+            }
+            None => self.length,
+        }
+    }
+}
+
+/// Um uso de um nome no código, ligado à definição `let` a que se refere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// Nome usado, com sua localização.
+    pub symbol: Symbol,
+    /// Índice da definição em [`Program::bindings`].
+    pub binding: usize,
+}
+
+/// Um arquivo aberto no editor, já analisado.
+#[derive(Debug, Clone)]
+pub struct Document {
+    ///
+    /// - `text`: código do arquivo
+    text: String,
+    ///
+    /// - `line_index`: conversor de posições
+    line_index: LineIndex,
+    ///
+    /// - `tokens`: tokens do arquivo (mesmo com erros léxicos)
+    tokens: Vec<Token>,
+    ///
+    /// - `program`: programa parsado, se o parser conseguiu produzir um
+    program: Option<Program>,
+    ///
+    /// - `references`: usos de definições `let` no programa
+    references: Vec<Reference>,
+}
+
+impl Document {
+    /// Analisa o código de um arquivo.
+    pub fn new(text: String) -> Self {
+        let line_index = LineIndex::new(&text);
+        let mut diagnostics = Diagnostics::new();
+        let tokens = generate_tokens(&text, &mut diagnostics);
+        let program = parse(tokens.clone(), &mut diagnostics);
+        let references =
+            program.as_ref().map_or_else(Vec::new, collect_references);
+
+        Self { text, line_index, tokens, program, references }
+    }
+
+    /// Compila o arquivo e converte os erros encontrados em diagnósticos do
+    /// LSP. Erros sem localização, ou localizados em arquivos importados,
+    /// são mostrados no começo do arquivo.
+    ///
+    /// - `loader`: carregador dos arquivos importados
+    pub fn diagnostics(&self, loader: &dyn Loader) -> Vec<Diagnostic> {
+        let options = Options::default();
+        let diagnostics =
+            match compile_with_loader(&self.text, loader, &options) {
+                Ok(_) => return Vec::new(),
+                Err(diagnostics) => diagnostics,
+            };
+
+        diagnostics
+            .iter()
+            .map(|error| {
+                let in_other_file =
+                    error.cause().downcast_ref::<InFile>().is_some();
+                let range = match error.span() {
+                    Some(span) if !in_other_file => {
+                        self.line_index.to_range(span)
+                    }
+                    _ => Range::default(),
+                };
+
+                Diagnostic {
+                    range,
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some(String::from("pinguim-lambda")),
+                    message: error.cause().to_string(),
+                    ..Diagnostic::default()
+                }
+            })
+            .collect()
+    }
+
+    /// Procura a definição `let` do nome na posição dada (que pode ser tanto
+    /// um uso quanto a própria definição). Retorna o índice da definição.
+    ///
+    /// - `position`: posição do cursor
+    pub fn binding_at(&self, position: LspPosition) -> Option<usize> {
+        let program = self.program.as_ref()?;
+        let index = self.line_index.to_utf16_index(position);
+
+        let definition = program
+            .bindings
+            .iter()
+            .position(|binding| contains(binding.name.span, index));
+
+        definition.or_else(|| {
+            self.references
+                .iter()
+                .find(|reference| contains(reference.symbol.span, index))
+                .map(|reference| reference.binding)
+        })
+    }
+
+    /// Retorna a localização do nome da definição `let` do nome na posição
+    /// dada.
+    ///
+    /// - `position`: posição do cursor
+    pub fn definition(&self, position: LspPosition) -> Option<Range> {
+        let binding = self.binding_at(position)?;
+        let program = self.program.as_ref()?;
+        Some(self.line_index.to_range(program.bindings[binding].name.span))
+    }
+
+    /// Retorna as localizações dos usos da definição `let` do nome na posição
+    /// dada, incluindo a própria definição se `include_declaration`.
+    ///
+    /// - `position`: posição do cursor
+    /// - `include_declaration`: se a definição deve ser incluída
+    pub fn references(
+        &self,
+        position: LspPosition,
+        include_declaration: bool,
+    ) -> Vec<Range> {
+        let (binding, program) =
+            match (self.binding_at(position), self.program.as_ref()) {
+                (Some(binding), Some(program)) => (binding, program),
+                _ => return Vec::new(),
+            };
+
+        let mut ranges = Vec::new();
+        if include_declaration {
+            let span = program.bindings[binding].name.span;
+            ranges.push(self.line_index.to_range(span));
+        }
+        for reference in &self.references {
+            if reference.binding == binding {
+                ranges.push(self.line_index.to_range(reference.symbol.span));
+            }
+        }
+        ranges
+    }
+
+    /// Retorna o texto (em Markdown) do hover sobre o nome na posição dada:
+    /// a documentação e a forma normal da definição `let` correspondente,
+    /// calculada com no máximo [`HOVER_MAX_STEPS`] passos.
+    ///
+    /// - `position`: posição do cursor
+    /// - `loader`: carregador dos arquivos importados
+    pub fn hover(
+        &self,
+        position: LspPosition,
+        loader: &dyn Loader,
+    ) -> Option<String> {
+        let binding = self.binding_at(position)?;
+        let program = self.program.as_ref()?;
+        let name = &program.bindings[binding].name;
+        let doc = &program.bindings[binding].doc;
+
+        // Programa cuja expressão principal é a definição, com todas as
+        // definições do arquivo.
+        let hover_program = Program {
+            imports: program.imports.clone(),
+            recursive: program.recursive,
+            main_expression: Expr::Variable(name.clone()),
+            bindings: program.bindings.clone(),
+        };
+
+        let mut diagnostics = Diagnostics::new();
+        let bindings = load_imports(
+            &hover_program.imports,
+            None,
+            loader,
+            prelude::bindings(),
+            &mut diagnostics,
+        );
+        let value =
+            expand_with_prelude(&hover_program, &bindings, &mut diagnostics)
+                .filter(|_| diagnostics.is_ok());
+
+        let mut text = String::new();
+        let _ = writeln!(text, "**{}**\n", name.content);
+        if let Some(doc) = doc {
+            let _ = writeln!(text, "{}\n", doc);
+        }
+
+        let value = match value {
+            Some(value) => value,
+            None => {
+                let _ = writeln!(text, "Definição com erros.");
+                return Some(text);
+            }
+        };
+
+        let mut interpreter = Interpreter::new(value);
+        let limits =
+            Limits { max_steps: Some(HOVER_MAX_STEPS), ..Limits::default() };
+        let outcome = interpreter.run_bounded(&limits);
+
+        let _ = writeln!(text, "```\n{}\n```\n", interpreter.output());
+        if outcome != Outcome::NormalForm {
+            let _ = writeln!(
+                text,
+                "Forma normal não alcançada em {} passos.\n",
+                HOVER_MAX_STEPS
+            );
+        }
+        for description in interpreter.describe_output() {
+            let _ = writeln!(text, "- {}", description);
+        }
+
+        Some(text)
+    }
+
+    /// Classifica os tokens do arquivo em tokens semânticos, já codificados
+    /// de forma relativa, como pede o LSP.
+    pub fn semantic_tokens(&self) -> Vec<SemanticToken> {
+        let declarations =
+            self.program.as_ref().map_or(Vec::new(), |program| {
+                program
+                    .bindings
+                    .iter()
+                    .map(|binding| binding.name.span)
+                    .collect()
+            });
+
+        let mut semantic_tokens = Vec::new();
+        let mut previous = LspPosition::new(0, 0);
+
+        for token in &self.tokens {
+            let (token_type, modifiers) =
+                match token.token_type {
+                    TokenType::Let
+                    | TokenType::Rec
+                    | TokenType::In
+                    | TokenType::Import => (KEYWORD, 0),
+                    TokenType::Number => (NUMBER, 0),
+                    TokenType::String => (STRING, 0),
+                    TokenType::DocComment => (COMMENT, 0),
+                    TokenType::Lambda
+                    | TokenType::Dot
+                    | TokenType::Arrow
+                    | TokenType::Equal => (OPERATOR, 0),
+                    TokenType::Identifier => {
+                        if declarations.contains(&token.span) {
+                            (FUNCTION, DECLARATION)
+                        } else if self.references.iter().any(|reference| {
+                            reference.symbol.span == token.span
+                        }) {
+                            (FUNCTION, 0)
+                        } else {
+                            (VARIABLE, 0)
+                        }
+                    }
+                    _ => continue,
+                };
+
+            let start = self.line_index.to_lsp(token.span.start);
+            let length =
+                token.span.end.utf16_index - token.span.start.utf16_index;
+            let delta_line = start.line - previous.line;
+            let delta_start = if delta_line == 0 {
+                start.character - previous.character
+            } else {
+                start.character
+            };
+
+            semantic_tokens.push(SemanticToken {
+                delta_line,
+                delta_start,
+                length: length as u32,
+                token_type,
+                token_modifiers_bitset: modifiers,
+            });
+            previous = start;
+        }
+
+        semantic_tokens
+    }
+}
+
+/// Testa se um índice UTF-16 está dentro de um intervalo (incluindo a posição
+/// logo após o fim, onde o cursor fica ao terminar de digitar um nome).
+fn contains(span: Span, index: usize) -> bool {
+    span.start.utf16_index <= index && index <= span.end.utf16_index
+}
+
+/// Liga cada uso de nome no programa à definição `let` a que se refere.
+///
+/// As definições podem ser declaradas em qualquer ordem, então todas são
+/// visíveis em todas as expressões do programa; parâmetros de lambdas e
+/// definições de `let` dentro de expressões escondem definições de mesmo
+/// nome (ver [`Expr::variable_uses`]).
+fn collect_references(program: &Program) -> Vec<Reference> {
+    let expressions = program
+        .bindings
+        .iter()
+        .map(|binding| &binding.expression)
+        .chain(Some(&program.main_expression));
+
+    expressions
+        .flat_map(Expr::variable_uses)
+        .filter(|(_, binder)| binder.is_none())
+        .filter_map(|(symbol, _)| {
+            let binding = program
+                .bindings
+                .iter()
+                .rposition(|binding| binding.name.content == symbol.content)?;
+            Some(Reference { symbol: symbol.clone(), binding })
+        })
+        .collect()
+}
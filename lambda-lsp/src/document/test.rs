@@ -0,0 +1,236 @@
+use super::{
+    Document, LineIndex, COMMENT, DECLARATION, FUNCTION, KEYWORD, OPERATOR,
+    VARIABLE,
+};
+use lambda::compiler::import::MemoryLoader;
+use lsp_types::{DiagnosticSeverity, Position, Range};
+
+fn range(start: (u32, u32), end: (u32, u32)) -> Range {
+    Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+}
+
+#[test]
+fn line_index_counts_utf16() {
+    let index = LineIndex::new("ação\n𝔸b\nc");
+
+    assert_eq!(index.to_utf16_index(Position::new(0, 2)), 2);
+    assert_eq!(index.to_utf16_index(Position::new(1, 0)), 5);
+    assert_eq!(index.to_utf16_index(Position::new(1, 3)), 8);
+    assert_eq!(index.to_utf16_index(Position::new(2, 0)), 9);
+    assert_eq!(index.to_utf16_index(Position::new(7, 0)), 10);
+}
+
+#[test]
+fn no_diagnostics_for_valid_program() {
+    let document = Document::new(String::from("let id = \\x. x;\nin id"));
+    assert!(document.diagnostics(&MemoryLoader::new()).is_empty());
+}
+
+#[test]
+fn diagnostics_have_ranges() {
+    let document = Document::new(String::from("let a = b; b = a;\nin a"));
+    let diagnostics = document.diagnostics(&MemoryLoader::new());
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range, range((0, 4), (0, 5)));
+    assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::ERROR));
+    assert_eq!(
+        diagnostics[0].message,
+        "Definições cíclicas fora de um `let rec`: \"a\" (linha 1, coluna 5) \
+         -> \"b\" (linha 1, coluna 12) -> \"a\""
+    );
+}
+
+#[test]
+fn diagnostics_without_span_at_start() {
+    let document = Document::new(String::from("\n\n99999999999"));
+    let diagnostics = document.diagnostics(&MemoryLoader::new());
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range, Range::default());
+}
+
+#[test]
+fn diagnostics_of_failed_import() {
+    let document = Document::new(String::from("import \"x.lam\";\n1"));
+    let diagnostics = document.diagnostics(&MemoryLoader::new());
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range, range((0, 7), (0, 14)));
+}
+
+#[test]
+fn definition_from_reference() {
+    let source = "let id = \\x. x;\n    k = \\x. \\y. x;\nin k id";
+    let document = Document::new(String::from(source));
+
+    assert_eq!(
+        document.definition(Position::new(2, 5)),
+        Some(range((0, 4), (0, 6)))
+    );
+    assert_eq!(
+        document.definition(Position::new(2, 3)),
+        Some(range((1, 4), (1, 5)))
+    );
+}
+
+#[test]
+fn definition_ignores_parameters_and_finds_later_bindings() {
+    let source =
+        "let x = \\y. y;\n    f = \\x. x;\n    g = h;\n    h = x;\nin f";
+    let document = Document::new(String::from(source));
+
+    // `x` é o parâmetro do lambda, não a definição
+    assert_eq!(document.definition(Position::new(1, 13)), None);
+    // `h` é declarado depois de `g`, mas as definições não têm ordem
+    assert_eq!(
+        document.definition(Position::new(2, 8)),
+        Some(range((3, 4), (3, 5)))
+    );
+    assert_eq!(
+        document.definition(Position::new(3, 8)),
+        Some(range((0, 4), (0, 5)))
+    );
+}
+
+#[test]
+fn definition_of_duplicated_binding_is_the_last() {
+    let source = "let a = \\x. x;\n    b = a;\n    a = b;\nin a";
+    let document = Document::new(String::from(source));
+
+    assert_eq!(
+        document.definition(Position::new(1, 8)),
+        Some(range((2, 4), (2, 5)))
+    );
+    assert_eq!(
+        document.definition(Position::new(3, 3)),
+        Some(range((2, 4), (2, 5)))
+    );
+}
+
+#[test]
+fn references_of_binding() {
+    let source = "let id = \\x. x;\n    a = id id;\nin a id";
+    let document = Document::new(String::from(source));
+
+    assert_eq!(
+        document.references(Position::new(0, 5), true),
+        vec![
+            range((0, 4), (0, 6)),
+            range((1, 8), (1, 10)),
+            range((1, 11), (1, 13)),
+            range((2, 5), (2, 7)),
+        ]
+    );
+    assert_eq!(
+        document.references(Position::new(2, 3), false),
+        vec![range((2, 3), (2, 4))]
+    );
+}
+
+#[test]
+fn hover_shows_normal_form() {
+    let source = "let two = succ 1;\nin two";
+    let document = Document::new(String::from(source));
+    let hover =
+        document.hover(Position::new(1, 4), &MemoryLoader::new()).unwrap();
+
+    assert!(hover.starts_with("**two**\n\n```\n"));
+    assert!(hover.contains("```\n\n- numeral de Church 2\n"));
+}
+
+#[test]
+fn hover_is_bounded() {
+    let source = "let omega = (\\x. x x) (\\x. x x);\nin omega";
+    let document = Document::new(String::from(source));
+    let hover =
+        document.hover(Position::new(0, 5), &MemoryLoader::new()).unwrap();
+
+    assert!(hover.contains("Forma normal não alcançada em 10000 passos."));
+}
+
+#[test]
+fn hover_outside_binding() {
+    let document = Document::new(String::from("let id = \\x. x;\nin id"));
+    let hover = document.hover(Position::new(0, 10), &MemoryLoader::new());
+    assert_eq!(hover, None);
+}
+
+#[test]
+fn semantic_tokens_are_classified() {
+    let document = Document::new(String::from("let id = \\x. x;\nin id y"));
+    let tokens = document
+        .semantic_tokens()
+        .into_iter()
+        .map(|token| {
+            (
+                token.delta_line,
+                token.delta_start,
+                token.length,
+                token.token_type,
+                token.token_modifiers_bitset,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tokens,
+        vec![
+            (0, 0, 3, KEYWORD, 0),
+            (0, 4, 2, FUNCTION, DECLARATION),
+            (0, 3, 1, OPERATOR, 0),
+            (0, 2, 1, OPERATOR, 0),
+            (0, 1, 1, VARIABLE, 0),
+            (0, 1, 1, OPERATOR, 0),
+            (0, 2, 1, VARIABLE, 0),
+            (1, 0, 2, KEYWORD, 0),
+            (0, 3, 2, FUNCTION, 0),
+            (0, 3, 1, VARIABLE, 0),
+        ]
+    );
+}
+
+#[test]
+fn hover_shows_doc_comment() {
+    let source = "let --| Identidade.\n    id = \\x. x;\nin id";
+    let document = Document::new(String::from(source));
+    let hover =
+        document.hover(Position::new(2, 3), &MemoryLoader::new()).unwrap();
+
+    assert!(hover.starts_with("**id**\n\nIdentidade.\n\n```\n"));
+}
+
+#[test]
+fn semantic_tokens_of_doc_comment() {
+    let document = Document::new(String::from("let --| Um.\n    a = 1;\nin a"));
+    let tokens = document.semantic_tokens();
+    let token = &tokens[1];
+
+    assert_eq!((token.delta_line, token.delta_start), (0, 4));
+    assert_eq!((token.length, token.token_type), (7, COMMENT));
+}
+
+#[test]
+fn references_in_let_rec() {
+    let source = "let rec even = \\n. odd n;\n    odd = \\n. even n;\nin even";
+    let document = Document::new(String::from(source));
+
+    assert_eq!(
+        document.definition(Position::new(0, 20)),
+        Some(range((1, 4), (1, 7)))
+    );
+
+    let hover =
+        document.hover(Position::new(2, 3), &MemoryLoader::new()).unwrap();
+    assert!(!hover.contains("Definição com erros."), "{}", hover);
+}
+
+#[test]
+fn nested_let_hides_binding() {
+    let source = "let a = \\x. x;\nin \\y. let a = y; b = a; in a b";
+    let document = Document::new(String::from(source));
+
+    assert_eq!(document.definition(Position::new(1, 22)), None);
+    assert_eq!(document.definition(Position::new(1, 28)), None);
+    assert_eq!(document.references(Position::new(0, 4), false), Vec::new());
+}
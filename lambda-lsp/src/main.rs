@@ -0,0 +1,22 @@
+//! Servidor do Language Server Protocol para o cálculo lambda, comunicando-se
+//! com o editor pela entrada e saída padrão.
+//!
+//! ```text
+//! pinguim-lambda-lsp
+//! ```
+//!
+//! Oferece diagnósticos enquanto o arquivo é editado, ir para definição e
+//! encontrar referências de definições `let`, hover com a forma normal das
+//! definições e tokens semânticos.
+
+mod document;
+mod server;
+
+use std::process;
+
+fn main() {
+    if let Err(error) = server::run() {
+        eprintln!("erro: {}", error);
+        process::exit(1);
+    }
+}
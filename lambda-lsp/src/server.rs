@@ -0,0 +1,361 @@
+//! Comunicação com o editor: recebe as mensagens do LSP, mantém os arquivos
+//! abertos e responde usando as análises de [`crate::document`].
+
+#[cfg(test)]
+mod test;
+
+use crate::document::{Document, TOKEN_MODIFIERS, TOKEN_TYPES};
+use lambda::compiler::import::FsLoader;
+use lsp_server::{
+    Connection, ErrorCode, ExtractError, Message, Notification, Request,
+    RequestId, Response,
+};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{
+    GotoDefinition, HoverRequest, References, SemanticTokensFullRequest,
+};
+use lsp_types::{
+    GotoDefinitionResponse, Hover, HoverContents, HoverProviderCapability,
+    Location, MarkupContent, MarkupKind, OneOf, PublishDiagnosticsParams,
+    SemanticTokens, SemanticTokensFullOptions, SemanticTokensLegend,
+    SemanticTokensOptions, SemanticTokensResult,
+    SemanticTokensServerCapabilities, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+
+/// Erro qualquer durante a comunicação com o editor.
+pub type ServerError = Box<dyn Error + Send + Sync>;
+
+/// Inicia o servidor na entrada e saída padrão e atende o editor até ele
+/// pedir o encerramento.
+pub fn run() -> Result<(), ServerError> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = serde_json::to_value(capabilities())?;
+    connection.initialize(capabilities)?;
+
+    Server::new(&connection).main_loop()?;
+    io_threads.join()?;
+
+    Ok(())
+}
+
+/// Funcionalidades oferecidas pelo servidor.
+fn capabilities() -> ServerCapabilities {
+    let legend = SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    };
+
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::FULL,
+        )),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        semantic_tokens_provider: Some(
+            SemanticTokensServerCapabilities::SemanticTokensOptions(
+                SemanticTokensOptions {
+                    legend,
+                    full: Some(SemanticTokensFullOptions::Bool(true)),
+                    ..SemanticTokensOptions::default()
+                },
+            ),
+        ),
+        ..ServerCapabilities::default()
+    }
+}
+
+/// Carregador das importações de um arquivo: caminhos relativos partem da
+/// pasta do arquivo. Arquivos que não estão no disco usam a pasta atual.
+///
+/// - `uri`: endereço do arquivo
+fn loader_for(uri: &Url) -> FsLoader {
+    let root = uri
+        .to_file_path()
+        .ok()
+        .and_then(|path| path.parent().map(PathBuf::from));
+
+    match root {
+        Some(root) => FsLoader::new(root),
+        None => FsLoader::default(),
+    }
+}
+
+/// Estado do servidor.
+struct Server<'conn> {
+    ///
+    /// - `connection`: conexão com o editor
+    connection: &'conn Connection,
+    ///
+    /// - `documents`: arquivos abertos no editor
+    documents: HashMap<Url, Document>,
+}
+
+impl<'conn> Server<'conn> {
+    /// Cria o estado do servidor, sem arquivos abertos.
+    ///
+    /// - `connection`: conexão com o editor
+    fn new(connection: &'conn Connection) -> Self {
+        Self { connection, documents: HashMap::new() }
+    }
+
+    /// Atende as mensagens do editor até o pedido de encerramento.
+    fn main_loop(&mut self) -> Result<(), ServerError> {
+        let connection = self.connection;
+
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => {
+                    self.handle_notification(notification)?;
+                }
+                Message::Response(_) => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Trata uma notificação do editor. Notificações com parâmetros
+    /// inválidos são ignoradas, já que não têm resposta.
+    ///
+    /// - `notification`: notificação recebida
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Result<(), ServerError> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                match params::<DidOpenTextDocument>(notification) {
+                    Some(params) => {
+                        let document = params.text_document;
+                        self.update(document.uri, document.text)
+                    }
+                    None => Ok(()),
+                }
+            }
+
+            DidChangeTextDocument::METHOD => {
+                let params = match params::<DidChangeTextDocument>(notification)
+                {
+                    Some(params) => params,
+                    None => return Ok(()),
+                };
+                // Com sincronização completa, a última mudança contém o
+                // texto inteiro do arquivo.
+                match params.content_changes.into_iter().last() {
+                    Some(change) => {
+                        self.update(params.text_document.uri, change.text)
+                    }
+                    None => Ok(()),
+                }
+            }
+
+            DidCloseTextDocument::METHOD => {
+                match params::<DidCloseTextDocument>(notification) {
+                    Some(params) => {
+                        let uri = params.text_document.uri;
+                        self.documents.remove(&uri);
+                        self.publish_diagnostics(uri, Vec::new())
+                    }
+                    None => Ok(()),
+                }
+            }
+
+            _ => Ok(()),
+        }
+    }
+
+    /// Analisa o novo texto de um arquivo e publica seus diagnósticos.
+    ///
+    /// - `uri`: endereço do arquivo
+    /// - `text`: texto atual do arquivo
+    fn update(&mut self, uri: Url, text: String) -> Result<(), ServerError> {
+        let document = Document::new(text);
+        let diagnostics = document.diagnostics(&loader_for(&uri));
+        self.documents.insert(uri.clone(), document);
+        self.publish_diagnostics(uri, diagnostics)
+    }
+
+    /// Envia os diagnósticos de um arquivo ao editor.
+    ///
+    /// - `uri`: endereço do arquivo
+    /// - `diagnostics`: diagnósticos do arquivo
+    fn publish_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<lsp_types::Diagnostic>,
+    ) -> Result<(), ServerError> {
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        let notification =
+            Notification::new(PublishDiagnostics::METHOD.to_owned(), params);
+        self.connection.sender.send(notification.into())?;
+        Ok(())
+    }
+
+    /// Trata um pedido do editor, respondendo-o. Pedidos com parâmetros
+    /// inválidos ou não suportados são respondidos com um erro.
+    ///
+    /// - `request`: pedido recebido
+    fn handle_request(&self, request: Request) -> Result<(), ServerError> {
+        let request = match cast::<GotoDefinition>(request) {
+            Ok(Ok((id, params))) => {
+                let params = params.text_document_position_params;
+                let uri = params.text_document.uri;
+                let position = params.position;
+                let response = self
+                    .documents
+                    .get(&uri)
+                    .and_then(|document| document.definition(position))
+                    .map(|range| {
+                        GotoDefinitionResponse::Scalar(Location::new(
+                            uri.clone(),
+                            range,
+                        ))
+                    });
+                return self.respond(id, response);
+            }
+            Ok(Err(request)) => request,
+            Err(response) => return self.send(response),
+        };
+
+        let request = match cast::<References>(request) {
+            Ok(Ok((id, params))) => {
+                let uri = params.text_document_position.text_document.uri;
+                let position = params.text_document_position.position;
+                let include_declaration = params.context.include_declaration;
+                let response = self.documents.get(&uri).map(|document| {
+                    document
+                        .references(position, include_declaration)
+                        .into_iter()
+                        .map(|range| Location::new(uri.clone(), range))
+                        .collect::<Vec<_>>()
+                });
+                return self.respond(id, response);
+            }
+            Ok(Err(request)) => request,
+            Err(response) => return self.send(response),
+        };
+
+        let request = match cast::<HoverRequest>(request) {
+            Ok(Ok((id, params))) => {
+                let params = params.text_document_position_params;
+                let uri = params.text_document.uri;
+                let position = params.position;
+                let loader = loader_for(&uri);
+                let response = self
+                    .documents
+                    .get(&uri)
+                    .and_then(|document| document.hover(position, &loader))
+                    .map(|value| Hover {
+                        contents: HoverContents::Markup(MarkupContent {
+                            kind: MarkupKind::Markdown,
+                            value,
+                        }),
+                        range: None,
+                    });
+                return self.respond(id, response);
+            }
+            Ok(Err(request)) => request,
+            Err(response) => return self.send(response),
+        };
+
+        let request = match cast::<SemanticTokensFullRequest>(request) {
+            Ok(Ok((id, params))) => {
+                let uri = params.text_document.uri;
+                let response = self.documents.get(&uri).map(|document| {
+                    SemanticTokensResult::Tokens(SemanticTokens {
+                        result_id: None,
+                        data: document.semantic_tokens(),
+                    })
+                });
+                return self.respond(id, response);
+            }
+            Ok(Err(request)) => request,
+            Err(response) => return self.send(response),
+        };
+
+        let message = format!("método não suportado: {}", request.method);
+        let code = ErrorCode::MethodNotFound as i32;
+        self.send(Response::new_err(request.id, code, message))
+    }
+
+    /// Envia a resposta de um pedido ao editor.
+    ///
+    /// - `id`: identificador do pedido
+    /// - `result`: resultado do pedido
+    fn respond<T>(&self, id: RequestId, result: T) -> Result<(), ServerError>
+    where
+        T: Serialize,
+    {
+        self.send(Response::new_ok(id, result))
+    }
+
+    /// Envia uma resposta, de sucesso ou de erro, ao editor.
+    ///
+    /// - `response`: resposta de um pedido
+    fn send(&self, response: Response) -> Result<(), ServerError> {
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+}
+
+/// Tenta interpretar o pedido como um pedido do tipo `R`, devolvendo-o se for
+/// de outro tipo. Se os parâmetros forem inválidos, retorna a resposta de
+/// erro do pedido.
+///
+/// - `request`: pedido recebido
+fn cast<R>(
+    request: Request,
+) -> Result<Result<(RequestId, R::Params), Request>, Response>
+where
+    R: lsp_types::request::Request,
+{
+    let id = request.id.clone();
+
+    match request.extract(R::METHOD) {
+        Ok(extracted) => Ok(Ok(extracted)),
+        Err(ExtractError::MethodMismatch(request)) => Ok(Err(request)),
+        Err(ExtractError::JsonError { method, error }) => {
+            let message =
+                format!("parâmetros inválidos em {}: {}", method, error);
+            let code = ErrorCode::InvalidParams as i32;
+            Err(Response::new_err(id, code, message))
+        }
+    }
+}
+
+/// Extrai os parâmetros de uma notificação do tipo `N`. Se forem inválidos,
+/// avisa na saída de erro e retorna `None`.
+///
+/// - `notification`: notificação recebida, do tipo `N`
+fn params<N>(notification: Notification) -> Option<N::Params>
+where
+    N: lsp_types::notification::Notification,
+{
+    match notification.extract(N::METHOD) {
+        Ok(params) => Some(params),
+        Err(ExtractError::MethodMismatch(notification)) => {
+            eprintln!("método inesperado: {}", notification.method);
+            None
+        }
+        Err(ExtractError::JsonError { method, error }) => {
+            eprintln!("parâmetros inválidos em {}: {}", method, error);
+            None
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use super::Server;
+use lsp_server::{
+    Connection, ErrorCode, Message, Notification, Request, RequestId,
+};
+use lsp_types::notification::{DidOpenTextDocument, Notification as _};
+use lsp_types::request::{HoverRequest, Request as _};
+use serde_json::json;
+
+/// Resposta de erro recebida pelo editor: o identificador do pedido e o
+/// código do erro.
+fn error_response(client: &Connection) -> (RequestId, Option<i32>) {
+    match client.receiver.try_recv() {
+        Ok(Message::Response(response)) => {
+            (response.id, response.error.map(|error| error.code))
+        }
+        message => panic!("esperava-se uma resposta, veio {:?}", message),
+    }
+}
+
+#[test]
+fn invalid_request_params() {
+    let (server, client) = Connection::memory();
+    let server = Server::new(&server);
+
+    let params = json!({ "textDocument": 5 });
+    let request =
+        Request::new(RequestId::from(1), HoverRequest::METHOD.into(), params);
+
+    assert!(server.handle_request(request).is_ok());
+    assert_eq!(
+        error_response(&client),
+        (RequestId::from(1), Some(ErrorCode::InvalidParams as i32))
+    );
+}
+
+#[test]
+fn unsupported_request() {
+    let (server, client) = Connection::memory();
+    let server = Server::new(&server);
+
+    let request = Request::new(
+        RequestId::from(2),
+        "lambda/desconhecido".into(),
+        json!({}),
+    );
+
+    assert!(server.handle_request(request).is_ok());
+    assert_eq!(
+        error_response(&client),
+        (RequestId::from(2), Some(ErrorCode::MethodNotFound as i32))
+    );
+}
+
+#[test]
+fn invalid_notification_params_are_ignored() {
+    let (server, client) = Connection::memory();
+    let mut server = Server::new(&server);
+
+    let notification = Notification::new(
+        DidOpenTextDocument::METHOD.into(),
+        json!({ "textDocument": null }),
+    );
+
+    assert!(server.handle_notification(notification).is_ok());
+    assert!(client.receiver.try_recv().is_err());
+    assert!(server.documents.is_empty());
+}
@@ -1,5 +1,12 @@
 mod utils;
 
+use js_sys::{Array, Object, Reflect};
+use lambda::compiler::report::{labels, render_error, RenderOptions};
+use lambda::compiler::{self, import::MemoryLoader, Options};
+use lambda::interpreter;
+use lambda::value::Value;
+use pinguim_language::error::Diagnostics;
+use pinguim_language::position::{Position, Span};
 use wasm_bindgen::prelude::*;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
@@ -8,12 +15,257 @@ use wasm_bindgen::prelude::*;
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+/// Compila o código fonte para um termo no formato usado por `www/lambda.js`:
+/// `{ varname }`, `{ function, argument }` ou `{ parameter, body }`.
+///
+/// Em caso de erro, lança um array de diagnósticos, cada um no formato
+/// `{ message, span: { start, end } | null, labels, rendered }`, sendo cada
+/// posição `{ line, column, utf16Index }`, cada trecho destacado em `labels`
+/// `{ span, message, primary }` e `rendered` o erro formatado como texto.
 #[wasm_bindgen]
-extern {
-    fn alert(s: &str);
+pub fn compile(source: &str) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    match compiler::compile(source) {
+        Ok(value) => Ok(value_to_js(&value)),
+        Err(diagnostics) => Err(diagnostics_to_js(source, &diagnostics)),
+    }
+}
+
+/// Compila o código fonte como em [`compile`], servindo as importações a
+/// partir de `files`, um objeto que mapeia caminhos para códigos fonte.
+#[wasm_bindgen]
+pub fn compile_with_files(
+    source: &str,
+    files: &Object,
+) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+
+    let loader = memory_loader(files);
+    match compiler::compile_with_loader(source, &loader, &Options::default()) {
+        Ok(value) => Ok(value_to_js(&value)),
+        Err(diagnostics) => Err(diagnostics_to_js(source, &diagnostics)),
+    }
+}
+
+/// Interpretador com estado, criado a partir do código fonte.
+#[wasm_bindgen]
+pub struct Interpreter {
+    inner: interpreter::Interpreter,
 }
 
 #[wasm_bindgen]
-pub fn greet() {
-    alert("Hello, lambda-wasm!");
+impl Interpreter {
+    /// Compila o código fonte e cria um interpretador para o termo obtido.
+    ///
+    /// Em caso de erro, lança um array de diagnósticos, no mesmo formato de
+    /// [`compile`].
+    #[wasm_bindgen(constructor)]
+    pub fn new(source: &str) -> Result<Interpreter, JsValue> {
+        utils::set_panic_hook();
+
+        match compiler::compile(source) {
+            Ok(value) => {
+                Ok(Self { inner: interpreter::Interpreter::new(value) })
+            }
+            Err(diagnostics) => Err(diagnostics_to_js(source, &diagnostics)),
+        }
+    }
+
+    /// Compila o código fonte como em [`compile_with_files`] e cria um
+    /// interpretador para o termo obtido.
+    pub fn with_files(
+        source: &str,
+        files: &Object,
+    ) -> Result<Interpreter, JsValue> {
+        utils::set_panic_hook();
+
+        let loader = memory_loader(files);
+        let options = Options::default();
+        match compiler::compile_with_loader(source, &loader, &options) {
+            Ok(value) => {
+                Ok(Self { inner: interpreter::Interpreter::new(value) })
+            }
+            Err(diagnostics) => Err(diagnostics_to_js(source, &diagnostics)),
+        }
+    }
+
+    /// Roda um passo da redução. Retorna `true` se houve redução.
+    pub fn step(&mut self) -> bool {
+        self.inner.run_step()
+    }
+
+    /// Roda até `max_steps` passos da redução. Retorna `false` se a forma
+    /// normal foi alcançada antes do limite.
+    pub fn run_steps(&mut self, max_steps: u32) -> bool {
+        self.inner.run_steps(max_steps)
+    }
+
+    /// Volta o interpretador para o termo de entrada.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Retorna quantos passos foram dados. Usa `f64` por ser o tipo de
+    /// números do JavaScript; é exato até 2^53 passos.
+    pub fn steps(&self) -> f64 {
+        self.inner.steps() as f64
+    }
+
+    /// Retorna o termo atual, no mesmo formato de [`compile`].
+    pub fn output(&self) -> JsValue {
+        value_to_js(self.inner.output())
+    }
+
+    /// Retorna o termo de entrada, no mesmo formato de [`compile`].
+    pub fn input(&self) -> JsValue {
+        value_to_js(self.inner.input())
+    }
+
+    /// Retorna o termo atual como texto.
+    pub fn output_string(&self) -> String {
+        self.inner.output().to_string()
+    }
+}
+
+/// Atribui uma propriedade a um objeto JavaScript comum.
+fn set_property(object: &Object, key: &str, value: &JsValue) {
+    Reflect::set(object, &JsValue::from_str(key), value)
+        .expect("objeto comum aceita propriedades");
+}
+
+/// Converte um termo para um objeto JavaScript, no formato de `www/lambda.js`.
+/// Iterativo, assim como os algoritmos de [`Value`].
+fn value_to_js(value: &Value) -> JsValue {
+    /// Uma operação auxiliar de conversão.
+    enum Operation<'value> {
+        /// Inicia a conversão de um termo qualquer.
+        Convert(&'value Value),
+        /// Finaliza a conversão de um termo lambda.
+        MakeLambda(&'value str),
+        /// Finaliza a conversão de um termo aplicação.
+        MakeApplication,
+    }
+
+    let mut operation_stack = vec![Operation::Convert(value)];
+    let mut output_stack: Vec<JsValue> = Vec::new();
+
+    while let Some(operation) = operation_stack.pop() {
+        match operation {
+            Operation::Convert(value) => match value {
+                // Caso base
+                Value::Variable(variable) => {
+                    let object = Object::new();
+                    set_property(&object, "varname", &variable.into());
+                    output_stack.push(object.into());
+                }
+
+                Value::Application { function, argument } => {
+                    // .pop() retorna elementos na ordem inversa do .push()
+                    operation_stack.push(Operation::MakeApplication);
+                    operation_stack.push(Operation::Convert(argument));
+                    operation_stack.push(Operation::Convert(function));
+                }
+
+                Value::Lambda { parameter, body } => {
+                    operation_stack.push(Operation::MakeLambda(parameter));
+                    operation_stack.push(Operation::Convert(body));
+                }
+            },
+
+            Operation::MakeLambda(parameter) => {
+                let body = output_stack.pop().expect("js body");
+                let object = Object::new();
+                set_property(&object, "parameter", &parameter.into());
+                set_property(&object, "body", &body);
+                output_stack.push(object.into());
+            }
+
+            Operation::MakeApplication => {
+                let argument = output_stack.pop().expect("js argument");
+                let function = output_stack.pop().expect("js function");
+                let object = Object::new();
+                set_property(&object, "function", &function);
+                set_property(&object, "argument", &argument);
+                output_stack.push(object.into());
+            }
+        }
+    }
+
+    output_stack.pop().expect("js value")
+}
+
+/// Cria um carregador de arquivos em memória a partir de um objeto que mapeia
+/// caminhos para códigos fonte. Entradas cujo valor não é texto são
+/// ignoradas.
+fn memory_loader(files: &Object) -> MemoryLoader {
+    let mut loader = MemoryLoader::new();
+
+    for entry in Object::entries(files).iter() {
+        let entry = Array::from(&entry);
+        if let (Some(path), Some(source)) =
+            (entry.get(0).as_string(), entry.get(1).as_string())
+        {
+            loader.insert(path, source);
+        }
+    }
+
+    loader
+}
+
+/// Converte os diagnósticos para um array JavaScript. Cada erro vira um
+/// objeto `{ message, span, labels, rendered }`, em que `message` é a
+/// mensagem sem a posição, `span` é `{ start, end }` ou `null`, se o erro não
+/// tiver posição, `labels` é um array com os trechos destacados pelo erro,
+/// cada um `{ span, message, primary }`, e `rendered` é o erro formatado como
+/// texto puro, com o trecho do código fonte. Cada posição é
+/// `{ line, column, utf16Index }`, contando linhas e colunas a partir de 1.
+fn diagnostics_to_js(source: &str, diagnostics: &Diagnostics) -> JsValue {
+    let array = Array::new();
+    let options = RenderOptions::default();
+
+    for error in diagnostics.iter() {
+        let object = Object::new();
+        set_property(&object, "message", &error.cause().to_string().into());
+
+        let span = match error.span() {
+            Some(span) => span_to_js(span),
+            None => JsValue::NULL,
+        };
+        set_property(&object, "span", &span);
+
+        let error_labels = Array::new();
+        for label in labels(error) {
+            let label_object = Object::new();
+            set_property(&label_object, "span", &span_to_js(label.span));
+            set_property(&label_object, "message", &label.message.into());
+            set_property(&label_object, "primary", &label.primary.into());
+            error_labels.push(&label_object);
+        }
+        set_property(&object, "labels", &error_labels);
+
+        let rendered = render_error(source, error, &options);
+        set_property(&object, "rendered", &rendered.into());
+
+        array.push(&object);
+    }
+
+    array.into()
+}
+
+/// Converte um intervalo do código fonte para um objeto JavaScript.
+fn span_to_js(span: Span) -> JsValue {
+    let object = Object::new();
+    set_property(&object, "start", &position_to_js(span.start));
+    set_property(&object, "end", &position_to_js(span.end));
+    object.into()
+}
+
+/// Converte uma posição do código fonte para um objeto JavaScript.
+fn position_to_js(position: Position) -> JsValue {
+    let object = Object::new();
+    set_property(&object, "line", &(position.line as f64).into());
+    set_property(&object, "column", &(position.column as f64).into());
+    set_property(&object, "utf16Index", &(position.utf16_index as f64).into());
+    object.into()
 }
@@ -0,0 +1,46 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use lambda::compiler::compile;
+use lambda::interpreter::run_once;
+use lambda::machine::{run_cek, run_krivine};
+
+/// Fatorial com numerais de Church, iterando sobre pares `(i, i!)`.
+const FACTORIAL: &str = "
+    let pair = \\a b f. f a b;
+        fst = \\p. p (\\a b. a);
+        snd = \\p. p (\\a b. b);
+        succ = \\n f x. f (n f x);
+        mul = \\m n f. m (n f);
+        next = \\p. pair (succ (fst p)) (mul (succ (fst p)) (snd p));
+        fact = \\n. snd (n next (pair 0 1));
+    in fact
+";
+
+fn factorial(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("fact");
+
+    for &number in &[3, 4, 5, 6] {
+        let source = format!("{} {}", FACTORIAL, number);
+        let input = compile(&source).unwrap();
+
+        group.bench_with_input(
+            BenchmarkId::new("run_once", number),
+            &input,
+            |bencher, input| bencher.iter(|| run_once(input.clone())),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("krivine", number),
+            &input,
+            |bencher, input| bencher.iter(|| run_krivine(input)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("cek", number),
+            &input,
+            |bencher, input| bencher.iter(|| run_cek(input)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, factorial);
+criterion_main!(benches);
@@ -1,7 +1,149 @@
+#[cfg(test)]
+mod test;
+
 pub mod position;
 pub mod lexer;
 pub mod parser;
+pub mod expansor;
+pub mod prelude;
+pub mod import;
+pub mod report;
 pub mod error;
 
+use crate::value::Value;
+use expansor::{expand_with_lowering, LetLowering};
+use import::{
+    error::ImportFailed, load_imports, resolve, Loader, MemoryLoader,
+};
 use lexer::generate_tokens;
-use parser::parse;
\ No newline at end of file
+use parser::parse;
+use pinguim_language::error::{Diagnostics, Error};
+
+/// Opções de compilação.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// Se as definições do [`prelude`] padrão estão disponíveis no programa.
+    /// Por padrão, estão.
+    pub prelude: bool,
+    /// Como os `let` dentro de expressões são convertidos em termos. Por
+    /// padrão, suas definições são substituídas.
+    pub let_lowering: LetLowering,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self { prelude: true, let_lowering: LetLowering::default() }
+    }
+}
+
+/// Compila o código-fonte de um programa em um termo do cálculo lambda, com
+/// as opções padrão (veja [`Options`]).
+///
+/// Executa a análise léxica, a análise sintática e a expansão das definições,
+/// parando na primeira etapa que encontrar erros.
+///
+/// - `source`: código-fonte do programa
+pub fn compile(source: &str) -> Result<Value, Diagnostics> {
+    compile_with_options(source, &Options::default())
+}
+
+/// Compila o código-fonte de um programa em um termo do cálculo lambda, como
+/// em [`compile`], mas com as opções dadas.
+///
+/// Sem um [`Loader`], toda importação falha; veja [`compile_with_loader`].
+///
+/// - `source`: código-fonte do programa
+/// - `options`: opções de compilação
+pub fn compile_with_options(
+    source: &str,
+    options: &Options,
+) -> Result<Value, Diagnostics> {
+    compile_with_loader(source, &MemoryLoader::new(), options)
+}
+
+/// Compila o código-fonte de um programa em um termo do cálculo lambda,
+/// carregando as importações com o `loader` dado. Caminhos relativos são
+/// resolvidos a partir da raiz do `loader`.
+///
+/// - `source`: código-fonte do programa
+/// - `loader`: carregador dos arquivos importados
+/// - `options`: opções de compilação
+pub fn compile_with_loader(
+    source: &str,
+    loader: &dyn Loader,
+    options: &Options,
+) -> Result<Value, Diagnostics> {
+    compile_module(source, None, loader, options)
+}
+
+/// Carrega o arquivo no caminho dado com o `loader` e o compila em um termo
+/// do cálculo lambda. Importações relativas são resolvidas a partir da pasta
+/// do arquivo.
+///
+/// - `path`: caminho do arquivo do programa
+/// - `loader`: carregador do programa e dos arquivos importados
+/// - `options`: opções de compilação
+pub fn compile_file(
+    path: &str,
+    loader: &dyn Loader,
+    options: &Options,
+) -> Result<Value, Diagnostics> {
+    let path = resolve(None, path);
+
+    match loader.load(&path) {
+        Ok(source) => compile_module(&source, Some(&path), loader, options),
+        Err(cause) => {
+            let cause = cause.to_string();
+            let mut diagnostics = Diagnostics::new();
+            let error = Error::with_no_span(ImportFailed { path, cause });
+            diagnostics.raise(error);
+            Err(diagnostics)
+        }
+    }
+}
+
+/// Compila o código-fonte de um arquivo, executando a análise léxica, a
+/// análise sintática, o carregamento das importações e a expansão das
+/// definições, parando na primeira etapa que encontrar erros.
+///
+/// - `source`: código-fonte do programa
+/// - `path`: caminho do arquivo do programa, se houver
+/// - `loader`: carregador dos arquivos importados
+/// - `options`: opções de compilação
+fn compile_module(
+    source: &str,
+    path: Option<&str>,
+    loader: &dyn Loader,
+    options: &Options,
+) -> Result<Value, Diagnostics> {
+    let mut diagnostics = Diagnostics::new();
+
+    let tokens = generate_tokens(source, &mut diagnostics);
+    if diagnostics.is_err() {
+        return Err(diagnostics);
+    }
+
+    let program = match parse(tokens, &mut diagnostics) {
+        Some(program) if diagnostics.is_ok() => program,
+        _ => return Err(diagnostics),
+    };
+
+    let prelude =
+        if options.prelude { prelude::bindings() } else { Vec::new() };
+    let bindings =
+        load_imports(&program.imports, path, loader, prelude, &mut diagnostics);
+    if diagnostics.is_err() {
+        return Err(diagnostics);
+    }
+
+    let value = expand_with_lowering(
+        &program,
+        &bindings,
+        options.let_lowering,
+        &mut diagnostics,
+    );
+    match value {
+        Some(value) if diagnostics.is_ok() => Ok(value),
+        _ => Err(diagnostics),
+    }
+}
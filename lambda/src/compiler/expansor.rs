@@ -0,0 +1,537 @@
+#[cfg(test)]
+mod test;
+
+pub mod error;
+pub mod recursion;
+
+use crate::compiler::parser::ast::{Binding, Expr, Program, Symbol};
+use crate::value::{NestedValue, Value};
+use error::{
+    DuplicatedBinding, NumberTooBig, RecursiveBinding, UndefinedBinding,
+};
+use pinguim_language::error::{Diagnostics, Error};
+use recursion::{recursion_components, sort_bindings, tie_recursion};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+};
+
+/// Como um `let` dentro de uma expressão é convertido em termo.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LetLowering {
+    /// Substitui as definições no corpo do `let`, como nas definições do
+    /// programa. É o padrão.
+    #[default]
+    Substitution,
+    /// Mantém cada definição como um redex: `let x = e; in b` vira
+    /// `(λx. b) e`, e a substituição fica para a avaliação.
+    Redex,
+}
+
+/// Expande as definições de um programa dentro da expressão principal,
+/// gerando um termo do cálculo lambda pronto para ser interpretado.
+///
+/// As definições podem ser declaradas em qualquer ordem: elas são ordenadas
+/// pelas dependências (veja [`sort_bindings`]) e substituídas a partir da
+/// última. Definições que usam umas às outras em ciclo só são aceitas com
+/// `let rec` (veja [`recursion`]). Números são convertidos para numerais de
+/// Church.
+///
+/// - `program`: programa parsado
+/// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+pub fn expand(
+    program: &Program,
+    diagnostics: &mut Diagnostics,
+) -> Option<Value> {
+    expand_with_prelude(program, &[], diagnostics)
+}
+
+/// Expande as definições de um programa como em [`expand`], mas com as
+/// definições de `prelude` declaradas antes das do programa.
+///
+/// O programa pode redefinir nomes do prelúdio: a redefinição vale no
+/// programa inteiro, mas não pode usar a versão do prelúdio do mesmo nome
+/// (isso seria um ciclo).
+///
+/// - `program`: programa parsado
+/// - `prelude`: definições disponíveis implicitamente no programa
+/// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+pub fn expand_with_prelude(
+    program: &Program,
+    prelude: &[Binding],
+    diagnostics: &mut Diagnostics,
+) -> Option<Value> {
+    expand_with_lowering(program, prelude, LetLowering::default(), diagnostics)
+}
+
+/// Expande as definições de um programa como em [`expand_with_prelude`],
+/// convertendo os `let` dentro de expressões conforme `lowering`.
+///
+/// - `program`: programa parsado
+/// - `prelude`: definições disponíveis implicitamente no programa
+/// - `lowering`: conversão dos `let` dentro de expressões
+/// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+pub fn expand_with_lowering(
+    program: &Program,
+    prelude: &[Binding],
+    lowering: LetLowering,
+    diagnostics: &mut Diagnostics,
+) -> Option<Value> {
+    let bindings = if program.recursive {
+        tie_recursion(&program.bindings)
+    } else {
+        sort_bindings(&program.bindings, diagnostics)?
+    };
+
+    Expansor::new(&bindings, prelude, lowering)
+        .expand(&program.main_expression, diagnostics)
+}
+
+/// Confere as definições de um arquivo importado, sem expandi-las: acusa
+/// definições duplicadas e definições usadas antes de serem declaradas, como
+/// em [`expand_with_prelude`]. Retorna `true` se nenhum erro foi encontrado.
+///
+/// As definições devem passar antes por [`sort_bindings`] ou, se forem
+/// recursivas, por [`tie_recursion`].
+///
+/// - `bindings`: definições do arquivo
+/// - `prelude`: definições disponíveis implicitamente no arquivo
+/// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+pub fn check_bindings(
+    bindings: &[Binding],
+    prelude: &[Binding],
+    diagnostics: &mut Diagnostics,
+) -> bool {
+    let mut expansor = Expansor::new(bindings, prelude, LetLowering::default());
+    expansor.check(diagnostics);
+    !expansor.failed
+}
+
+/// Estrutura responsável por expandir as definições de um programa.
+struct Expansor<'ast> {
+    ///
+    /// - `bindings`: definições do programa sendo expandido
+    bindings: &'ast [Binding],
+    ///
+    /// - `prelude`: definições declaradas implicitamente antes do programa
+    prelude: &'ast [Binding],
+    ///
+    /// - `binding_indices`: índice da primeira declaração de cada definição
+    binding_indices: HashMap<&'ast str, usize>,
+    ///
+    /// - `lowering`: conversão dos `let` dentro de expressões
+    lowering: LetLowering,
+    ///
+    /// - `failed`: se algum erro foi encontrado durante a expansão
+    failed: bool,
+}
+
+impl<'ast> Expansor<'ast> {
+    /// Cria uma nova estrutura de Expansor
+    ///
+    /// - `bindings`: definições do programa a ser expandido
+    /// - `prelude`: definições declaradas implicitamente antes do programa
+    /// - `lowering`: conversão dos `let` dentro de expressões
+    fn new(
+        bindings: &'ast [Binding],
+        prelude: &'ast [Binding],
+        lowering: LetLowering,
+    ) -> Self {
+        Self {
+            bindings,
+            prelude,
+            binding_indices: HashMap::new(),
+            lowering,
+            failed: false,
+        }
+    }
+
+    /// Itera sobre todas as definições visíveis no programa: primeiro as do
+    /// prelúdio, depois as do próprio programa
+    fn all_bindings(&self) -> impl Iterator<Item = &'ast Binding> {
+        self.prelude.iter().chain(self.bindings.iter())
+    }
+
+    /// Sinaliza um erro no diagnóstico, marcando a expansão como falha
+    ///
+    /// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+    /// - `error`: erro encontrado
+    fn raise(&mut self, diagnostics: &mut Diagnostics, error: Error) {
+        self.failed = true;
+        diagnostics.raise(error);
+    }
+
+    /// Faz a expansão do programa em um termo
+    ///
+    /// - `main_expression`: expressão principal do programa
+    /// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+    fn expand(
+        mut self,
+        main_expression: &Expr,
+        diagnostics: &mut Diagnostics,
+    ) -> Option<Value> {
+        self.check(diagnostics);
+
+        // A expressão principal enxerga todas as definições do programa, mas
+        // os `let` dentro dela também precisam ser conferidos.
+        let main_index = self.prelude.len() + self.bindings.len();
+        self.check_references(main_expression, main_index, &[], diagnostics);
+
+        let main_value = self.lower(main_expression, diagnostics);
+        let bindings = self.all_bindings().collect::<Vec<_>>();
+        let binding_values = bindings
+            .iter()
+            .map(|binding| self.lower(&binding.expression, diagnostics))
+            .collect::<Vec<_>>();
+
+        if self.failed {
+            return None;
+        }
+
+        // Substitui da última definição para a primeira: cada definição
+        // substituída só pode introduzir referências às anteriores, que
+        // serão substituídas em seguida.
+        let mut value = main_value;
+        for (binding, binding_value) in
+            bindings.into_iter().zip(binding_values).rev()
+        {
+            value.replace(&binding.name.content, &binding_value);
+        }
+
+        Some(value)
+    }
+
+    /// Confere as definições do programa, acusando duplicatas e referências a
+    /// definições declaradas depois
+    ///
+    /// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+    fn check(&mut self, diagnostics: &mut Diagnostics) {
+        let bindings = self.bindings;
+        let offset = self.prelude.len();
+
+        self.index_bindings(diagnostics);
+        let components = recursion_components(bindings);
+
+        for (index, binding) in bindings.iter().enumerate() {
+            self.check_references(
+                &binding.expression,
+                offset + index,
+                &components,
+                diagnostics,
+            );
+        }
+    }
+
+    /// Registra o índice da primeira declaração de cada definição, acusando
+    /// definições duplicadas no programa. Redefinir um nome do prelúdio não é
+    /// um erro.
+    ///
+    /// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+    fn index_bindings(&mut self, diagnostics: &mut Diagnostics) {
+        let bindings = self.bindings;
+        let prelude = self.prelude;
+        let offset = prelude.len();
+
+        for (index, binding) in prelude.iter().enumerate() {
+            let name = binding.name.content.as_str();
+            self.binding_indices.entry(name).or_insert(index);
+        }
+
+        let mut declared = HashSet::new();
+        for (index, binding) in bindings.iter().enumerate() {
+            let name = binding.name.content.as_str();
+
+            if declared.insert(name) {
+                self.binding_indices.entry(name).or_insert(offset + index);
+            } else {
+                let error = Error::new(
+                    DuplicatedBinding { name: name.to_owned() },
+                    binding.name.span,
+                );
+                self.raise(diagnostics, error);
+            }
+        }
+    }
+
+    /// Confere se a expressão de uma definição só usa definições declaradas
+    /// antes dela. Uma referência a uma definição declarada depois que forma
+    /// um ciclo de referências é acusada como recursão. As definições de
+    /// `let` dentro da expressão são conferidas da mesma forma.
+    ///
+    /// - `expr`: expressão da definição
+    /// - `binding_index`: índice da definição que contém a expressão
+    /// - `components`: componente de recursão de cada definição do programa
+    /// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+    fn check_references(
+        &mut self,
+        expr: &Expr,
+        binding_index: usize,
+        components: &[usize],
+        diagnostics: &mut Diagnostics,
+    ) {
+        let offset = self.prelude.len();
+        let mut scope_errors = Vec::new();
+        let mut scope = Scope::default();
+        self.collect_scope_errors(
+            expr,
+            binding_index,
+            &mut scope,
+            &mut scope_errors,
+        );
+
+        for scope_error in scope_errors {
+            let error = match scope_error {
+                ScopeError::Later(symbol) => {
+                    let target = self.binding_indices[symbol.content.as_str()];
+                    let recursive = components[target - offset]
+                        == components[binding_index - offset];
+                    later_binding_error(symbol, recursive)
+                }
+                ScopeError::LocalLater { symbol, recursive } => {
+                    later_binding_error(symbol, recursive)
+                }
+                ScopeError::Duplicated(symbol) => {
+                    let name = symbol.content.clone();
+                    Error::new(DuplicatedBinding { name }, symbol.span)
+                }
+            };
+            self.raise(diagnostics, error);
+        }
+    }
+
+    /// Coleta variáveis livres da expressão que se referem a definições ainda
+    /// não declaradas, tanto do programa quanto de `let` dentro da expressão,
+    /// e definições duplicadas nesses `let`
+    ///
+    /// - `expr`: expressão sendo visitada
+    /// - `binding_index`: índice da definição que contém a expressão
+    /// - `scope`: nomes que envolvem a expressão
+    /// - `errors`: erros encontrados até o momento
+    fn collect_scope_errors<'expr>(
+        &self,
+        expr: &'expr Expr,
+        binding_index: usize,
+        scope: &mut Scope<'expr>,
+        errors: &mut Vec<ScopeError<'expr>>,
+    ) {
+        match expr {
+            Expr::Variable(symbol) => {
+                let name = symbol.content.as_str();
+                let declared_index = self.binding_indices.get(name).copied();
+                let declared_later =
+                    declared_index.is_some_and(|index| index >= binding_index);
+                let declared_before =
+                    declared_index.is_some_and(|index| index < binding_index);
+                // Uma definição pendente não esconde um nome de fora: em um
+                // `let` sem `rec`, `y = succ y` usa o `y` que envolve o `let`.
+                let is_visible = scope.bound.contains(&name) || declared_before;
+                let pending =
+                    scope.pending.iter().rev().find(|local| local.name == name);
+
+                match pending {
+                    _ if is_visible => (),
+                    Some(local) => {
+                        let recursive = local.recursive;
+                        errors
+                            .push(ScopeError::LocalLater { symbol, recursive });
+                    }
+                    None if declared_later => {
+                        errors.push(ScopeError::Later(symbol));
+                    }
+                    None => (),
+                }
+            }
+
+            Expr::Number(_) => (),
+
+            Expr::Application { function, argument } => {
+                self.collect_scope_errors(
+                    function,
+                    binding_index,
+                    scope,
+                    errors,
+                );
+                self.collect_scope_errors(
+                    argument,
+                    binding_index,
+                    scope,
+                    errors,
+                );
+            }
+
+            Expr::Lambda { parameter, body } => {
+                scope.bound.push(&parameter.content);
+                self.collect_scope_errors(body, binding_index, scope, errors);
+                scope.bound.pop();
+            }
+
+            Expr::Let { recursive, bindings, body } => {
+                let bound_len = scope.bound.len();
+
+                let mut declared = HashSet::new();
+                for binding in bindings {
+                    if !declared.insert(binding.name.content.as_str()) {
+                        errors.push(ScopeError::Duplicated(&binding.name));
+                    }
+                }
+
+                if *recursive {
+                    let names = bindings
+                        .iter()
+                        .map(|binding| binding.name.content.as_str());
+                    scope.bound.extend(names);
+                    for binding in bindings {
+                        self.collect_scope_errors(
+                            &binding.expression,
+                            binding_index,
+                            scope,
+                            errors,
+                        );
+                    }
+                } else {
+                    // Cada definição só enxerga as anteriores: ela mesma e
+                    // as seguintes ficam pendentes enquanto é visitada.
+                    let components = recursion_components(bindings);
+                    for (index, binding) in bindings.iter().enumerate() {
+                        let pending_len = scope.pending.len();
+                        let later = bindings[index..]
+                            .iter()
+                            .zip(&components[index..])
+                            .map(|(later, &component)| Pending {
+                                name: &later.name.content,
+                                recursive: component == components[index],
+                            });
+                        scope.pending.extend(later);
+
+                        self.collect_scope_errors(
+                            &binding.expression,
+                            binding_index,
+                            scope,
+                            errors,
+                        );
+
+                        scope.pending.truncate(pending_len);
+                        scope.bound.push(&binding.name.content);
+                    }
+                }
+
+                self.collect_scope_errors(body, binding_index, scope, errors);
+                scope.bound.truncate(bound_len);
+            }
+        }
+    }
+
+    /// Converte uma expressão da AST para um termo, sem substituir definições
+    ///
+    /// - `expr`: expressão a ser convertida
+    /// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+    fn lower(&mut self, expr: &Expr, diagnostics: &mut Diagnostics) -> Value {
+        match expr {
+            Expr::Variable(symbol) => Value::Variable(symbol.content.clone()),
+
+            Expr::Number(number) => match u32::try_from(*number) {
+                Ok(converted) => Value::church_numeral(converted),
+                Err(_) => {
+                    let error =
+                        Error::with_no_span(NumberTooBig { number: *number });
+                    self.raise(diagnostics, error);
+                    Value::Variable(number.to_string())
+                }
+            },
+
+            Expr::Application { function, argument } => Value::Application {
+                function: NestedValue::new(self.lower(function, diagnostics)),
+                argument: NestedValue::new(self.lower(argument, diagnostics)),
+            },
+
+            Expr::Lambda { parameter, body } => Value::Lambda {
+                parameter: parameter.content.clone(),
+                body: NestedValue::new(self.lower(body, diagnostics)),
+            },
+
+            Expr::Let { recursive, bindings, body } => {
+                let tied;
+                let bindings = if *recursive {
+                    tied = tie_recursion(bindings);
+                    &tied
+                } else {
+                    bindings
+                };
+
+                // Da última definição para a primeira, como na expansão do
+                // programa.
+                let mut value = self.lower(body, diagnostics);
+                for binding in bindings.iter().rev() {
+                    let name = &binding.name.content;
+                    let binding_value =
+                        self.lower(&binding.expression, diagnostics);
+
+                    value = match self.lowering {
+                        LetLowering::Substitution => {
+                            value.replace(name, &binding_value);
+                            value
+                        }
+                        LetLowering::Redex => Value::Application {
+                            function: NestedValue::new(Value::Lambda {
+                                parameter: name.clone(),
+                                body: NestedValue::new(value),
+                            }),
+                            argument: NestedValue::new(binding_value),
+                        },
+                    };
+                }
+
+                value
+            }
+        }
+    }
+}
+
+/// Nomes que envolvem uma expressão durante a conferência das referências.
+#[derive(Debug, Default)]
+struct Scope<'expr> {
+    ///
+    /// - `bound`: parâmetros de lambdas e definições de `let` visíveis
+    bound: Vec<&'expr str>,
+    ///
+    /// - `pending`: definições de `let` que envolvem a expressão mas ainda
+    ///   não foram declaradas
+    pending: Vec<Pending<'expr>>,
+}
+
+/// Definição de um `let` ainda não declarada no ponto da expressão.
+#[derive(Debug)]
+struct Pending<'expr> {
+    ///
+    /// - `name`: nome da definição
+    name: &'expr str,
+    ///
+    /// - `recursive`: se a definição forma um ciclo com a definição sendo
+    ///   visitada
+    recursive: bool,
+}
+
+/// Erro de escopo encontrado em uma expressão.
+#[derive(Debug)]
+enum ScopeError<'expr> {
+    /// Uso de uma definição do programa declarada depois.
+    Later(&'expr Symbol),
+    /// Uso de uma definição de um `let` declarada depois.
+    LocalLater { symbol: &'expr Symbol, recursive: bool },
+    /// Nome declarado mais de uma vez no mesmo `let`.
+    Duplicated(&'expr Symbol),
+}
+
+/// Erro do uso de uma definição antes de ser declarada, acusado como
+/// recursão se as definições formam um ciclo.
+///
+/// - `symbol`: nome usado
+/// - `recursive`: se as definições formam um ciclo
+fn later_binding_error(symbol: &Symbol, recursive: bool) -> Error {
+    let name = symbol.content.clone();
+    if recursive {
+        Error::new(RecursiveBinding { name }, symbol.span)
+    } else {
+        Error::new(UndefinedBinding { name }, symbol.span)
+    }
+}
@@ -0,0 +1,93 @@
+use pinguim_language::position::Span;
+use std::{error::Error, fmt};
+
+#[derive(Debug, Clone)]
+pub struct DuplicatedBinding {
+    pub name: String,
+}
+
+impl fmt::Display for DuplicatedBinding {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Definição {:?} declarada mais de uma vez", self.name)
+    }
+}
+
+impl Error for DuplicatedBinding {}
+
+#[derive(Debug, Clone)]
+pub struct UndefinedBinding {
+    pub name: String,
+}
+
+impl fmt::Display for UndefinedBinding {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "Definição {:?} usada antes de ser declarada",
+            self.name
+        )
+    }
+}
+
+impl Error for UndefinedBinding {}
+
+#[derive(Debug, Clone)]
+pub struct RecursiveBinding {
+    pub name: String,
+}
+
+impl fmt::Display for RecursiveBinding {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "Definição {:?} usada recursivamente fora de um `let rec`",
+            self.name
+        )
+    }
+}
+
+impl Error for RecursiveBinding {}
+
+#[derive(Debug, Clone)]
+pub struct BindingCycle {
+    pub cycle: Vec<(String, Span)>,
+    pub uses: Vec<Span>,
+}
+
+impl fmt::Display for BindingCycle {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Definições cíclicas fora de um `let rec`: ")?;
+
+        for (name, span) in &self.cycle {
+            write!(
+                formatter,
+                "{:?} (linha {}, coluna {}) -> ",
+                name, span.start.line, span.start.column
+            )?;
+        }
+
+        match self.cycle.first() {
+            Some((name, _)) => write!(formatter, "{:?}", name),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Error for BindingCycle {}
+
+#[derive(Debug, Clone)]
+pub struct NumberTooBig {
+    pub number: u64,
+}
+
+impl fmt::Display for NumberTooBig {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "Número {} é grande demais para um numeral de Church",
+            self.number
+        )
+    }
+}
+
+impl Error for NumberTooBig {}
@@ -0,0 +1,484 @@
+//! Definições recursivas, declaradas com `let rec` (ou com `rec` no começo de
+//! um arquivo importado).
+//!
+//! Em um grupo recursivo, cada definição pode usar qualquer definição do
+//! grupo, inclusive ela mesma e as declaradas depois dela. O grupo é
+//! reescrito em definições comuns, que só usam definições anteriores, com o
+//! combinador de ponto fixo
+//!
+//! ```text
+//! Y = λf. (λx. f (x x)) (λx. f (x x))
+//! ```
+//!
+//! Uma definição que usa a si mesma, `f = e`, vira `f = Y (λf. e)`. Definições
+//! mutuamente recursivas `f1 = e1; ...; fn = en` viram uma tupla calculada por
+//! um único ponto fixo,
+//!
+//! ```text
+//! T = Y ((λg. λt. g (t π1) ... (t πn)) (λf1 ... fn. tupla e1 ... en))
+//! ```
+//!
+//! em que `tupla = λa1 ... an. λs. s a1 ... an` e `πi = λa1 ... an. ai`, e
+//! cada `fi` vira `T πi`. Os combinadores gerados são termos fechados, então
+//! não capturam variáveis das expressões do programa.
+//!
+//! O combinador `Y` só funciona com estratégias preguiçosas, como a ordem
+//! normal e a chamada por nome.
+//!
+//! Fora de um `let rec`, as definições também podem ser declaradas em
+//! qualquer ordem: [`sort_bindings`] as ordena pelas dependências e acusa os
+//! ciclos.
+
+use super::error::BindingCycle;
+use crate::compiler::parser::ast::{Binding, Expr, Symbol};
+use pinguim_language::error::{Diagnostics, Error};
+use pinguim_language::position::Span;
+use std::collections::{HashMap, VecDeque};
+
+/// Reescreve as definições de um grupo recursivo em definições comuns, em que
+/// cada definição só usa definições declaradas antes dela.
+///
+/// As definições são reordenadas para que cada uma venha depois das que usa;
+/// definições que não dependem umas das outras mantêm a ordem original.
+/// Assume que não há definições duplicadas (se houver, somente a primeira é
+/// considerada nas referências).
+///
+/// - `bindings`: definições do grupo recursivo
+pub fn tie_recursion(bindings: &[Binding]) -> Vec<Binding> {
+    let graph = reference_graph(&reference_uses(bindings));
+    let mut tied = Vec::with_capacity(bindings.len());
+
+    for component in strongly_connected_components(&graph) {
+        match component.as_slice() {
+            [index] if !graph[*index].contains(index) => {
+                tied.push(bindings[*index].clone());
+            }
+            [index] => tied.push(tie_single(&bindings[*index])),
+            _ => {
+                let members = component
+                    .iter()
+                    .map(|&index| &bindings[index])
+                    .collect::<Vec<_>>();
+                tied.extend(tie_mutual(&members));
+            }
+        }
+    }
+
+    tied
+}
+
+/// Ordena definições comuns para que cada uma venha depois das que usa;
+/// definições que não dependem umas das outras mantêm a ordem original.
+///
+/// Definições que usam umas às outras em ciclo precisariam de um `let rec`:
+/// cada ciclo é acusado com os nomes que o formam e onde cada um usa o
+/// próximo, e nesse caso nada é retornado. Assume que não há definições
+/// duplicadas (se houver, somente a primeira é considerada nas referências).
+///
+/// - `bindings`: definições, na ordem em que foram declaradas
+/// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+pub fn sort_bindings(
+    bindings: &[Binding],
+    diagnostics: &mut Diagnostics,
+) -> Option<Vec<Binding>> {
+    let uses = reference_uses(bindings);
+    let graph = reference_graph(&uses);
+    let mut sorted = Vec::with_capacity(bindings.len());
+    let mut acyclic = true;
+
+    for component in strongly_connected_components(&graph) {
+        match component.as_slice() {
+            [index] if !graph[*index].contains(index) => {
+                sorted.push(bindings[*index].clone());
+            }
+            _ => {
+                let indices = find_cycle(&graph, &component, component[0]);
+                let cycle = indices
+                    .iter()
+                    .map(|&index| {
+                        let name = &bindings[index].name;
+                        (name.content.clone(), name.span)
+                    })
+                    .collect::<Vec<_>>();
+                // Onde cada definição do ciclo usa a próxima.
+                let uses = indices
+                    .iter()
+                    .zip(indices.iter().cycle().skip(1))
+                    .map(|(&index, &next)| {
+                        let (_, span) = uses[index]
+                            .iter()
+                            .find(|&&(target, _)| target == next)
+                            .expect("aresta do ciclo");
+                        *span
+                    })
+                    .collect::<Vec<_>>();
+                let span = cycle[0].1;
+                let error = BindingCycle { cycle, uses };
+                diagnostics.raise(Error::new(error, span));
+                acyclic = false;
+            }
+        }
+    }
+
+    if acyclic {
+        Some(sorted)
+    } else {
+        None
+    }
+}
+
+/// Encontra o menor ciclo que sai de `start` e volta para ele, passando só
+/// por vértices do componente fortemente conexo de `start`. Retorna os
+/// vértices do ciclo a partir de `start`, sem repeti-lo no final.
+///
+/// # Algoritmo Recursivo
+///
+/// ```haskell
+/// -- Busca em largura: cada caminho da fila começa em `start`.
+/// findCycle :: Graph -> [Int] -> Int -> [Int]
+/// findCycle graph component start = search [[start]] [start]
+///   where
+///     search ((v : path) : queue) seen
+///       | start `elem` (graph !! v) = reverse (v : path)
+///       | otherwise =
+///           let next = [ w | w <- graph !! v, w `elem` component
+///                          , w `notElem` seen ]
+///           in search (queue ++ [w : v : path | w <- next]) (seen ++ next)
+/// ```
+///
+/// - `graph`: lista de adjacência de cada vértice
+/// - `component`: vértices do componente de `start`
+/// - `start`: vértice onde o ciclo começa
+fn find_cycle(
+    graph: &[Vec<usize>],
+    component: &[usize],
+    start: usize,
+) -> Vec<usize> {
+    let mut previous = HashMap::new();
+    let mut queue = VecDeque::from(vec![start]);
+
+    while let Some(vertex) = queue.pop_front() {
+        if graph[vertex].contains(&start) {
+            let mut cycle = vec![vertex];
+            let mut current = vertex;
+            while current != start {
+                current = previous[&current];
+                cycle.push(current);
+            }
+            cycle.reverse();
+            return cycle;
+        }
+
+        for &target in &graph[vertex] {
+            if component.contains(&target)
+                && target != start
+                && !previous.contains_key(&target)
+            {
+                previous.insert(target, vertex);
+                queue.push_back(target);
+            }
+        }
+    }
+
+    // Todo componente com mais de um vértice, ou com um laço, tem um ciclo
+    // passando por qualquer um dos seus vértices.
+    vec![start]
+}
+
+/// Calcula, para cada definição, o índice do seu componente fortemente
+/// conexo no grafo de referências entre as definições. Duas definições são
+/// mutuamente recursivas se e somente se estão no mesmo componente.
+///
+/// - `bindings`: definições
+pub(super) fn recursion_components(bindings: &[Binding]) -> Vec<usize> {
+    let graph = reference_graph(&reference_uses(bindings));
+    let mut components = vec![0; bindings.len()];
+
+    for (component_index, component) in
+        strongly_connected_components(&graph).into_iter().enumerate()
+    {
+        for index in component {
+            components[index] = component_index;
+        }
+    }
+
+    components
+}
+
+/// Monta o grafo de referências entre as definições: a lista de adjacência de
+/// cada definição contém as definições que ela usa.
+///
+/// - `uses`: definições usadas por cada definição, como em [`reference_uses`]
+fn reference_graph(uses: &[Vec<(usize, Span)>]) -> Vec<Vec<usize>> {
+    uses.iter()
+        .map(|targets| targets.iter().map(|&(target, _)| target).collect())
+        .collect()
+}
+
+/// Coleta, para cada definição, as definições que ela usa, junto do primeiro
+/// uso de cada uma.
+///
+/// - `bindings`: definições
+fn reference_uses(bindings: &[Binding]) -> Vec<Vec<(usize, Span)>> {
+    let mut indices = HashMap::new();
+    for (index, binding) in bindings.iter().enumerate() {
+        indices.entry(binding.name.content.as_str()).or_insert(index);
+    }
+
+    bindings
+        .iter()
+        .map(|binding| references(&binding.expression, &indices))
+        .collect()
+}
+
+/// Coleta as definições referenciadas por variáveis livres da expressão, sem
+/// repetições, junto do trecho do primeiro uso de cada uma. Usos ligados
+/// dentro da expressão (ver [`Expr::variable_uses`]) não contam.
+///
+/// - `expr`: expressão de uma definição
+/// - `indices`: índice de cada definição, pelo nome
+fn references(
+    expr: &Expr,
+    indices: &HashMap<&str, usize>,
+) -> Vec<(usize, Span)> {
+    let mut found: Vec<(usize, Span)> = Vec::new();
+
+    for (symbol, binder) in expr.variable_uses() {
+        if binder.is_some() {
+            continue;
+        }
+        if let Some(&index) = indices.get(symbol.content.as_str()) {
+            if found.iter().all(|&(target, _)| target != index) {
+                found.push((index, symbol.span));
+            }
+        }
+    }
+
+    found
+}
+
+/// Encontra os componentes fortemente conexos de um grafo com o algoritmo de
+/// Tarjan. Cada componente vem depois de todos os componentes alcançáveis a
+/// partir dele, e os vértices de um componente ficam em ordem crescente.
+///
+/// # Algoritmo Recursivo
+///
+/// ```haskell
+/// -- Estado: próximo índice, índice e menor índice alcançável de cada
+/// -- vértice, pilha de vértices e componentes encontrados.
+/// visit :: Graph -> Int -> State -> State
+/// visit graph v state =
+///   let state' = foldl (edge v) (push v (number v state)) (graph !! v)
+///   in if low state' v == index state' v
+///        then popComponent v state'
+///        else state'
+///   where
+///     edge v state w
+///       | not (numbered state w) =
+///           let state' = visit graph w state
+///           in setLow v (min (low state' v) (low state' w)) state'
+///       | onStack state w = setLow v (min (low state v) (index state w)) state
+///       | otherwise = state
+/// ```
+///
+/// - `graph`: lista de adjacência de cada vértice
+fn strongly_connected_components(graph: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut next_index = 0;
+    let mut indices: Vec<Option<usize>> = vec![None; graph.len()];
+    let mut low_links = vec![0; graph.len()];
+    let mut on_stack = vec![false; graph.len()];
+    let mut vertex_stack = Vec::new();
+    let mut components = Vec::new();
+
+    for root in 0..graph.len() {
+        if indices[root].is_some() {
+            continue;
+        }
+
+        // Cada quadro guarda o vértice visitado e a próxima aresta dele.
+        let mut call_stack = vec![(root, 0)];
+        indices[root] = Some(next_index);
+        low_links[root] = next_index;
+        next_index += 1;
+        vertex_stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(frame) = call_stack.last_mut() {
+            let vertex = frame.0;
+
+            if let Some(&target) = graph[vertex].get(frame.1) {
+                frame.1 += 1;
+
+                match indices[target] {
+                    None => {
+                        indices[target] = Some(next_index);
+                        low_links[target] = next_index;
+                        next_index += 1;
+                        vertex_stack.push(target);
+                        on_stack[target] = true;
+                        call_stack.push((target, 0));
+                    }
+                    Some(target_index) if on_stack[target] => {
+                        low_links[vertex] = low_links[vertex].min(target_index);
+                    }
+                    Some(_) => (),
+                }
+            } else {
+                call_stack.pop();
+
+                if let Some(&(parent, _)) = call_stack.last() {
+                    low_links[parent] =
+                        low_links[parent].min(low_links[vertex]);
+                }
+
+                if indices[vertex] == Some(low_links[vertex]) {
+                    let mut component = Vec::new();
+                    while let Some(member) = vertex_stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == vertex {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+    }
+
+    components
+}
+
+/// Reescreve uma definição que usa a si mesma: `f = e` vira `f = Y (λf. e)`.
+///
+/// - `binding`: definição recursiva
+fn tie_single(binding: &Binding) -> Binding {
+    let span = binding.name.span;
+    let function = lambda(binding.name.clone(), binding.expression.clone());
+
+    Binding {
+        name: binding.name.clone(),
+        expression: application(fixed_point(span), function),
+        doc: binding.doc.clone(),
+    }
+}
+
+/// Reescreve definições mutuamente recursivas, calculando todas como uma
+/// tupla por um único ponto fixo.
+///
+/// - `members`: definições mutuamente recursivas, na ordem original
+fn tie_mutual(members: &[&Binding]) -> Vec<Binding> {
+    let count = members.len();
+    let span = members[0].name.span;
+
+    // λf1 ... fn. tupla e1 ... en
+    let mut tuple = tuple_constructor(count, span);
+    for member in members {
+        tuple = application(tuple, member.expression.clone());
+    }
+    let mut step = tuple;
+    for member in members.iter().rev() {
+        step = lambda(member.name.clone(), step);
+    }
+
+    // Y ((λg. λt. g (t π1) ... (t πn)) passo)
+    let tupled = application(tupled_step(count, span), step);
+    let fixed_tuple = application(fixed_point(span), tupled);
+
+    members
+        .iter()
+        .enumerate()
+        .map(|(index, member)| Binding {
+            name: member.name.clone(),
+            expression: application(
+                fixed_tuple.clone(),
+                projection(index, count, span),
+            ),
+            doc: member.doc.clone(),
+        })
+        .collect()
+}
+
+/// Combinador de ponto fixo `λf. (λx. f (x x)) (λx. f (x x))`.
+///
+/// - `span`: localização atribuída aos nomes gerados
+fn fixed_point(span: Span) -> Expr {
+    let half = lambda(
+        symbol("x", span),
+        application(
+            variable("f", span),
+            application(variable("x", span), variable("x", span)),
+        ),
+    );
+    lambda(symbol("f", span), application(half.clone(), half))
+}
+
+/// Construtor de tuplas `λa1 ... an. λs. s a1 ... an`.
+///
+/// - `count`: tamanho da tupla
+/// - `span`: localização atribuída aos nomes gerados
+fn tuple_constructor(count: usize, span: Span) -> Expr {
+    let mut body = variable("s", span);
+    for index in 0..count {
+        body = application(body, variable(&element_name(index), span));
+    }
+
+    let mut tuple = lambda(symbol("s", span), body);
+    for index in (0..count).rev() {
+        tuple = lambda(symbol(&element_name(index), span), tuple);
+    }
+    tuple
+}
+
+/// Projeção `λa1 ... an. ai` do elemento `index` de uma tupla.
+///
+/// - `index`: índice do elemento, a partir de 0
+/// - `count`: tamanho da tupla
+/// - `span`: localização atribuída aos nomes gerados
+fn projection(index: usize, count: usize, span: Span) -> Expr {
+    let mut projection = variable(&element_name(index), span);
+    for element in (0..count).rev() {
+        projection = lambda(symbol(&element_name(element), span), projection);
+    }
+    projection
+}
+
+/// Combinador `λg. λt. g (t π1) ... (t πn)`, que passa para `g` cada elemento
+/// da tupla `t`.
+///
+/// - `count`: tamanho da tupla
+/// - `span`: localização atribuída aos nomes gerados
+fn tupled_step(count: usize, span: Span) -> Expr {
+    let mut body = variable("g", span);
+    for index in 0..count {
+        let element =
+            application(variable("t", span), projection(index, count, span));
+        body = application(body, element);
+    }
+    lambda(symbol("g", span), lambda(symbol("t", span), body))
+}
+
+/// Nome do parâmetro do elemento `index` de uma tupla.
+fn element_name(index: usize) -> String {
+    format!("a{}", index + 1)
+}
+
+fn symbol(name: &str, span: Span) -> Symbol {
+    Symbol { content: name.to_owned(), span }
+}
+
+fn variable(name: &str, span: Span) -> Expr {
+    Expr::Variable(symbol(name, span))
+}
+
+fn lambda(parameter: Symbol, body: Expr) -> Expr {
+    Expr::Lambda { parameter, body: Box::new(body) }
+}
+
+fn application(function: Expr, argument: Expr) -> Expr {
+    Expr::Application {
+        function: Box::new(function),
+        argument: Box::new(argument),
+    }
+}
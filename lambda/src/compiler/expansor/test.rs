@@ -0,0 +1,288 @@
+use crate::compiler::expansor::error::BindingCycle;
+use crate::compiler::expansor::{expand, expand_with_lowering, LetLowering};
+use crate::compiler::lexer::generate_tokens;
+use crate::compiler::parser::parse;
+use crate::value::{NestedValue, Value};
+use pinguim_language::error::Diagnostics;
+
+fn expand_source(source_code: &str) -> (Option<Value>, Diagnostics) {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let ast = parse(tokens, &mut diagnostics).expect("programa válido");
+    let value = expand(&ast, &mut diagnostics);
+    (value, diagnostics)
+}
+
+#[test]
+fn expand_numeral() {
+    let (value, diagnostics) = expand_source("2");
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(value, Some(Value::church_numeral(2)));
+}
+
+#[test]
+fn expand_binding() {
+    let (value, diagnostics) = expand_source("let id = \\x. x; in id y");
+
+    assert!(diagnostics.is_ok());
+
+    // (λx. x) y
+    let expected = Value::Application {
+        function: NestedValue::new(Value::Lambda {
+            parameter: String::from("x"),
+            body: NestedValue::new(Value::Variable(String::from("x"))),
+        }),
+        argument: NestedValue::new(Value::Variable(String::from("y"))),
+    };
+
+    assert_eq!(value, Some(expected));
+}
+
+#[test]
+fn expand_dependent_bindings() {
+    let (value, diagnostics) =
+        expand_source("let id = \\x. x; app = \\f. id f; in app");
+
+    assert!(diagnostics.is_ok());
+
+    // λf. (λx. x) f
+    let expected = Value::Lambda {
+        parameter: String::from("f"),
+        body: NestedValue::new(Value::Application {
+            function: NestedValue::new(Value::Lambda {
+                parameter: String::from("x"),
+                body: NestedValue::new(Value::Variable(String::from("x"))),
+            }),
+            argument: NestedValue::new(Value::Variable(String::from("f"))),
+        }),
+    };
+
+    assert_eq!(value, Some(expected));
+}
+
+#[test]
+fn expand_avoids_capture() {
+    let (value, diagnostics) = expand_source("let k = \\x. y; in \\y. k");
+
+    assert!(diagnostics.is_ok());
+
+    // λy_. λx. y
+    let expected = Value::Lambda {
+        parameter: String::from("y_"),
+        body: NestedValue::new(Value::Lambda {
+            parameter: String::from("x"),
+            body: NestedValue::new(Value::Variable(String::from("y"))),
+        }),
+    };
+
+    assert_eq!(value, Some(expected));
+}
+
+#[test]
+fn expand_param_shadows_binding() {
+    let (value, diagnostics) = expand_source("let a = \\b. b; b = a; in b");
+
+    assert!(diagnostics.is_ok());
+
+    // λb. b
+    let expected = Value::Lambda {
+        parameter: String::from("b"),
+        body: NestedValue::new(Value::Variable(String::from("b"))),
+    };
+
+    assert_eq!(value, Some(expected));
+}
+
+#[test]
+fn duplicated_binding() {
+    let (value, diagnostics) = expand_source("let a = x; a = y; in a");
+
+    assert!(diagnostics.is_err());
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        &["Definição \"a\" declarada mais de uma vez, na linha 1 e coluna 12"]
+    );
+    assert_eq!(value, None);
+}
+
+#[test]
+fn binding_used_before_declaration() {
+    let (value, diagnostics) = expand_source("let a = b; b = \\x. x; in a");
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(value, expand_source("\\x. x").0);
+}
+
+#[test]
+fn bindings_in_any_order() {
+    let (value, diagnostics) =
+        expand_source("let c = b b; b = \\x. a x; a = y; in c");
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(value, expand_source("(\\x. y x) (\\x. y x)").0);
+}
+
+#[test]
+fn binding_uses_itself() {
+    let (value, diagnostics) = expand_source("let f = \\x. f x; in f");
+
+    assert!(diagnostics.is_err());
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        &["Definições cíclicas fora de um `let rec`: \"f\" (linha 1, coluna \
+           5) -> \"f\", na linha 1 e coluna 5"]
+    );
+    assert_eq!(value, None);
+
+    // O uso recursivo também é apontado, além do nome da definição.
+    let error = diagnostics.iter().next().unwrap();
+    let cycle = error.cause().downcast_ref::<BindingCycle>().unwrap();
+    let uses = cycle
+        .uses
+        .iter()
+        .map(|span| (span.start.line, span.start.column))
+        .collect::<Vec<_>>();
+    assert_eq!(uses, &[(1, 13)]);
+}
+
+#[test]
+fn mutual_recursion_outside_let_rec() {
+    let (value, diagnostics) =
+        expand_source("let a = \\x. b x; c = a; b = \\x. a x; in a");
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        &["Definições cíclicas fora de um `let rec`: \"a\" (linha 1, coluna \
+           5) -> \"b\" (linha 1, coluna 25) -> \"a\", na linha 1 e coluna 5"]
+    );
+    assert_eq!(value, None);
+}
+
+#[test]
+fn let_rec_uses_fixed_point() {
+    let (value, diagnostics) = expand_source("let rec f = \\x. f x; in f");
+    let (expected, _) =
+        expand_source("(\\f. (\\x. f (x x)) (\\x. f (x x))) (\\f. \\x. f x)");
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn let_rec_without_recursion() {
+    let (value, diagnostics) = expand_source("let rec a = b; b = \\x. x; in a");
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(value, expand_source("\\x. x").0);
+}
+
+#[test]
+fn expand_nested_let() {
+    let (value, diagnostics) =
+        expand_source("\\n. let sq = n n; b = sq sq; in b");
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(value, expand_source("\\n. n n (n n)").0);
+}
+
+#[test]
+fn expand_nested_let_as_redex() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("\\n. let a = n; in a", &mut diagnostics);
+    let ast = parse(tokens, &mut diagnostics).expect("programa válido");
+    let value =
+        expand_with_lowering(&ast, &[], LetLowering::Redex, &mut diagnostics);
+
+    assert!(diagnostics.is_ok());
+
+    // λn. (λa. a) n
+    let expected = Value::Lambda {
+        parameter: String::from("n"),
+        body: NestedValue::new(Value::Application {
+            function: NestedValue::new(Value::Lambda {
+                parameter: String::from("a"),
+                body: NestedValue::new(Value::Variable(String::from("a"))),
+            }),
+            argument: NestedValue::new(Value::Variable(String::from("n"))),
+        }),
+    };
+    assert_eq!(value, Some(expected));
+}
+
+#[test]
+fn nested_let_shadows_binding() {
+    let (value, diagnostics) =
+        expand_source("let a = y; f = \\x. let a = x; in a; in f a");
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(value, expand_source("(\\x. x) y").0);
+}
+
+#[test]
+fn nested_let_uses_shadowed_binding() {
+    let (value, diagnostics) = expand_source("let y = a; in let y = f y; in y");
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(value, expand_source("f a").0);
+
+    let (value, diagnostics) =
+        expand_source("\\y. let y = f y; z = y; in let y = g y; in y z");
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(value, expand_source("\\y. g (f y) (f y)").0);
+}
+
+#[test]
+fn nested_binding_used_before_declaration() {
+    let (value, diagnostics) =
+        expand_source("\\n. let a = b; b = n; in let f = \\x. f x; in a");
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        &[
+            "Definição \"b\" usada antes de ser declarada, na linha 1 e \
+             coluna 13",
+            "Definição \"f\" usada recursivamente fora de um `let rec`, na \
+             linha 1 e coluna 38",
+        ]
+    );
+    assert_eq!(value, None);
+}
+
+#[test]
+fn nested_binding_sees_outer_names() {
+    let (value, diagnostics) =
+        expand_source("let b = y; in \\a. let c = a b; a = c; in a");
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(value, expand_source("\\a. a y").0);
+}
+
+#[test]
+fn nested_duplicated_binding() {
+    let (value, diagnostics) = expand_source("\\n. let a = x; a = y; in a");
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        &["Definição \"a\" declarada mais de uma vez, na linha 1 e coluna 16"]
+    );
+    assert_eq!(value, None);
+}
@@ -0,0 +1,314 @@
+//! Esse módulo exporta o sistema de importação de arquivos: a abstração de
+//! [`Loader`], que carrega o código-fonte de um caminho, e a resolução das
+//! importações `import "caminho.lam";` de um programa.
+//!
+//! Caminhos são sempre separados por `/` e, quando relativos, são resolvidos
+//! a partir da pasta do arquivo que os importa. As definições de um arquivo
+//! importado ficam disponíveis para quem o importa da mesma forma que as do
+//! prelúdio: podem ser usadas e redefinidas.
+
+#[cfg(test)]
+mod test;
+
+pub mod error;
+
+use crate::compiler::expansor::check_bindings;
+use crate::compiler::expansor::recursion::{sort_bindings, tie_recursion};
+use crate::compiler::lexer::generate_tokens;
+use crate::compiler::parser::ast::{Binding, Import};
+use crate::compiler::parser::parse_library;
+use error::{ImportCycle, ImportFailed, InFile};
+use pinguim_language::error::{Diagnostics, Error};
+use pinguim_language::position::Span;
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::PathBuf,
+};
+
+/// Carrega o código-fonte de arquivos a partir de caminhos já resolvidos
+/// (veja [`resolve`]).
+pub trait Loader {
+    /// Carrega o código-fonte do arquivo no caminho dado.
+    fn load(&self, path: &str) -> io::Result<String>;
+}
+
+/// Carrega arquivos do sistema de arquivos. Caminhos relativos são buscados a
+/// partir de uma pasta raiz; caminhos absolutos são usados como estão.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsLoader {
+    ///
+    /// - `root`: pasta a partir da qual os caminhos são buscados
+    root: PathBuf,
+}
+
+impl FsLoader {
+    /// Cria um carregador que busca os caminhos a partir da pasta `root`.
+    pub fn new<P>(root: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self { root: root.into() }
+    }
+}
+
+impl Default for FsLoader {
+    /// Cria um carregador que busca os caminhos a partir da pasta atual.
+    fn default() -> Self {
+        Self::new(".")
+    }
+}
+
+impl Loader for FsLoader {
+    fn load(&self, path: &str) -> io::Result<String> {
+        fs::read_to_string(self.root.join(path))
+    }
+}
+
+/// Carrega arquivos guardados em memória, indexados pelo caminho (útil, por
+/// exemplo, quando não há sistema de arquivos, como no WebAssembly).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryLoader {
+    ///
+    /// - `files`: código-fonte de cada arquivo, indexado pelo caminho
+    files: HashMap<String, String>,
+}
+
+impl MemoryLoader {
+    /// Cria um carregador sem nenhum arquivo.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adiciona um arquivo, substituindo o anterior de mesmo caminho. O
+    /// caminho é normalizado com [`resolve`].
+    pub fn insert<P, S>(&mut self, path: P, source: S)
+    where
+        P: AsRef<str>,
+        S: Into<String>,
+    {
+        self.files.insert(resolve(None, path.as_ref()), source.into());
+    }
+}
+
+impl Loader for MemoryLoader {
+    fn load(&self, path: &str) -> io::Result<String> {
+        match self.files.get(path) {
+            Some(source) => Ok(source.clone()),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "arquivo não encontrado",
+            )),
+        }
+    }
+}
+
+/// Resolve o caminho de uma importação, removendo componentes `.` e `..`.
+///
+/// - `importer`: caminho (já resolvido) do arquivo que faz a importação, se
+///   houver; caminhos relativos partem da pasta desse arquivo
+/// - `path`: caminho escrito na importação
+///
+/// # Exemplo
+///
+/// ```
+/// use lambda::compiler::import::resolve;
+///
+/// assert_eq!(resolve(Some("lib/num.lam"), "../bool.lam"), "bool.lam");
+/// assert_eq!(resolve(Some("lib/num.lam"), "./pair.lam"), "lib/pair.lam");
+/// assert_eq!(resolve(Some("lib/num.lam"), "/bool.lam"), "/bool.lam");
+/// ```
+pub fn resolve(importer: Option<&str>, path: &str) -> String {
+    let absolute = path.starts_with('/');
+    let directory = match importer {
+        Some(importer) if !absolute => {
+            importer.rfind('/').map_or("", |index| &importer[..index])
+        }
+        _ => "",
+    };
+    let absolute = absolute || directory.starts_with('/');
+
+    let mut components = Vec::new();
+    for component in directory.split('/').chain(path.split('/')) {
+        match component {
+            "" | "." => (),
+            ".." if components.last().is_some_and(|&last| last != "..") => {
+                components.pop();
+            }
+            ".." if absolute => (),
+            _ => components.push(component),
+        }
+    }
+
+    let joined = components.join("/");
+    if absolute {
+        format!("/{}", joined)
+    } else {
+        joined
+    }
+}
+
+/// Carrega recursivamente as importações de um programa, retornando as
+/// definições disponíveis para ele: as de `prelude` seguidas das definições
+/// dos arquivos importados, na ordem em que foram importados. Cada arquivo é
+/// carregado uma única vez, mesmo que seja importado por vários arquivos.
+///
+/// Erros encontrados dentro de arquivos importados são marcados com o
+/// caminho do arquivo (veja [`InFile`]).
+///
+/// - `imports`: importações do programa
+/// - `path`: caminho (já resolvido) do programa, se houver
+/// - `loader`: carregador dos arquivos importados
+/// - `prelude`: definições disponíveis implicitamente em todos os arquivos
+/// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+pub fn load_imports(
+    imports: &[Import],
+    path: Option<&str>,
+    loader: &dyn Loader,
+    prelude: Vec<Binding>,
+    diagnostics: &mut Diagnostics,
+) -> Vec<Binding> {
+    let mut importer = Importer {
+        loader,
+        bindings: prelude,
+        loaded: HashSet::new(),
+        stack: path.map(str::to_owned).into_iter().collect(),
+    };
+    importer.import_all(imports, path, diagnostics);
+    importer.bindings
+}
+
+/// Estrutura responsável por carregar as importações de um programa.
+struct Importer<'loader> {
+    ///
+    /// - `loader`: carregador dos arquivos importados
+    loader: &'loader dyn Loader,
+    ///
+    /// - `bindings`: prelúdio e definições dos arquivos já importados
+    bindings: Vec<Binding>,
+    ///
+    /// - `loaded`: caminhos dos arquivos já importados
+    loaded: HashSet<String>,
+    ///
+    /// - `stack`: caminhos dos arquivos sendo importados no momento, do
+    ///   programa até o mais recente, usados para detectar ciclos
+    stack: Vec<String>,
+}
+
+impl<'loader> Importer<'loader> {
+    /// Importa cada arquivo de uma lista de importações
+    ///
+    /// - `imports`: importações de um arquivo
+    /// - `importer`: caminho do arquivo que faz as importações, se houver
+    /// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+    fn import_all(
+        &mut self,
+        imports: &[Import],
+        importer: Option<&str>,
+        diagnostics: &mut Diagnostics,
+    ) {
+        for import in imports {
+            let path = resolve(importer, &import.path);
+            self.import(path, import.span, importer, diagnostics);
+        }
+    }
+
+    /// Importa um arquivo, junto com os arquivos que ele importa
+    ///
+    /// - `path`: caminho resolvido do arquivo importado
+    /// - `span`: localização da importação no arquivo que a faz
+    /// - `importer`: caminho do arquivo que faz a importação, se houver
+    /// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+    fn import(
+        &mut self,
+        path: String,
+        span: Span,
+        importer: Option<&str>,
+        diagnostics: &mut Diagnostics,
+    ) {
+        if let Some(start) = self.stack.iter().position(|open| *open == path) {
+            let mut cycle = self.stack[start..].to_vec();
+            cycle.push(path);
+            let error = Error::new(ImportCycle { cycle }, span);
+            raise_in(diagnostics, importer, error);
+            return;
+        }
+
+        if self.loaded.contains(&path) {
+            return;
+        }
+
+        let source = match self.loader.load(&path) {
+            Ok(source) => source,
+            Err(cause) => {
+                let cause = cause.to_string();
+                let error = Error::new(ImportFailed { path, cause }, span);
+                raise_in(diagnostics, importer, error);
+                return;
+            }
+        };
+
+        let mut local_diagnostics = Diagnostics::new();
+        let tokens = generate_tokens(&source, &mut local_diagnostics);
+        let library = if local_diagnostics.is_ok() {
+            parse_library(tokens, &mut local_diagnostics)
+        } else {
+            None
+        };
+
+        if let Some(library) = library.filter(|_| local_diagnostics.is_ok()) {
+            self.stack.push(path.clone());
+            self.import_all(&library.imports, Some(&path), diagnostics);
+            self.stack.pop();
+
+            let bindings = if library.recursive {
+                Some(tie_recursion(&library.bindings))
+            } else {
+                sort_bindings(&library.bindings, &mut local_diagnostics)
+            };
+            if let Some(bindings) = bindings {
+                let valid = check_bindings(
+                    &bindings,
+                    &self.bindings,
+                    &mut local_diagnostics,
+                );
+                if valid {
+                    self.bindings.extend(bindings);
+                }
+            }
+        }
+
+        for error in local_diagnostics.iter() {
+            diagnostics.raise(in_file(&path, error));
+        }
+
+        self.loaded.insert(path);
+    }
+}
+
+/// Sinaliza um erro encontrado em um arquivo, marcando-o com o caminho do
+/// arquivo se ele não for o programa principal
+///
+/// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+/// - `file`: caminho do arquivo onde o erro foi encontrado, se houver
+/// - `error`: erro encontrado
+fn raise_in(diagnostics: &mut Diagnostics, file: Option<&str>, error: Error) {
+    match file {
+        Some(path) => diagnostics.raise(in_file(path, &error)),
+        None => diagnostics.raise(error),
+    }
+}
+
+/// Cria um erro igual ao dado, mas marcado com o caminho do arquivo onde foi
+/// encontrado
+///
+/// - `path`: caminho do arquivo onde o erro foi encontrado
+/// - `error`: erro encontrado
+fn in_file(path: &str, error: &Error) -> Error {
+    let cause =
+        InFile { path: path.to_owned(), cause: error.cause().to_string() };
+    match error.span() {
+        Some(span) => Error::new(cause, span),
+        None => Error::with_no_span(cause),
+    }
+}
@@ -0,0 +1,55 @@
+use std::{error::Error, fmt};
+
+#[derive(Debug, Clone)]
+pub struct ImportFailed {
+    pub path: String,
+    pub cause: String,
+}
+
+impl fmt::Display for ImportFailed {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "Não foi possível importar {:?}: {}",
+            self.path, self.cause
+        )
+    }
+}
+
+impl Error for ImportFailed {}
+
+#[derive(Debug, Clone)]
+pub struct ImportCycle {
+    pub cycle: Vec<String>,
+}
+
+impl fmt::Display for ImportCycle {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Importação cíclica: ")?;
+
+        for (index, path) in self.cycle.iter().enumerate() {
+            if index > 0 {
+                write!(formatter, " -> ")?;
+            }
+            write!(formatter, "{:?}", path)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ImportCycle {}
+
+#[derive(Debug, Clone)]
+pub struct InFile {
+    pub path: String,
+    pub cause: String,
+}
+
+impl fmt::Display for InFile {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Em {:?}: {}", self.path, self.cause)
+    }
+}
+
+impl Error for InFile {}
@@ -0,0 +1,195 @@
+use super::{resolve, FsLoader, MemoryLoader};
+use crate::compiler::{compile, compile_file, compile_with_loader, Options};
+use crate::interpreter::run_once;
+use crate::value::Value;
+use pinguim_language::error::Diagnostics;
+use std::{env, fs};
+
+fn errors(diagnostics: Diagnostics) -> Vec<String> {
+    diagnostics.iter().map(ToString::to_string).collect()
+}
+
+fn library_loader() -> MemoryLoader {
+    let mut loader = MemoryLoader::new();
+    loader.insert("lib/bool.lam", "yes = \\t f. t;\nno = \\t f. f;\n");
+    loader.insert(
+        "lib/logic.lam",
+        "import \"bool.lam\";\nneg = \\b. b no yes;\n",
+    );
+    loader.insert(
+        "lib/both.lam",
+        "import \"./bool.lam\";\nimport \"logic.lam\";\n\
+         both = \\a b. a b no;\n",
+    );
+    loader
+}
+
+#[test]
+fn resolve_paths() {
+    assert_eq!(resolve(None, "a/./b/../c.lam"), "a/c.lam");
+    assert_eq!(resolve(None, "../c.lam"), "../c.lam");
+    assert_eq!(resolve(Some("a/b.lam"), "c.lam"), "a/c.lam");
+    assert_eq!(resolve(Some("a/b.lam"), "../../c.lam"), "../c.lam");
+    assert_eq!(resolve(Some("/a/b.lam"), "../../c.lam"), "/c.lam");
+}
+
+#[test]
+fn import_library() {
+    let source = "import \"lib/logic.lam\";\nneg yes";
+    let options = Options { prelude: false, ..Options::default() };
+    let value =
+        compile_with_loader(source, &library_loader(), &options).unwrap();
+
+    let expected = Value::church_boolean(false);
+    assert!(run_once(value).beta_equiv(&expected));
+}
+
+#[test]
+fn import_shared_library_once() {
+    // `both.lam` importa `bool.lam` diretamente e através de `logic.lam`.
+    let source = "import \"lib/both.lam\";\nboth yes (neg no)";
+    let value =
+        compile_with_loader(source, &library_loader(), &Options::default())
+            .unwrap();
+
+    let expected = Value::church_boolean(true);
+    assert!(run_once(value).beta_equiv(&expected));
+}
+
+#[test]
+fn program_shadows_import() {
+    let source = "import \"lib/bool.lam\";\nlet yes = a; in yes";
+    let value =
+        compile_with_loader(source, &library_loader(), &Options::default())
+            .unwrap();
+
+    assert_eq!(value, Value::Variable(String::from("a")));
+}
+
+#[test]
+fn import_cycle() {
+    let mut loader = MemoryLoader::new();
+    loader.insert("a.lam", "import \"b.lam\";\nx = 1;");
+    loader.insert("b.lam", "import \"a.lam\";\ny = 2;");
+
+    let source = "import \"a.lam\";\nx";
+    let diagnostics =
+        compile_with_loader(source, &loader, &Options::default()).unwrap_err();
+
+    assert_eq!(
+        errors(diagnostics),
+        &["Em \"b.lam\": Importação cíclica: \"a.lam\" -> \"b.lam\" -> \
+           \"a.lam\", na linha 1 e coluna 8"]
+    );
+}
+
+#[test]
+fn import_cycle_through_main_file() {
+    let mut loader = MemoryLoader::new();
+    loader.insert("main.lam", "import \"lib.lam\";\nx");
+    loader.insert("lib.lam", "import \"main.lam\";\nx = 1;");
+
+    let diagnostics =
+        compile_file("main.lam", &loader, &Options::default()).unwrap_err();
+
+    assert_eq!(
+        errors(diagnostics),
+        &["Em \"lib.lam\": Importação cíclica: \"main.lam\" -> \"lib.lam\" \
+           -> \"main.lam\", na linha 1 e coluna 8"]
+    );
+}
+
+#[test]
+fn import_missing_file() {
+    let diagnostics = compile("import \"x.lam\";\n1").unwrap_err();
+
+    assert_eq!(
+        errors(diagnostics),
+        &["Não foi possível importar \"x.lam\": arquivo não encontrado, na \
+           linha 1 e coluna 8"]
+    );
+}
+
+#[test]
+fn errors_in_library_have_file_and_span() {
+    let source = "import \"lib.lam\";\nid";
+    let mut loader = MemoryLoader::new();
+    loader.insert("lib.lam", "id = \\x. x;\n\nid = \\y. y;\nbad = (x;");
+
+    let diagnostics =
+        compile_with_loader(source, &loader, &Options::default()).unwrap_err();
+
+    assert_eq!(
+        errors(diagnostics),
+        &["Em \"lib.lam\": Parentesis aberto mas não fechado, na linha 4 e \
+           coluna 7"]
+    );
+
+    let mut loader = MemoryLoader::new();
+    loader.insert("lib.lam", "id = \\x. x;\n\nid = \\y. y;");
+
+    let diagnostics =
+        compile_with_loader(source, &loader, &Options::default()).unwrap_err();
+
+    assert_eq!(
+        errors(diagnostics),
+        &["Em \"lib.lam\": Definição \"id\" declarada mais de uma vez, na \
+           linha 3 e coluna 1"]
+    );
+}
+
+#[test]
+fn compile_file_from_filesystem() {
+    let root = env::temp_dir().join("pinguim-lambda-import-test");
+    fs::create_dir_all(root.join("lib")).unwrap();
+    fs::write(root.join("lib/two.lam"), "two = \\f x. f (f x);").unwrap();
+    fs::write(root.join("main.lam"), "import \"lib/two.lam\";\ntwo").unwrap();
+
+    let loader = FsLoader::new(root);
+    let value = compile_file("main.lam", &loader, &Options::default()).unwrap();
+
+    assert_eq!(value, Value::church_numeral(2));
+}
+
+#[test]
+fn import_recursive_library() {
+    let mut loader = MemoryLoader::new();
+    loader.insert(
+        "nat.lam",
+        "rec\n\
+         sum = \\n. if (is_zero n) 0 (plus n (sum (pred n)));\n",
+    );
+    let source = "import \"nat.lam\";\nsum 3";
+    let value =
+        compile_with_loader(source, &loader, &Options::default()).unwrap();
+
+    assert_eq!(run_once(value).church_numeral_to_int(), Some(6));
+}
+
+#[test]
+fn import_library_in_any_order() {
+    let mut loader = MemoryLoader::new();
+    loader.insert("k.lam", "k2 = \\x. k x;\nk = \\x y. x;\n");
+    let options = Options { prelude: false, ..Options::default() };
+    let value =
+        compile_with_loader("import \"k.lam\";\nk2 a b", &loader, &options)
+            .unwrap();
+
+    assert_eq!(run_once(value), Value::Variable(String::from("a")));
+}
+
+#[test]
+fn library_binding_cycle() {
+    let mut loader = MemoryLoader::new();
+    loader.insert("c.lam", "a = b;\nb = a;\n");
+    let source = "import \"c.lam\";\na";
+    let diagnostics =
+        compile_with_loader(source, &loader, &Options::default()).unwrap_err();
+
+    assert_eq!(
+        errors(diagnostics),
+        &["Em \"c.lam\": Definições cíclicas fora de um `let rec`: \"a\" \
+           (linha 1, coluna 1) -> \"b\" (linha 2, coluna 1) -> \"a\", na \
+           linha 1 e coluna 1"]
+    );
+}
@@ -4,9 +4,12 @@ mod test;
 pub mod error;
 pub mod token;
 
-use error::{BadCommentStart, InvalidChar};
+use error::{
+    BadCommentStart, InvalidChar, UnterminatedComment, UnterminatedString,
+};
 use std::{error::Error as StdError, iter::Peekable, str};
 use token::{Token, TokenType};
+use unicode_xid::UnicodeXID;
 
 use pinguim_language::{
     error::{Diagnostics, Error},
@@ -69,8 +72,14 @@ impl<'src> Lexer<'src> {
 
         self.clear_current();
 
-        if self.is_identifier() {
+        if self.is_identifier_start() {
             Ok(self.tokenize_ident())
+        } else if self.is_ascii_arrow() {
+            Ok(self.tokenize_ascii_arrow())
+        } else if self.is_doc_comment_start() {
+            Ok(self.tokenize_doc_comment())
+        } else if self.is_quote() {
+            Ok(self.tokenize_string(diagnostics))
         } else if let Some(typ) = self.match_punctuation() {
             Ok(self.tokenize_punct(typ))
         } else {
@@ -111,7 +120,13 @@ impl<'src> Lexer<'src> {
     }
 
     fn skip_comment(&mut self, diagnostics: &mut Diagnostics) -> bool {
-        if self.is_comment_start() {
+        if self.is_block_comment_start() {
+            self.skip_block_comment(diagnostics);
+            true
+        } else if self.is_comment_start()
+            && !self.is_ascii_arrow()
+            && !self.is_doc_comment_start()
+        {
             self.clear_current();
             self.next_char();
             if self.is_comment_start() {
@@ -128,6 +143,35 @@ impl<'src> Lexer<'src> {
         }
     }
 
+    /// Pula um comentário `{- ... -}`, que pode conter outros comentários
+    /// desse tipo.
+    fn skip_block_comment(&mut self, diagnostics: &mut Diagnostics) {
+        // Localização de cada `{-` ainda não fechado.
+        let mut openings = Vec::new();
+
+        loop {
+            if self.is_block_comment_start() {
+                self.clear_current();
+                self.next_char();
+                self.next_char();
+                openings.push(self.token_span);
+            } else if self.is_block_comment_end() {
+                self.next_char();
+                self.next_char();
+                openings.pop();
+                if openings.is_empty() {
+                    break;
+                }
+            } else if self.source.peek().is_some() {
+                self.next_char();
+            } else {
+                let error = Error::new(UnterminatedComment, openings[0]);
+                diagnostics.raise(error);
+                break;
+            }
+        }
+    }
+
     fn tokenize_ident(&mut self) -> Token {
         let mut only_number = true;
         while self.is_identifier() {
@@ -144,6 +188,39 @@ impl<'src> Lexer<'src> {
         self.make_token(token_type)
     }
 
+    fn tokenize_string(&mut self, diagnostics: &mut Diagnostics) -> Token {
+        // Aspas de abertura.
+        self.next_char();
+        loop {
+            match self.source.peek() {
+                Some('"') => {
+                    self.next_char();
+                    break;
+                }
+                Some('\n') | None => {
+                    self.raise(diagnostics, UnterminatedString);
+                    break;
+                }
+                Some(_) => self.next_char(),
+            }
+        }
+        self.make_token(TokenType::String)
+    }
+
+    fn tokenize_ascii_arrow(&mut self) -> Token {
+        // `-` e `>`.
+        self.next_char();
+        self.next_char();
+        self.make_token(TokenType::Arrow)
+    }
+
+    fn tokenize_doc_comment(&mut self) -> Token {
+        while !self.is_comment_end() {
+            self.next_char();
+        }
+        self.make_token(TokenType::DocComment)
+    }
+
     fn tokenize_punct(&mut self, token_type: TokenType) -> Token {
         self.next_char();
         self.make_token(token_type)
@@ -178,14 +255,59 @@ impl<'src> Lexer<'src> {
         }
     }
 
-    fn is_identifier(&mut self) -> bool {
+    /// Testa se os próximos caracteres são `{-`.
+    fn is_block_comment_start(&self) -> bool {
+        self.starts_with("{-")
+    }
+
+    /// Testa se os próximos caracteres são `-}`.
+    fn is_block_comment_end(&self) -> bool {
+        self.starts_with("-}")
+    }
+
+    /// Testa se os próximos caracteres são `--|`.
+    fn is_doc_comment_start(&self) -> bool {
+        self.starts_with("--|")
+    }
+
+    /// Testa se os próximos caracteres do código são os de `prefix`, sem
+    /// consumi-los.
+    fn starts_with(&self, prefix: &str) -> bool {
+        let mut lookahead = self.source.clone();
+        prefix.chars().all(|character| lookahead.next() == Some(character))
+    }
+
+    fn is_quote(&mut self) -> bool {
+        self.source.peek() == Some(&'"')
+    }
+
+    /// Testa se o próximo caracter pode começar um identificador (ou um
+    /// número). `λ` é uma letra, mas fica reservado para lambdas.
+    fn is_identifier_start(&mut self) -> bool {
         match self.source.peek() {
             Some('_') => true,
-            Some(&character) => character.is_ascii_alphanumeric(),
+            Some('λ') => false,
+            Some(&character) => {
+                character.is_ascii_digit() || character.is_xid_start()
+            }
             None => false,
         }
     }
 
+    /// Testa se o próximo caracter pode continuar um identificador.
+    fn is_identifier(&mut self) -> bool {
+        match self.source.peek() {
+            Some('λ') => false,
+            Some(&character) => character.is_xid_continue(),
+            None => false,
+        }
+    }
+
+    /// Testa se os próximos caracteres são `->`.
+    fn is_ascii_arrow(&self) -> bool {
+        self.starts_with("->")
+    }
+
     fn is_number(&mut self) -> bool {
         match self.source.peek() {
             Some(&character) => character.is_ascii_digit(),
@@ -203,7 +325,9 @@ impl<'src> Lexer<'src> {
     fn match_keyword(&self) -> Option<TokenType> {
         match self.token_content.as_str() {
             "let" => Some(TokenType::Let),
+            "rec" => Some(TokenType::Rec),
             "in" => Some(TokenType::In),
+            "import" => Some(TokenType::Import),
             _ => None,
         }
     }
@@ -215,7 +339,8 @@ impl<'src> Lexer<'src> {
                 '.' => Some(TokenType::Dot),
                 '(' => Some(TokenType::OpenParen),
                 ')' => Some(TokenType::CloseParen),
-                '\\' => Some(TokenType::Lambda),
+                '\\' | 'λ' => Some(TokenType::Lambda),
+                '→' => Some(TokenType::Arrow),
                 ';' => Some(TokenType::Semicolon),
                 _ => None,
             },
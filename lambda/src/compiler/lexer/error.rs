@@ -22,4 +22,25 @@ impl fmt::Display for BadCommentStart {
     }
 }
 
-impl Error for BadCommentStart {}
\ No newline at end of file
+impl Error for BadCommentStart {}
+#[derive(Debug, Clone)]
+pub struct UnterminatedString;
+
+impl fmt::Display for UnterminatedString {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Texto aberto mas não fechado")
+    }
+}
+
+impl Error for UnterminatedString {}
+
+#[derive(Debug, Clone)]
+pub struct UnterminatedComment;
+
+impl fmt::Display for UnterminatedComment {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "Comentário aberto mas não fechado")
+    }
+}
+
+impl Error for UnterminatedComment {}
@@ -2305,3 +2305,420 @@ fn complete_code() {
         ]
     );
 }
+
+#[test]
+fn token_import() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("import", &mut diagnostics);
+    assert!(diagnostics.is_ok());
+    assert_eq!(
+        tokens,
+        &[Token {
+            token_type: TokenType::Import,
+            content: "import".to_owned(),
+            span: Span {
+                start: Position {
+                    line: 1,
+                    column: 1,
+                    utf8_index: 0,
+                    utf16_index: 0,
+                },
+                end: Position {
+                    line: 1,
+                    column: 7,
+                    utf8_index: 6,
+                    utf16_index: 6,
+                },
+            },
+        }]
+    );
+}
+
+#[test]
+fn token_rec() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("rec", &mut diagnostics);
+    assert!(diagnostics.is_ok());
+    assert_eq!(
+        tokens,
+        &[Token {
+            token_type: TokenType::Rec,
+            content: "rec".to_owned(),
+            span: Span {
+                start: Position {
+                    line: 1,
+                    column: 1,
+                    utf8_index: 0,
+                    utf16_index: 0,
+                },
+                end: Position {
+                    line: 1,
+                    column: 4,
+                    utf8_index: 3,
+                    utf16_index: 3,
+                },
+            },
+        }]
+    );
+}
+
+#[test]
+fn token_string() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("\"lib/bool.lam\"", &mut diagnostics);
+    assert!(diagnostics.is_ok());
+    assert_eq!(
+        tokens,
+        &[Token {
+            token_type: TokenType::String,
+            content: "\"lib/bool.lam\"".to_owned(),
+            span: Span {
+                start: Position {
+                    line: 1,
+                    column: 1,
+                    utf8_index: 0,
+                    utf16_index: 0,
+                },
+                end: Position {
+                    line: 1,
+                    column: 15,
+                    utf8_index: 14,
+                    utf16_index: 14,
+                },
+            },
+        }]
+    );
+}
+
+#[test]
+fn unterminated_string() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("import \"bool.lam\nx", &mut diagnostics);
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        &["Texto aberto mas não fechado, na linha 1 e coluna 8"]
+    );
+
+    let token_types =
+        tokens.iter().map(|token| token.token_type).collect::<Vec<_>>();
+
+    assert_eq!(
+        token_types,
+        &[TokenType::Import, TokenType::String, TokenType::Identifier]
+    );
+}
+
+#[test]
+fn token_unicode_lambda() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("λx", &mut diagnostics);
+    assert!(diagnostics.is_ok());
+    assert_eq!(
+        tokens,
+        &[
+            Token {
+                token_type: TokenType::Lambda,
+                content: "λ".to_owned(),
+                span: Span {
+                    start: Position {
+                        line: 1,
+                        column: 1,
+                        utf8_index: 0,
+                        utf16_index: 0,
+                    },
+                    end: Position {
+                        line: 1,
+                        column: 2,
+                        utf8_index: 2,
+                        utf16_index: 1,
+                    },
+                },
+            },
+            Token {
+                token_type: TokenType::Identifier,
+                content: "x".to_owned(),
+                span: Span {
+                    start: Position {
+                        line: 1,
+                        column: 2,
+                        utf8_index: 2,
+                        utf16_index: 1,
+                    },
+                    end: Position {
+                        line: 1,
+                        column: 3,
+                        utf8_index: 3,
+                        utf16_index: 2,
+                    },
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn token_arrows() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("-> →", &mut diagnostics);
+    assert!(diagnostics.is_ok());
+    assert_eq!(
+        tokens,
+        &[
+            Token {
+                token_type: TokenType::Arrow,
+                content: "->".to_owned(),
+                span: Span {
+                    start: Position {
+                        line: 1,
+                        column: 1,
+                        utf8_index: 0,
+                        utf16_index: 0,
+                    },
+                    end: Position {
+                        line: 1,
+                        column: 3,
+                        utf8_index: 2,
+                        utf16_index: 2,
+                    },
+                },
+            },
+            Token {
+                token_type: TokenType::Arrow,
+                content: "→".to_owned(),
+                span: Span {
+                    start: Position {
+                        line: 1,
+                        column: 4,
+                        utf8_index: 3,
+                        utf16_index: 3,
+                    },
+                    end: Position {
+                        line: 1,
+                        column: 5,
+                        utf8_index: 6,
+                        utf16_index: 4,
+                    },
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn arrow_after_comment() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("-- x -> y\n-> --> z", &mut diagnostics);
+    assert!(diagnostics.is_ok());
+
+    let token_types =
+        tokens.iter().map(|token| token.token_type).collect::<Vec<_>>();
+
+    assert_eq!(token_types, &[TokenType::Arrow]);
+}
+
+#[test]
+fn token_unicode_identifiers() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("ação αβ ωb", &mut diagnostics);
+    assert!(diagnostics.is_ok());
+    assert_eq!(
+        tokens,
+        &[
+            Token {
+                token_type: TokenType::Identifier,
+                content: "ação".to_owned(),
+                span: Span {
+                    start: Position {
+                        line: 1,
+                        column: 1,
+                        utf8_index: 0,
+                        utf16_index: 0,
+                    },
+                    end: Position {
+                        line: 1,
+                        column: 5,
+                        utf8_index: 6,
+                        utf16_index: 4,
+                    },
+                },
+            },
+            Token {
+                token_type: TokenType::Identifier,
+                content: "αβ".to_owned(),
+                span: Span {
+                    start: Position {
+                        line: 1,
+                        column: 6,
+                        utf8_index: 7,
+                        utf16_index: 5,
+                    },
+                    end: Position {
+                        line: 1,
+                        column: 8,
+                        utf8_index: 11,
+                        utf16_index: 7,
+                    },
+                },
+            },
+            Token {
+                token_type: TokenType::Identifier,
+                content: "ωb".to_owned(),
+                span: Span {
+                    start: Position {
+                        line: 1,
+                        column: 9,
+                        utf8_index: 12,
+                        utf16_index: 8,
+                    },
+                    end: Position {
+                        line: 1,
+                        column: 11,
+                        utf8_index: 15,
+                        utf16_index: 10,
+                    },
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn lambda_is_not_identifier_char() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("fλx", &mut diagnostics);
+    assert!(diagnostics.is_ok());
+
+    let token_types =
+        tokens.iter().map(|token| token.token_type).collect::<Vec<_>>();
+
+    assert_eq!(
+        token_types,
+        &[TokenType::Identifier, TokenType::Lambda, TokenType::Identifier]
+    );
+}
+
+#[test]
+fn invalid_unicode_char() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("a ∘ b", &mut diagnostics);
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(errors, &["Caracter '∘' é inválido, na linha 1 e coluna 3"]);
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[1].span.start.utf8_index, 6);
+    assert_eq!(tokens[1].span.start.column, 5);
+}
+
+#[test]
+fn block_comments() {
+    let mut diagnostics = Diagnostics::new();
+    let code = "let {- um\ncomentário {- aninhado -} -} x {--} = 1 in x";
+    let tokens = generate_tokens(code, &mut diagnostics);
+    assert!(diagnostics.is_ok());
+
+    let contents =
+        tokens.iter().map(|token| token.content.as_str()).collect::<Vec<_>>();
+
+    assert_eq!(contents, &["let", "x", "=", "1", "in", "x"]);
+    assert_eq!(tokens[1].span.start.line, 2);
+}
+
+#[test]
+fn unterminated_block_comment() {
+    let mut diagnostics = Diagnostics::new();
+    let code = "x {- a {- b -}\ny";
+    let tokens = generate_tokens(code, &mut diagnostics);
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        &["Comentário aberto mas não fechado, na linha 1 e coluna 3"]
+    );
+
+    let error = diagnostics.iter().next().unwrap();
+    assert_eq!(
+        error.span(),
+        Some(Span {
+            start: Position {
+                line: 1,
+                column: 3,
+                utf8_index: 2,
+                utf16_index: 2,
+            },
+            end: Position { line: 1, column: 5, utf8_index: 4, utf16_index: 4 },
+        })
+    );
+
+    let token_types =
+        tokens.iter().map(|token| token.token_type).collect::<Vec<_>>();
+
+    assert_eq!(token_types, &[TokenType::Identifier]);
+}
+
+#[test]
+fn token_doc_comment() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("--| identidade\nid", &mut diagnostics);
+    assert!(diagnostics.is_ok());
+    assert_eq!(
+        tokens,
+        &[
+            Token {
+                token_type: TokenType::DocComment,
+                content: "--| identidade".to_owned(),
+                span: Span {
+                    start: Position {
+                        line: 1,
+                        column: 1,
+                        utf8_index: 0,
+                        utf16_index: 0,
+                    },
+                    end: Position {
+                        line: 1,
+                        column: 15,
+                        utf8_index: 14,
+                        utf16_index: 14,
+                    },
+                },
+            },
+            Token {
+                token_type: TokenType::Identifier,
+                content: "id".to_owned(),
+                span: Span {
+                    start: Position {
+                        line: 2,
+                        column: 1,
+                        utf8_index: 15,
+                        utf16_index: 15,
+                    },
+                    end: Position {
+                        line: 2,
+                        column: 3,
+                        utf8_index: 17,
+                        utf16_index: 17,
+                    },
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn doc_comment_marker_inside_comment() {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens("-- --| não é doc\nx", &mut diagnostics);
+    assert!(diagnostics.is_ok());
+
+    let token_types =
+        tokens.iter().map(|token| token.token_type).collect::<Vec<_>>();
+
+    assert_eq!(token_types, &[TokenType::Identifier]);
+}
@@ -9,11 +9,16 @@ pub enum TokenType {
     Number,
     Equal,
     Let,
+    Rec,
     In,
+    Import,
+    String,
     Dot,
+    Arrow,
     OpenParen,
     CloseParen,
     Semicolon,
+    DocComment,
 }
 
 impl fmt::Display for TokenType {
@@ -26,11 +31,16 @@ impl fmt::Display for TokenType {
             TokenType::Number => write!(formatter, "<número>"),
             TokenType::Equal => write!(formatter, "="),
             TokenType::Let => write!(formatter, "let"),
+            TokenType::Rec => write!(formatter, "rec"),
             TokenType::In => write!(formatter, "in"),
+            TokenType::Import => write!(formatter, "import"),
+            TokenType::String => write!(formatter, "<texto>"),
             TokenType::Dot => write!(formatter, "."),
+            TokenType::Arrow => write!(formatter, "->"),
             TokenType::OpenParen => write!(formatter, "("),
             TokenType::CloseParen => write!(formatter, ")"),
             TokenType::Semicolon => write!(formatter, ";"),
+            TokenType::DocComment => write!(formatter, "<documentação>"),
         }
     }
 }
@@ -5,12 +5,13 @@ pub mod ast;
 pub mod error;
 
 use crate::compiler::lexer::token::{Token, TokenType};
-use ast::{Binding, Expr, Program, Symbol};
+use ast::{Binding, Expr, Import, Library, Program, Symbol};
 use error::{
     EmptyExpression, LambdaWithoutParams, UnexpectedEndOfInput,
     UnexpectedToken, UnmatchedCloseParen, UnmatchedOpenParen,
 };
 use pinguim_language::error::{Diagnostics, Error};
+use std::collections::HashMap;
 
 /// Cria uma estrutura Parser e parsa a lista de tokens para um programa
 ///
@@ -23,6 +24,41 @@ pub fn parse(
     Parser::new(tokens).parse_program(diagnostics).ok().flatten()
 }
 
+/// Parsa a lista de tokens de um arquivo importado: importações seguidas de
+/// definições `nome = expr;`, sem `let`, `in` ou expressão principal. Um `rec`
+/// antes das definições as torna recursivas, como em `let rec`.
+///
+/// - `tokens`: vetor de tokens
+/// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+pub fn parse_library(
+    tokens: Vec<Token>,
+    diagnostics: &mut Diagnostics,
+) -> Option<Library> {
+    Parser::new(tokens).parse_library(diagnostics).ok()
+}
+
+/// Parsa uma lista de tokens formada apenas por definições `nome = expr;`,
+/// sem `let`, `in` ou expressão principal (usado, por exemplo, no prelúdio).
+/// O `;` após a última definição é opcional.
+///
+/// - `tokens`: vetor de tokens
+/// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+pub fn parse_bindings(
+    tokens: Vec<Token>,
+    diagnostics: &mut Diagnostics,
+) -> Option<Vec<Binding>> {
+    Parser::new(tokens).parse_binding_list(diagnostics).ok()
+}
+
+/// Texto de um comentário `--|`, sem o `--|` e sem o espaço que o segue.
+///
+/// - `content`: conteúdo do token do comentário
+fn doc_comment_text(content: &str) -> &str {
+    let text = content.strip_prefix("--|").unwrap_or(content);
+    let text = text.strip_prefix(' ').unwrap_or(text);
+    text.trim_end()
+}
+
 // main e binding são os dois únicos contextos externos da gramática onde uma
 // expressão aparece.
 //
@@ -92,14 +128,43 @@ struct Parser {
     ///
     /// - `curr_token`: índice do token que está sendo parsado
     curr_token: usize,
+    ///
+    /// - `docs`: texto dos comentários `--|` indexado pelo índice do token
+    ///   que vem logo depois deles
+    docs: HashMap<usize, String>,
 }
 
 impl Parser {
-    /// Cria uma nova estrutura de Parser
+    /// Cria uma nova estrutura de Parser.
+    ///
+    /// Comentários `--|` são separados dos demais tokens e guardados para
+    /// serem ligados à definição seguinte.
     ///
     /// - `tokens`: vetor de tokens
     fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, curr_token: 0 }
+        let mut code_tokens = Vec::with_capacity(tokens.len());
+        let mut docs = HashMap::new();
+        let mut doc: Option<String> = None;
+
+        for token in tokens {
+            if token.token_type == TokenType::DocComment {
+                let line = doc_comment_text(&token.content);
+                match &mut doc {
+                    Some(doc) => {
+                        doc.push('\n');
+                        doc.push_str(line);
+                    }
+                    None => doc = Some(line.to_owned()),
+                }
+            } else {
+                if let Some(doc) = doc.take() {
+                    docs.insert(code_tokens.len(), doc);
+                }
+                code_tokens.push(token);
+            }
+        }
+
+        Self { tokens: code_tokens, curr_token: 0, docs }
     }
     /// Pega o token o qual está sendo parsado no momento dado seu índice `curr_token`
     fn current(&self) -> Option<&Token> {
@@ -176,6 +241,34 @@ impl Parser {
         }
     }
 
+    /// Recupera o parser após um erro, pulando tokens sem gerar novos erros
+    /// até um ponto de sincronização: um `;`, um `in`, o `)` que fecha a
+    /// expressão atual, um dos tipos em `targets` ou o fim do código. O
+    /// token de sincronização não é consumido.
+    ///
+    /// Parênteses abertos durante a recuperação são pulados junto com seu
+    /// conteúdo, então `)` e `targets` só sincronizam fora deles.
+    ///
+    /// - `expr_end`: fim da expressão onde o erro foi encontrado
+    /// - `targets`: tipos de token que também sincronizam
+    fn synchronize(&mut self, expr_end: ExprEnd, targets: &[TokenType]) {
+        let mut depth = 0usize;
+
+        while let Some(token) = self.current() {
+            match token.token_type {
+                TokenType::Semicolon | TokenType::In => break,
+                TokenType::OpenParen => depth += 1,
+                TokenType::CloseParen if depth > 0 => depth -= 1,
+                TokenType::CloseParen if expr_end.parenthesized => break,
+                token_type if depth == 0 && targets.contains(&token_type) => {
+                    break
+                }
+                _ => (),
+            }
+            self.next();
+        }
+    }
+
     /// Faz o parse do vetor de tokens em um programa
     ///
     /// - `diagnostics`: vetor que armazena erros coletados durante a compilação
@@ -183,26 +276,106 @@ impl Parser {
         &mut self,
         diagnostics: &mut Diagnostics,
     ) -> Result<Option<Program>, Abort> {
+        let imports = self.parse_imports(diagnostics)?;
         let token = self.require_current(diagnostics)?;
 
-        let bindings = if token.token_type == TokenType::Let {
+        let (recursive, bindings) = if token.token_type == TokenType::Let {
             self.parse_let(diagnostics)?
         } else {
-            Vec::new()
+            (false, Vec::new())
         };
 
         let expr_end =
             ExprEnd { end_type: ExprEndType::Main, parenthesized: false };
         let main_expr_opt = self.parse_expression(diagnostics, expr_end)?;
-        Ok(main_expr_opt
-            .map(|main_expression| Program { main_expression, bindings }))
+        Ok(main_expr_opt.map(|main_expression| Program {
+            imports,
+            recursive,
+            main_expression,
+            bindings,
+        }))
+    }
+
+    /// Faz o parse do vetor de tokens em um arquivo importado
+    ///
+    /// - `diagnostics`: vetor que armazena erros coletados durante a compilação
+    fn parse_library(
+        &mut self,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Library, Abort> {
+        let imports = self.parse_imports(diagnostics)?;
+        let recursive = self.check_rec();
+        let bindings = self.parse_binding_list(diagnostics)?;
+        Ok(Library { imports, recursive, bindings })
+    }
+
+    // import "caminho.lam";
+    fn parse_imports(
+        &mut self,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Vec<Import>, Abort> {
+        let mut imports = Vec::new();
+
+        while let Some(token) = self.current() {
+            if token.token_type != TokenType::Import {
+                break;
+            }
+            self.next();
+
+            if let Some(import) = self.parse_import_path(diagnostics)? {
+                imports.push(import);
+            }
+
+            self.expect(TokenType::Semicolon, diagnostics)?;
+        }
+
+        Ok(imports)
     }
 
+    fn parse_import_path(
+        &mut self,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Option<Import>, Abort> {
+        let token = self.require_current(diagnostics)?;
+
+        if token.token_type == TokenType::String {
+            let content = token.content.as_str();
+            let path = content.strip_prefix('"').unwrap_or(content);
+            let path = path.strip_suffix('"').unwrap_or(path);
+            let import = Import { path: path.to_owned(), span: token.span };
+            self.next();
+            Ok(Some(import))
+        } else {
+            let expected_types = vec![TokenType::String];
+            diagnostics.raise(Error::new(
+                UnexpectedToken { expected_types },
+                token.span,
+            ));
+            // Pula o caminho inválido para que o `;` seguinte seja aceito.
+            self.next();
+
+            Ok(None)
+        }
+    }
+
+    /// Consome um `rec`, se houver. Retorna se havia um `rec`.
+    fn check_rec(&mut self) -> bool {
+        let is_rec = self
+            .current()
+            .is_some_and(|token| token.token_type == TokenType::Rec);
+        if is_rec {
+            self.next();
+        }
+        is_rec
+    }
+
+    // let [rec] ident = expr; ... in
     fn parse_let(
         &mut self,
         diagnostics: &mut Diagnostics,
-    ) -> Result<Vec<Binding>, Abort> {
+    ) -> Result<(bool, Vec<Binding>), Abort> {
         self.expect(TokenType::Let, diagnostics)?; // facilita isolamento dessa função (expect() X next())
+        let recursive = self.check_rec();
         let mut bindings = Vec::new();
 
         while !self.check_expect(TokenType::In, diagnostics)? {
@@ -213,6 +386,52 @@ impl Parser {
             self.check_expect(TokenType::Semicolon, diagnostics)?;
         }
 
+        Ok((recursive, bindings))
+    }
+
+    // let [rec] ident = expr; ... in expr
+    fn parse_let_expression(
+        &mut self,
+        diagnostics: &mut Diagnostics,
+        expr_end: ExprEnd,
+    ) -> Result<Option<Expr>, Abort> {
+        let (recursive, bindings) = self.parse_let(diagnostics)?;
+
+        // corpo do `let`
+        let let_expr =
+            self.parse_expression(diagnostics, expr_end)?.map(|body| {
+                Expr::Let { recursive, bindings, body: Box::new(body) }
+            });
+
+        Ok(let_expr)
+    }
+
+    fn parse_binding_list(
+        &mut self,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Vec<Binding>, Abort> {
+        let mut bindings = Vec::new();
+
+        while self.current().is_some() {
+            if let Some(binding) = self.parse_binding(diagnostics)? {
+                bindings.push(binding);
+            }
+
+            // A expressão da definição termina em `;`, `in` ou no fim da
+            // entrada; o token final é sempre consumido para garantir
+            // progresso.
+            if let Some(token) = self.current() {
+                if token.token_type != TokenType::Semicolon {
+                    let expected_types = vec![TokenType::Semicolon];
+                    diagnostics.raise(Error::new(
+                        UnexpectedToken { expected_types },
+                        token.span,
+                    ));
+                }
+                self.next();
+            }
+        }
+
         Ok(bindings)
     }
 
@@ -221,16 +440,25 @@ impl Parser {
         &mut self,
         diagnostics: &mut Diagnostics,
     ) -> Result<Option<Binding>, Abort> {
+        let doc = self.docs.remove(&self.curr_token);
+        let expr_end =
+            ExprEnd { end_type: ExprEndType::Binding, parenthesized: false };
+
         let name_opt = self.parse_binding_name(diagnostics)?;
+        if name_opt.is_none() {
+            // Sem nome, o resto da definição só geraria erros em cascata.
+            self.synchronize(expr_end, &[]);
+            return Ok(None);
+        }
 
         self.expect(TokenType::Equal, diagnostics)?;
-        let expr_end =
-            ExprEnd { end_type: ExprEndType::Binding, parenthesized: false };
         let expression_opt = self.parse_expression(diagnostics, expr_end)?;
 
-        Ok(name_opt
-            .zip(expression_opt)
-            .map(|(name, expression)| Binding { name, expression }))
+        Ok(name_opt.zip(expression_opt).map(|(name, expression)| Binding {
+            name,
+            expression,
+            doc,
+        }))
     }
 
     fn parse_binding_name(
@@ -301,6 +529,16 @@ impl Parser {
                     // o body do Lambda.
                 }
 
+                TokenType::Let => {
+                    if let Some(let_expr) =
+                        self.parse_let_expression(diagnostics, expr_end)?
+                    {
+                        self.stack_exprs(&mut curr_expr, let_expr);
+                    }
+                    // Assim como no Lambda, o corpo do `let` vai até o final
+                    // da expressão.
+                }
+
                 TokenType::OpenParen => {
                     let span = token.span;
                     self.next();
@@ -319,9 +557,11 @@ impl Parser {
                             {
                                 self.next();
                             }
-                            _ => {
+                            token => {
+                                let expected_close =
+                                    token.map(|token| token.span);
                                 diagnostics.raise(Error::new(
-                                    UnmatchedOpenParen,
+                                    UnmatchedOpenParen { expected_close },
                                     span,
                                 ));
                             }
@@ -334,18 +574,35 @@ impl Parser {
                         .raise(Error::new(UnmatchedCloseParen, token.span));
                     self.next();
                 }
-                _ => {
+                token_type => {
                     let expected_types = vec![
                         TokenType::Number,
                         TokenType::Identifier,
                         TokenType::Lambda,
                         TokenType::OpenParen,
+                        TokenType::Let,
                     ];
                     diagnostics.raise(Error::new(
                         UnexpectedToken { expected_types },
                         token.span,
                     ));
                     self.next();
+
+                    // Um `;` ou `in` inesperado já é um ponto de
+                    // sincronização; senão, pula o resto do trecho inválido.
+                    // Fora das definições, o `;` ou `in` encontrado não
+                    // termina a expressão e é consumido.
+                    let is_sync_point = token_type == TokenType::Semicolon
+                        || token_type == TokenType::In;
+                    if !is_sync_point {
+                        self.synchronize(expr_end, &[]);
+                        match self.current() {
+                            Some(token) if !expr_end.test(token.token_type) => {
+                                self.next();
+                            }
+                            _ => (),
+                        }
+                    }
                 }
             }
         }
@@ -390,17 +647,32 @@ impl Parser {
         self.next();
 
         let mut params = Vec::new();
+        let mut invalid_param = false;
 
-        // até o ponto são os parâmetros da expressão lambda
-        while !self.check_expect(TokenType::Dot, diagnostics)? {
+        // até o ponto (ou a seta) são os parâmetros da expressão lambda
+        while !self.check_expect(TokenType::Dot, diagnostics)?
+            && !self.check_expect(TokenType::Arrow, diagnostics)?
+        {
             if let Some(param) = self.parse_param(diagnostics)? {
                 params.push(param);
-            } else {
-                self.next();
+                continue;
+            }
+
+            // Parâmetro inválido: pula até o ponto (ou a seta), sem outros
+            // erros. Se a expressão acabar antes, o lambda não tem corpo.
+            invalid_param = true;
+            let separators = [TokenType::Dot, TokenType::Arrow];
+            self.synchronize(expr_end, &separators);
+            match self.current() {
+                Some(token) if separators.contains(&token.token_type) => {
+                    self.next();
+                    break;
+                }
+                _ => return Ok(None),
             }
         }
 
-        if params.is_empty() {
+        if params.is_empty() && !invalid_param {
             diagnostics.raise(Error::new(LambdaWithoutParams, lambda_span));
         }
 
@@ -431,7 +703,8 @@ impl Parser {
             self.next();
             Ok(Some(symbol))
         } else {
-            let expected_types = vec![TokenType::Identifier];
+            let expected_types =
+                vec![TokenType::Identifier, TokenType::Dot, TokenType::Arrow];
             diagnostics.raise(Error::new(
                 UnexpectedToken { expected_types },
                 token.span,
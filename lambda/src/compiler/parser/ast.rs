@@ -1,9 +1,19 @@
+#[cfg(test)]
+mod test;
+
 use pinguim_language::position::Span;
 
-// <program> ::=
+// <program> ::= <imports> <main>
+//
+// <main> ::=
 //      | <let> <bindings> <in> <expr>
 //      | <expr>
 //
+// <library> ::= <imports> <bindings>
+//
+// // repete
+// <imports> ::= import <string> ;
+//
 // // repete
 // <bindings> ::= <var> = <expr> ;
 //
@@ -11,6 +21,7 @@ use pinguim_language::position::Span;
 //      | <var>
 //      | <expr> <expr>
 //      | \<var>. <expr>
+//      | <let> <bindings> <in> <expr>
 //      | (<expr>)
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -19,18 +30,152 @@ pub enum Expr {
     Number(u64),
     Application { function: Box<Expr>, argument: Box<Expr> },
     Lambda { parameter: Symbol, body: Box<Expr> },
+    Let { recursive: bool, bindings: Vec<Binding>, body: Box<Expr> },
+}
+
+impl Expr {
+    /// Lista os usos de variáveis da expressão, em ordem, cada um junto do
+    /// nome que o liga dentro da expressão: o parâmetro de um lambda ou uma
+    /// definição de um `let`. Usos de variáveis livres vêm com `None`.
+    ///
+    /// Em um `let rec`, todas as definições estão no escopo de todas as
+    /// expressões; senão, cada definição só está no escopo das expressões
+    /// seguintes e do corpo.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// variableUses :: Expr -> [(Symbol, Maybe Symbol)]
+    /// variableUses expr = uses expr []
+    ///   where
+    ///     uses (Variable s) scope =
+    ///       [(s, find (\p -> content p == content s) scope)]
+    ///     uses (Number _) _ = []
+    ///     uses (Application f a) scope = uses f scope ++ uses a scope
+    ///     uses (Lambda p b) scope = uses b (p : scope)
+    ///     uses (Let rec bs b) scope =
+    ///       usesLet rec bs b (if rec then reverse (map name bs) ++ scope
+    ///                         else scope)
+    ///     usesLet rec [] b scope = uses b scope
+    ///     usesLet rec (Binding n e : bs) b scope =
+    ///       uses e scope
+    ///         ++ usesLet rec bs b (if rec then scope else n : scope)
+    /// ```
+    pub fn variable_uses(&self) -> Vec<(&Symbol, Option<&Symbol>)> {
+        /// Uma operação da visita às expressões.
+        enum Operation<'expr> {
+            /// Visita uma expressão.
+            Visit(&'expr Expr),
+            /// Coloca o nome de uma definição de um `let` no escopo.
+            Bind(&'expr Symbol),
+            /// Sai do corpo de um lambda ou de um `let`, removendo um nome do
+            /// escopo.
+            Leave,
+        }
+
+        let mut uses = Vec::new();
+        // Nomes em escopo, do mais externo para o mais interno.
+        let mut scope: Vec<&Symbol> = Vec::new();
+        let mut operation_stack = vec![Operation::Visit(self)];
+
+        while let Some(operation) = operation_stack.pop() {
+            match operation {
+                Operation::Bind(name) => scope.push(name),
+
+                Operation::Leave => {
+                    scope.pop();
+                }
+
+                Operation::Visit(Expr::Variable(symbol)) => {
+                    let binder = scope
+                        .iter()
+                        .rev()
+                        .find(|name| name.content == symbol.content)
+                        .copied();
+                    uses.push((symbol, binder));
+                }
+
+                Operation::Visit(Expr::Number(_)) => (),
+
+                Operation::Visit(Expr::Application { function, argument }) => {
+                    operation_stack.push(Operation::Visit(argument));
+                    operation_stack.push(Operation::Visit(function));
+                }
+
+                Operation::Visit(Expr::Lambda { parameter, body }) => {
+                    scope.push(parameter);
+                    operation_stack.push(Operation::Leave);
+                    operation_stack.push(Operation::Visit(body));
+                }
+
+                Operation::Visit(Expr::Let { recursive, bindings, body }) => {
+                    // Operações empilhadas ao contrário: no `let rec` todos
+                    // os nomes entram no escopo antes das expressões; senão,
+                    // cada nome entra logo depois da sua expressão.
+                    for _ in bindings {
+                        operation_stack.push(Operation::Leave);
+                    }
+                    operation_stack.push(Operation::Visit(body));
+                    for binding in bindings.iter().rev() {
+                        if !recursive {
+                            operation_stack
+                                .push(Operation::Bind(&binding.name));
+                        }
+                        operation_stack
+                            .push(Operation::Visit(&binding.expression));
+                    }
+                    if *recursive {
+                        scope.extend(
+                            bindings.iter().map(|binding| &binding.name),
+                        );
+                    }
+                }
+            }
+        }
+
+        uses
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Program {
+    pub imports: Vec<Import>,
+    ///
+    /// - `recursive`: se as definições foram declaradas com `let rec`
+    pub recursive: bool,
     pub main_expression: Expr,
     pub bindings: Vec<Binding>,
 }
 
+/// Um arquivo importado por outros: só tem importações e definições, sem
+/// expressão principal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Library {
+    pub imports: Vec<Import>,
+    ///
+    /// - `recursive`: se as definições foram declaradas após um `rec`
+    pub recursive: bool,
+    pub bindings: Vec<Binding>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Import {
+    ///
+    /// - `path`: caminho do arquivo importado, sem as aspas
+    pub path: String,
+    ///
+    /// - `span`: localização do caminho no código
+    pub span: Span,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Binding {
     pub name: Symbol,
     pub expression: Expr,
+    ///
+    /// - `doc`: texto dos comentários `--|` logo antes da definição, sem o
+    ///   `--|`, com uma linha por comentário
+    pub doc: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -0,0 +1,67 @@
+use crate::compiler::lexer::generate_tokens;
+use crate::compiler::parser::parse;
+use pinguim_language::error::Diagnostics;
+
+/// Usos de variáveis da expressão principal do código, como o nome usado, o
+/// índice do uso e o índice do nome que o liga.
+fn variable_uses(source_code: &str) -> Vec<(String, usize, Option<usize>)> {
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let program = parse(tokens, &mut diagnostics).unwrap();
+    assert!(diagnostics.is_ok());
+
+    program
+        .main_expression
+        .variable_uses()
+        .into_iter()
+        .map(|(symbol, binder)| {
+            (
+                symbol.content.clone(),
+                symbol.span.start.utf8_index,
+                binder.map(|binder| binder.span.start.utf8_index),
+            )
+        })
+        .collect()
+}
+
+fn bound(
+    name: &str,
+    index: usize,
+    binder: usize,
+) -> (String, usize, Option<usize>) {
+    (String::from(name), index, Some(binder))
+}
+
+fn free(name: &str, index: usize) -> (String, usize, Option<usize>) {
+    (String::from(name), index, None)
+}
+
+#[test]
+fn lambda_parameters() {
+    assert_eq!(variable_uses("\\x. x y"), vec![bound("x", 4, 1), free("y", 6)]);
+}
+
+#[test]
+fn let_bindings_in_order() {
+    assert_eq!(
+        variable_uses("\\x. let a = x; b = a; in b"),
+        vec![bound("x", 12, 1), bound("a", 19, 8), bound("b", 25, 15)]
+    );
+    assert_eq!(
+        variable_uses("\\a. let a = a; in a"),
+        vec![bound("a", 12, 1), bound("a", 18, 8)]
+    );
+}
+
+#[test]
+fn let_rec_bindings_in_scope() {
+    assert_eq!(
+        variable_uses("\\x. let rec f = g; g = f; in f x"),
+        vec![
+            bound("g", 16, 19),
+            bound("f", 23, 12),
+            bound("f", 29, 12),
+            bound("x", 31, 1),
+        ]
+    );
+}
@@ -1,4 +1,5 @@
 use crate::compiler::lexer::token::TokenType;
+use pinguim_language::position::Span;
 use std::{error::Error, fmt};
 
 #[derive(Debug, Clone)]
@@ -53,7 +54,10 @@ impl fmt::Display for EmptyLetBlockDeclared {
 impl Error for EmptyLetBlockDeclared {}
 
 #[derive(Debug, Clone)]
-pub struct UnmatchedOpenParen;
+pub struct UnmatchedOpenParen {
+    /// Onde o `)` era esperado, ou `None` se o código terminou antes.
+    pub expected_close: Option<Span>,
+}
 
 impl fmt::Display for UnmatchedOpenParen {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
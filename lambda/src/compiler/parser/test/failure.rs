@@ -41,6 +41,8 @@ fn lambda_without_params() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Application {
                 function: Box::new(ast::Expr::Variable(ast::Symbol {
                     content: String::from("f"),
@@ -135,6 +137,8 @@ fn lambda_without_body_until_semicolon() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Variable(ast::Symbol {
                 content: String::from("bar"),
                 span: Span {
@@ -174,6 +178,8 @@ fn lambda_without_body_until_in_keyword() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Variable(ast::Symbol {
                 content: String::from("bar"),
                 span: Span {
@@ -212,6 +218,8 @@ fn binding_missing_equal_symbol() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Variable(ast::Symbol {
                 content: String::from("foo"),
                 span: Span {
@@ -301,7 +309,8 @@ fn binding_missing_equal_symbol() {
                             }
                         }))
                     })
-                }
+                },
+                doc: None,
             }]
         })
     );
@@ -327,6 +336,8 @@ fn not_closed_open_paren_until_eof() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Lambda {
                 parameter: ast::Symbol {
                     content: String::from("f"),
@@ -470,6 +481,8 @@ fn not_closed_open_paren_until_semicolon() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Variable(ast::Symbol {
                 content: String::from("three"),
                 span: Span {
@@ -625,6 +638,7 @@ fn not_closed_open_paren_until_semicolon() {
                         }),
                     }),
                 },
+                doc: None,
             }],
         })
     )
@@ -650,6 +664,8 @@ fn not_closed_open_paren_until_in_keyword() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Variable(ast::Symbol {
                 content: String::from("three"),
                 span: Span {
@@ -805,6 +821,7 @@ fn not_closed_open_paren_until_in_keyword() {
                         }),
                     }),
                 },
+                doc: None,
             }],
         })
     );
@@ -830,6 +847,8 @@ fn not_opened_close_paren() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Application {
                 function: Box::new(ast::Expr::Lambda {
                     parameter: ast::Symbol {
@@ -961,113 +980,77 @@ fn missing_let_keyword_at_the_beginning() {
     let errors =
         diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
 
+    // O trecho inválido é pulado até o `in`, sem gerar outros erros.
     assert_eq!(
         errors, 
         &[
-            "Token inesperado encontrado, esperava-se um \"<número>\", \"<identificador>\", \"\\\" ou \"(\", na linha 1 e coluna 8",
-            "Token inesperado encontrado, esperava-se um \"<número>\", \"<identificador>\", \"\\\" ou \"(\", da linha 2 e coluna 1, até a coluna 2"
+            "Token inesperado encontrado, esperava-se um \"<número>\", \"<identificador>\", \"\\\", \"(\" ou \"let\", na linha 1 e coluna 8",
         ]
     );
 
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Application {
-                function: Box::new(ast::Expr::Variable(ast::Symbol {
-                    content: String::from("id"),
-                    span: Span {
-                        start: Position {
-                            line: 1,
-                            column: 5,
-                            utf8_index: 4,
-                            utf16_index: 4,
-                        },
-                        end: Position {
-                            line: 1,
-                            column: 7,
-                            utf8_index: 6,
-                            utf16_index: 6,
-                        }
-                    }
-                })),
-                argument: Box::new(ast::Expr::Lambda {
-                    parameter: ast::Symbol {
-                        content: String::from("x"),
+                function: Box::new(ast::Expr::Application {
+                    function: Box::new(ast::Expr::Variable(ast::Symbol {
+                        content: String::from("id"),
                         span: Span {
                             start: Position {
                                 line: 1,
-                                column: 11,
-                                utf8_index: 10,
-                                utf16_index: 10,
+                                column: 5,
+                                utf8_index: 4,
+                                utf16_index: 4,
                             },
                             end: Position {
                                 line: 1,
-                                column: 12,
-                                utf8_index: 11,
-                                utf16_index: 11,
+                                column: 7,
+                                utf8_index: 6,
+                                utf16_index: 6,
                             }
                         }
-                    },
-                    body: Box::new(ast::Expr::Application {
-                        function: Box::new(ast::Expr::Application {
-                            function: Box::new(ast::Expr::Variable(ast::Symbol {
-                                content: String::from("x"),
-                                span: Span {
-                                    start: Position {
-                                        line: 1,
-                                        column: 14,
-                                        utf8_index: 13,
-                                        utf16_index: 13,
-                                    },
-                                    end: Position {
-                                        line: 1,
-                                        column: 15,
-                                        utf8_index: 14,
-                                        utf16_index: 14,
-                                    }
-                                }
-                            })),
-                            argument: Box::new(ast::Expr::Variable(ast::Symbol {
-                                content: String::from("id"),
-                                span: Span {
-                                    start: Position {
-                                        line: 3,
-                                        column: 4,
-                                        utf8_index: 21,
-                                        utf16_index: 21,
-                                    },
-                                    end: Position {
-                                        line: 3,
-                                        column: 6,
-                                        utf8_index: 23,
-                                        utf16_index: 23,
-                                    }
-                                }
-                            }))
-                        }),
-                        argument: Box::new(ast::Expr::Variable(ast::Symbol {
-                            content: String::from("id"),
-                            span: Span {
-                                start: Position {
-                                    line: 3,
-                                    column: 7,
-                                    utf8_index: 24,
-                                    utf16_index: 24,
-                                },
-                                end: Position {
-                                    line: 3,
-                                    column: 9,
-                                    utf8_index: 26,
-                                    utf16_index: 26,
-                                }
+                    })),
+                    argument: Box::new(ast::Expr::Variable(ast::Symbol {
+                        content: String::from("id"),
+                        span: Span {
+                            start: Position {
+                                line: 3,
+                                column: 4,
+                                utf8_index: 21,
+                                utf16_index: 21,
+                            },
+                            end: Position {
+                                line: 3,
+                                column: 6,
+                                utf8_index: 23,
+                                utf16_index: 23,
                             }
-                        }))
-                    })
+                        }
+                    }))
                 }),
+                argument: Box::new(ast::Expr::Variable(ast::Symbol {
+                    content: String::from("id"),
+                    span: Span {
+                        start: Position {
+                            line: 3,
+                            column: 7,
+                            utf8_index: 24,
+                            utf16_index: 24,
+                        },
+                        end: Position {
+                            line: 3,
+                            column: 9,
+                            utf8_index: 26,
+                            utf16_index: 26,
+                        }
+                    }
+                }))
             },
             bindings: Vec::new()
         })
-    )
+    );
 }
 
 #[test]
@@ -1082,17 +1065,19 @@ fn applying_application_to_lambda_params() {
     let errors =
         diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
 
+    // Os parâmetros inválidos são pulados até o ponto.
     assert_eq!(
         errors, 
         &[
-            "Token inesperado encontrado, esperava-se um \"<identificador>\", na linha 1 e coluna 4",
-            "Token inesperado encontrado, esperava-se um \"<identificador>\", na linha 1 e coluna 8"
+            "Token inesperado encontrado, esperava-se um \"<identificador>\", \".\" ou \"->\", na linha 1 e coluna 4",
         ]
     );
 
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Lambda {
                 parameter: ast::Symbol {
                     content: String::from("x"),
@@ -1111,65 +1096,27 @@ fn applying_application_to_lambda_params() {
                         }
                     }
                 },
-                body: Box::new(ast::Expr::Lambda {
-                    parameter: ast::Symbol {
-                        content: String::from("y"),
-                        span: Span {
-                            start: Position {
-                                line: 1,
-                                column: 5,
-                                utf8_index: 4,
-                                utf16_index: 4,
-                            },
-                            end: Position {
-                                line: 1,
-                                column: 6,
-                                utf8_index: 5,
-                                utf16_index: 5,
-                            }
-                        }
-                    },
-                    body: Box::new(ast::Expr::Lambda {
-                        parameter: ast::Symbol {
-                            content: String::from("z"),
-                            span: Span {
-                                start: Position {
-                                    line: 1,
-                                    column: 7,
-                                    utf8_index: 6,
-                                    utf16_index: 6,
-                                },
-                                end: Position {
-                                    line: 1,
-                                    column: 8,
-                                    utf8_index: 7,
-                                    utf16_index: 7,
-                                }
-                            }
+                body: Box::new(ast::Expr::Variable(ast::Symbol {
+                    content: String::from("w"),
+                    span: Span {
+                        start: Position {
+                            line: 1,
+                            column: 11,
+                            utf8_index: 10,
+                            utf16_index: 10,
                         },
-                        body: Box::new(ast::Expr::Variable(ast::Symbol {
-                            content: String::from("w"),
-                            span: Span {
-                                start: Position {
-                                    line: 1,
-                                    column: 11,
-                                    utf8_index: 10,
-                                    utf16_index: 10,
-                                },
-                                end: Position {
-                                    line: 1,
-                                    column: 12,
-                                    utf8_index: 11,
-                                    utf16_index: 11,
-                                }
-                            }
-                        }))
-                    })
-                })
+                        end: Position {
+                            line: 1,
+                            column: 12,
+                            utf8_index: 11,
+                            utf16_index: 11,
+                        }
+                    }
+                }))
             },
             bindings: Vec::new()
         })
-    )
+    );
 }
 
 #[test]
@@ -1187,16 +1134,124 @@ fn lambda_params_without_dot_at_the_ending() {
     assert_eq!(
         errors, 
         &[
-            "Token inesperado encontrado, esperava-se um \"<identificador>\", na linha 1 e coluna 8",
-            "Token inesperado encontrado, esperava-se um \"<identificador>\", na linha 1 e coluna 12",
-            "Token inesperado encontrado, esperava-se um \"<identificador>\", na linha 1 e coluna 14",
-            "Token inesperado encontrado, esperava-se um \"<identificador>\", na linha 1 e coluna 16",
-            "Fim inesperado do código"
+            "Token inesperado encontrado, esperava-se um \"<identificador>\", \".\" ou \"->\", na linha 1 e coluna 8",
         ]
     );
 
+    assert_eq!(ast, None)
+}
+
+#[test]
+fn import_without_string() {
+    let source_code = "import bool;\nx";
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let ast = parse(tokens, &mut diagnostics);
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
     assert_eq!(
-        ast,
-        None
-    )
-}
\ No newline at end of file
+        errors,
+        &["Token inesperado encontrado, esperava-se um \"<texto>\", na linha \
+           1 e coluna 8"]
+    );
+
+    assert_eq!(ast.map(|program| program.imports), Some(Vec::new()));
+}
+
+#[test]
+fn independent_errors_in_bindings() {
+    let source_code =
+        "let\n    a = \\x (y). x;\n    b = = c;\n    c = (d;\nin\n   a b c";
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let ast = parse(tokens, &mut diagnostics);
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    // Um erro por definição; as definições seguintes continuam sendo lidas.
+    assert_eq!(
+        errors,
+        &[
+            "Token inesperado encontrado, esperava-se um \"<identificador>\", \".\" ou \"->\", na linha 2 e coluna 12",
+            "Token inesperado encontrado, esperava-se um \"<número>\", \"<identificador>\", \"\\\", \"(\" ou \"let\", na linha 3 e coluna 9",
+            "Parentesis aberto mas não fechado, na linha 4 e coluna 9",
+        ]
+    );
+
+    let names = ast.map(|program| {
+        program
+            .bindings
+            .into_iter()
+            .map(|binding| binding.name.content)
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(names, Some(vec![String::from("a"), String::from("c")]));
+}
+
+#[test]
+fn invalid_binding_name_skips_binding() {
+    let source_code = "let\n    1 = x;\n    b = \\x. x;\nin\n   b";
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let ast = parse(tokens, &mut diagnostics);
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        &["Token inesperado encontrado, esperava-se um \"<identificador>\", \
+           na linha 2 e coluna 5"]
+    );
+
+    let names = ast.map(|program| {
+        program
+            .bindings
+            .into_iter()
+            .map(|binding| binding.name.content)
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(names, Some(vec![String::from("b")]));
+}
+
+#[test]
+fn unexpected_token_recovers_at_close_paren() {
+    let source_code = "(a = b c) d;\ne";
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let ast = parse(tokens, &mut diagnostics);
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    // O `=` pula até o `)`; o `;` fora de definições também é inesperado,
+    // mas é ele mesmo o ponto de sincronização.
+    assert_eq!(
+        errors,
+        &[
+            "Token inesperado encontrado, esperava-se um \"<número>\", \"<identificador>\", \"\\\", \"(\" ou \"let\", na linha 1 e coluna 4",
+            "Token inesperado encontrado, esperava-se um \"<número>\", \"<identificador>\", \"\\\", \"(\" ou \"let\", na linha 1 e coluna 12",
+        ]
+    );
+
+    let name = |expr: &ast::Expr| match expr {
+        ast::Expr::Variable(symbol) => symbol.content.clone(),
+        _ => String::from("?"),
+    };
+    match ast.map(|program| program.main_expression) {
+        Some(ast::Expr::Application { function, argument }) => {
+            assert_eq!(name(&argument), "e");
+            match *function {
+                ast::Expr::Application { function, argument } => {
+                    assert_eq!(name(&function), "a");
+                    assert_eq!(name(&argument), "d");
+                }
+                expr => panic!("aplicação esperada, encontrado {:?}", expr),
+            }
+        }
+        expr => panic!("aplicação esperada, encontrado {:?}", expr),
+    }
+}
@@ -1,5 +1,5 @@
 use crate::compiler::lexer::generate_tokens;
-use crate::compiler::parser::{ast, parse};
+use crate::compiler::parser::{ast, parse, parse_library};
 use pinguim_language::{
     error::Diagnostics,
     position::{Position, Span},
@@ -16,6 +16,8 @@ fn parse_identifier() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Variable(ast::Symbol {
                 content: String::from("id"),
                 span: Span {
@@ -49,6 +51,8 @@ fn parse_numeral() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Number(42),
             bindings: Vec::new(),
         })
@@ -66,6 +70,8 @@ fn parse_lambda() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Lambda {
                 parameter: ast::Symbol {
                     content: String::from("foo"),
@@ -108,6 +114,156 @@ fn parse_lambda() {
     )
 }
 
+#[test]
+fn parse_unicode_lambda_with_arrow() {
+    let source_code = "λx → x";
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let ast = parse(tokens, &mut diagnostics);
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(
+        ast,
+        Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
+            main_expression: ast::Expr::Lambda {
+                parameter: ast::Symbol {
+                    content: String::from("x"),
+                    span: Span {
+                        start: Position {
+                            line: 1,
+                            column: 2,
+                            utf8_index: 2,
+                            utf16_index: 1,
+                        },
+                        end: Position {
+                            line: 1,
+                            column: 3,
+                            utf8_index: 3,
+                            utf16_index: 2,
+                        }
+                    }
+                },
+
+                body: Box::new(ast::Expr::Variable(ast::Symbol {
+                    content: String::from("x"),
+                    span: Span {
+                        start: Position {
+                            line: 1,
+                            column: 6,
+                            utf8_index: 8,
+                            utf16_index: 5,
+                        },
+                        end: Position {
+                            line: 1,
+                            column: 7,
+                            utf8_index: 9,
+                            utf16_index: 6,
+                        }
+                    }
+                })),
+            },
+            bindings: Vec::new(),
+        })
+    )
+}
+
+#[test]
+fn parse_nested_let() {
+    let source_code = "\\n. let a = n; in a";
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let ast = parse(tokens, &mut diagnostics);
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(
+        ast,
+        Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
+            main_expression: ast::Expr::Lambda {
+                parameter: ast::Symbol {
+                    content: String::from("n"),
+                    span: Span {
+                        start: Position {
+                            line: 1,
+                            column: 2,
+                            utf8_index: 1,
+                            utf16_index: 1,
+                        },
+                        end: Position {
+                            line: 1,
+                            column: 3,
+                            utf8_index: 2,
+                            utf16_index: 2,
+                        }
+                    }
+                },
+
+                body: Box::new(ast::Expr::Let {
+                    recursive: false,
+                    bindings: vec![ast::Binding {
+                        name: ast::Symbol {
+                            content: String::from("a"),
+                            span: Span {
+                                start: Position {
+                                    line: 1,
+                                    column: 9,
+                                    utf8_index: 8,
+                                    utf16_index: 8,
+                                },
+                                end: Position {
+                                    line: 1,
+                                    column: 10,
+                                    utf8_index: 9,
+                                    utf16_index: 9,
+                                }
+                            }
+                        },
+                        expression: ast::Expr::Variable(ast::Symbol {
+                            content: String::from("n"),
+                            span: Span {
+                                start: Position {
+                                    line: 1,
+                                    column: 13,
+                                    utf8_index: 12,
+                                    utf16_index: 12,
+                                },
+                                end: Position {
+                                    line: 1,
+                                    column: 14,
+                                    utf8_index: 13,
+                                    utf16_index: 13,
+                                }
+                            }
+                        }),
+                        doc: None,
+                    }],
+                    body: Box::new(ast::Expr::Variable(ast::Symbol {
+                        content: String::from("a"),
+                        span: Span {
+                            start: Position {
+                                line: 1,
+                                column: 19,
+                                utf8_index: 18,
+                                utf16_index: 18,
+                            },
+                            end: Position {
+                                line: 1,
+                                column: 20,
+                                utf8_index: 19,
+                                utf16_index: 19,
+                            }
+                        }
+                    })),
+                }),
+            },
+            bindings: Vec::new(),
+        })
+    )
+}
+
 #[test]
 fn parse_lambda_with_many_params() {
     let source_code = "\\foo1 foo2 foo3. bar";
@@ -119,6 +275,8 @@ fn parse_lambda_with_many_params() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Lambda {
                 parameter: ast::Symbol {
                     content: String::from("foo1"),
@@ -212,6 +370,8 @@ fn parse_lambda_with_many_params_nested() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Lambda {
                 parameter: ast::Symbol {
                     content: String::from("foo1"),
@@ -305,6 +465,8 @@ fn parse_app() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Application {
                 function: Box::new(ast::Expr::Application {
                     function: Box::new(ast::Expr::Variable(ast::Symbol {
@@ -568,6 +730,8 @@ fn parse_parenthesized() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Application {
                 function: Box::new(ast::Expr::Application {
                     function: Box::new(f_var),
@@ -594,6 +758,8 @@ fn parse_with_one_binding() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Application {
                 function: Box::new(ast::Expr::Variable(ast::Symbol {
                     content: String::from("two"),
@@ -766,6 +932,7 @@ fn parse_with_one_binding() {
                         })
                     }),
                 },
+                doc: None,
             }]
         })
     )
@@ -782,6 +949,8 @@ fn parse_with_one_binding_no_semicolon() {
     assert_eq!(
         ast,
         Some(ast::Program {
+            imports: Vec::new(),
+            recursive: false,
             main_expression: ast::Expr::Application {
                 function: Box::new(ast::Expr::Variable(ast::Symbol {
                     content: String::from("two"),
@@ -954,6 +1123,7 @@ fn parse_with_one_binding_no_semicolon() {
                         })
                     }),
                 },
+                doc: None,
             }]
         })
     )
@@ -966,6 +1136,8 @@ fn parse_with_many_bindings() {
     let tokens = generate_tokens(source_code, &mut diagnostics);
     let ast = parse(tokens, &mut diagnostics);
     let expected_ast = ast::Program {
+        imports: Vec::new(),
+        recursive: false,
         bindings: vec![
             ast::Binding {
                 name: ast::Symbol {
@@ -1124,6 +1296,7 @@ fn parse_with_many_bindings() {
                         }),
                     }),
                 },
+                doc: None,
             },
             ast::Binding {
                 name: ast::Symbol {
@@ -1242,6 +1415,7 @@ fn parse_with_many_bindings() {
                         }),
                     }),
                 },
+                doc: None,
             },
             ast::Binding {
                 name: ast::Symbol {
@@ -1363,6 +1537,7 @@ fn parse_with_many_bindings() {
                         }),
                     }),
                 },
+                doc: None,
             },
         ],
         main_expression: ast::Expr::Application {
@@ -1393,3 +1568,147 @@ fn parse_with_many_bindings() {
     assert!(diagnostics.is_ok());
     assert_eq!(ast, Some(expected_ast));
 }
+
+#[test]
+fn parse_imports() {
+    let source_code = "import \"bool.lam\";\nimport \"../num.lam\";\n42";
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let ast = parse(tokens, &mut diagnostics).unwrap();
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(
+        ast.imports,
+        &[
+            ast::Import {
+                path: String::from("bool.lam"),
+                span: Span {
+                    start: Position {
+                        line: 1,
+                        column: 8,
+                        utf8_index: 7,
+                        utf16_index: 7,
+                    },
+                    end: Position {
+                        line: 1,
+                        column: 18,
+                        utf8_index: 17,
+                        utf16_index: 17,
+                    },
+                },
+            },
+            ast::Import {
+                path: String::from("../num.lam"),
+                span: Span {
+                    start: Position {
+                        line: 2,
+                        column: 8,
+                        utf8_index: 26,
+                        utf16_index: 26,
+                    },
+                    end: Position {
+                        line: 2,
+                        column: 20,
+                        utf8_index: 38,
+                        utf16_index: 38,
+                    },
+                },
+            },
+        ]
+    );
+    assert_eq!(ast.main_expression, ast::Expr::Number(42));
+    assert!(ast.bindings.is_empty());
+}
+
+#[test]
+fn parse_library_file() {
+    let source_code = "import \"bool.lam\";\nnot = \\b. b false true;\nid = 1";
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let library = parse_library(tokens, &mut diagnostics).unwrap();
+
+    assert!(diagnostics.is_ok());
+
+    let paths =
+        library.imports.iter().map(|import| &import.path).collect::<Vec<_>>();
+    assert_eq!(paths, &["bool.lam"]);
+
+    let names = library
+        .bindings
+        .iter()
+        .map(|binding| binding.name.content.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, &["not", "id"]);
+    assert_eq!(library.bindings[1].expression, ast::Expr::Number(1));
+}
+
+#[test]
+fn parse_doc_comments() {
+    let source_code = "let\n    \
+                       --| Identidade.\n    \
+                       --|\n    \
+                       --|  Não faz nada.\n    \
+                       id = \\x. x;\n    \
+                       k = \\x y. x;\n    \
+                       --| Constante.\n    \
+                       c = k id;\n\
+                       in c";
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let ast = parse(tokens, &mut diagnostics).unwrap();
+
+    assert!(diagnostics.is_ok());
+
+    let docs = ast
+        .bindings
+        .iter()
+        .map(|binding| binding.doc.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        docs,
+        &[Some("Identidade.\n\n Não faz nada."), None, Some("Constante.")]
+    );
+}
+
+#[test]
+fn parse_doc_comments_outside_bindings() {
+    let source_code = "--| Ignorado.\nlet a = 1; --| Também ignorado.\nin a";
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let ast = parse(tokens, &mut diagnostics).unwrap();
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(ast.bindings[0].doc, None);
+    assert_eq!(
+        ast.main_expression,
+        ast::Expr::Variable(ast::Symbol {
+            content: String::from("a"),
+            span: Span {
+                start: Position {
+                    line: 3,
+                    column: 4,
+                    utf8_index: 50,
+                    utf16_index: 49,
+                },
+                end: Position {
+                    line: 3,
+                    column: 5,
+                    utf8_index: 51,
+                    utf16_index: 50,
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn parse_library_doc_comments() {
+    let source_code =
+        "import \"bool.lam\";\n--| Negação.\nnot = \\b. b false true;";
+    let mut diagnostics = Diagnostics::new();
+    let tokens = generate_tokens(source_code, &mut diagnostics);
+    let library = parse_library(tokens, &mut diagnostics).unwrap();
+
+    assert!(diagnostics.is_ok());
+    assert_eq!(library.bindings[0].doc.as_deref(), Some("Negação."));
+}
@@ -0,0 +1,41 @@
+//! Esse módulo exporta o prelúdio padrão: definições de combinadores comuns
+//! (booleanos, aritmética, pares, listas, etc.) que ficam disponíveis em todo
+//! programa, a não ser que sejam desabilitadas nas [`Options`] de compilação.
+//!
+//! As codificações usadas são as mesmas do módulo [`crate::encoding`].
+//!
+//! [`Options`]: crate::compiler::Options
+
+#[cfg(test)]
+mod test;
+
+use crate::compiler::lexer::generate_tokens;
+use crate::compiler::parser::ast::Binding;
+use crate::compiler::parser::parse_bindings;
+use pinguim_language::error::Diagnostics;
+use std::sync::OnceLock;
+
+/// Versão do prelúdio. É incrementada sempre que uma definição é adicionada,
+/// removida ou alterada.
+pub const VERSION: u32 = 1;
+
+/// Código-fonte do prelúdio: uma sequência de definições `nome = expr;`.
+pub const SOURCE: &str = include_str!("prelude.lam");
+
+/// Retorna as definições do prelúdio, na ordem em que foram declaradas. O
+/// prelúdio só é parsado na primeira chamada; as seguintes retornam cópias.
+pub fn bindings() -> Vec<Binding> {
+    static BINDINGS: OnceLock<Vec<Binding>> = OnceLock::new();
+
+    let bindings = BINDINGS.get_or_init(|| {
+        let mut diagnostics = Diagnostics::new();
+        let tokens = generate_tokens(SOURCE, &mut diagnostics);
+
+        match parse_bindings(tokens, &mut diagnostics) {
+            Some(bindings) if diagnostics.is_ok() => bindings,
+            _ => panic!("prelúdio padrão inválido"),
+        }
+    });
+
+    bindings.clone()
+}
@@ -0,0 +1,130 @@
+use super::bindings;
+use crate::compiler::{compile, compile_with_options, Options};
+use crate::interpreter::run_once;
+use crate::value::Value;
+
+fn normalize(source: &str) -> Value {
+    run_once(compile(source).unwrap())
+}
+
+fn normalize_without_prelude(source: &str) -> Value {
+    let options = Options { prelude: false, ..Options::default() };
+    run_once(compile_with_options(source, &options).unwrap())
+}
+
+fn assert_normal_form(source: &str, expected: &str) {
+    let actual = normalize(source);
+    let expected = normalize_without_prelude(expected);
+    assert!(
+        actual.beta_equiv(&expected),
+        "{} reduziu para {}, esperado {}",
+        source,
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn prelude_parses() {
+    let names = bindings()
+        .into_iter()
+        .map(|binding| binding.name.content)
+        .collect::<Vec<_>>();
+
+    for name in &["id", "true", "false", "succ", "pred", "plus", "mult", "Y"] {
+        assert!(names.iter().any(|found| found == name), "{}", name);
+    }
+    for name in &["pair", "fst", "snd", "nil", "cons", "just", "nothing"] {
+        assert!(names.iter().any(|found| found == name), "{}", name);
+    }
+}
+
+#[test]
+fn combinators_normal_forms() {
+    assert_normal_form("id", "\\x. x");
+    assert_normal_form("const", "\\x y. x");
+    assert_normal_form("compose", "\\f g x. f (g x)");
+    assert_normal_form("flip", "\\f x y. f y x");
+}
+
+#[test]
+fn booleans_normal_forms() {
+    assert_normal_form("true", "\\t f. t");
+    assert_normal_form("false", "\\t f. f");
+    assert_normal_form("not true", "\\t f. f");
+    assert_normal_form("not false", "\\t f. t");
+    assert_normal_form("and true false", "\\t f. f");
+    assert_normal_form("and true true", "\\t f. t");
+    assert_normal_form("or false true", "\\t f. t");
+    assert_normal_form("or false false", "\\t f. f");
+    assert_normal_form("if true a b", "a");
+    assert_normal_form("if false a b", "b");
+}
+
+#[test]
+fn arithmetic_normal_forms() {
+    assert_normal_form("succ 2", "3");
+    assert_normal_form("pred 3", "2");
+    assert_normal_form("pred 0", "0");
+    assert_normal_form("plus 2 3", "5");
+    assert_normal_form("sub 5 2", "3");
+    assert_normal_form("sub 2 5", "0");
+    assert_normal_form("mult 2 3", "6");
+    assert_normal_form("pow 2 3", "8");
+    assert_normal_form("is_zero 0", "\\t f. t");
+    assert_normal_form("is_zero 2", "\\t f. f");
+    assert_normal_form("leq 2 3", "\\t f. t");
+    assert_normal_form("leq 3 2", "\\t f. f");
+    assert_normal_form("eq 3 3", "\\t f. t");
+    assert_normal_form("eq 2 3", "\\t f. f");
+}
+
+#[test]
+fn fixed_point_combinator() {
+    // Soma de 0 até n.
+    let source = "let sum = Y (\\r n. is_zero n 0 (plus n (r (pred n)))); \
+                  in sum 3";
+    assert_normal_form(source, "6");
+}
+
+#[test]
+fn data_structures_normal_forms() {
+    assert_normal_form("pair a b", "\\p. p a b");
+    assert_normal_form("fst (pair a b)", "a");
+    assert_normal_form("snd (pair a b)", "b");
+    assert_normal_form("nil", "\\c n. n");
+    assert_normal_form("cons a (cons b nil)", "\\c n. c a (c b n)");
+    assert_normal_form("is_nil nil", "\\t f. t");
+    assert_normal_form("is_nil (cons a nil)", "\\t f. f");
+    assert_normal_form("nothing", "\\j n. n");
+    assert_normal_form("just a", "\\j n. j a");
+}
+
+#[test]
+fn program_shadows_prelude() {
+    assert_normal_form("let succ = \\n. n; in succ 2", "2");
+    // As definições podem vir em qualquer ordem, então `two` também usa a
+    // redefinição de `succ`.
+    let source = "let two = succ 1; succ = \\n. n; in plus two (succ 1)";
+    assert_normal_form(source, "2");
+}
+
+#[test]
+fn program_duplicates_are_still_errors() {
+    let diagnostics = compile("let id = a; id = b; in id").unwrap_err();
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        &["Definição \"id\" declarada mais de uma vez, na linha 1 e coluna 13"]
+    );
+}
+
+#[test]
+fn prelude_disabled() {
+    let options = Options { prelude: false, ..Options::default() };
+    let value = compile_with_options("succ", &options).unwrap();
+    assert_eq!(value, Value::Variable(String::from("succ")));
+}
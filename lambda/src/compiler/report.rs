@@ -0,0 +1,266 @@
+//! Esse módulo formata os erros de compilação para leitura humana, mostrando
+//! o trecho do código-fonte onde cada erro foi encontrado:
+//!
+//! ```text
+//! erro: Parentesis aberto mas não fechado
+//!  --> main.lam, linha 1, coluna 9
+//!   |
+//! 1 | let f = (\x. x;
+//!   |         ^ parêntese aberto aqui
+//!   |               - esperava-se ")" aqui
+//! ```
+//!
+//! Alguns erros destacam outros trechos além da sua localização, como o
+//! ponto onde um `)` era esperado; veja [`labels`]. A saída pode ser texto
+//! puro ou colorida com códigos ANSI, para terminais.
+
+#[cfg(test)]
+mod test;
+
+use crate::compiler::expansor::error::BindingCycle;
+use crate::compiler::import::error::InFile;
+use crate::compiler::parser::error::{UnmatchedCloseParen, UnmatchedOpenParen};
+use pinguim_language::error::{Diagnostics, Error};
+use pinguim_language::position::Span;
+use std::fmt::Write;
+
+/// Estilo ANSI do título e dos destaques principais.
+const ERROR_STYLE: &str = "\x1b[1;31m";
+/// Estilo ANSI das margens e dos destaques secundários.
+const NOTE_STYLE: &str = "\x1b[1;34m";
+/// Estilo ANSI da mensagem do erro.
+const MESSAGE_STYLE: &str = "\x1b[1m";
+/// Código ANSI que volta ao estilo padrão.
+const RESET: &str = "\x1b[0m";
+
+/// Opções de formatação dos erros.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RenderOptions {
+    /// Nome do arquivo do código-fonte, mostrado junto da localização. Por
+    /// padrão, nenhum.
+    pub file: Option<String>,
+    /// Se a saída usa cores ANSI. Por padrão, é texto puro.
+    pub colored: bool,
+}
+
+/// Trecho do código-fonte destacado na formatação de um erro.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Label {
+    /// Trecho destacado.
+    pub span: Span,
+    /// Explicação mostrada ao lado do trecho, possivelmente vazia.
+    pub message: String,
+    /// Se o trecho é a localização do erro (marcado com `^`), e não uma
+    /// informação adicional (marcada com `-`).
+    pub primary: bool,
+}
+
+/// Retorna os trechos do código-fonte destacados por um erro: sua
+/// localização, se houver, seguida dos trechos secundários conhecidos.
+///
+/// Erros localizados em arquivos importados não destacam nada, já que os
+/// trechos não pertencem ao código-fonte do programa; a sua localização é
+/// mostrada por [`render_error`] junto do arquivo importado.
+///
+/// - `error`: erro cujos trechos são retornados
+pub fn labels(error: &Error) -> Vec<Label> {
+    let cause = error.cause();
+    let span = match error.span() {
+        Some(span) if cause.downcast_ref::<InFile>().is_none() => span,
+        _ => return Vec::new(),
+    };
+
+    let mut labels = Vec::new();
+
+    if let Some(unmatched) = cause.downcast_ref::<UnmatchedOpenParen>() {
+        labels.push(primary(span, "parêntese aberto aqui"));
+        if let Some(close) = unmatched.expected_close {
+            labels.push(secondary(close, "esperava-se \")\" aqui"));
+        }
+    } else if cause.downcast_ref::<UnmatchedCloseParen>().is_some() {
+        labels.push(primary(span, "nenhum parêntese aberto antes"));
+    } else if let Some(binding_cycle) = cause.downcast_ref::<BindingCycle>() {
+        let cycle = &binding_cycle.cycle;
+        for (index, (name, name_span)) in cycle.iter().enumerate() {
+            let (next, _) = &cycle[(index + 1) % cycle.len()];
+            let message = format!("{:?} depende de {:?}", name, next);
+            if index == 0 {
+                labels.push(primary(span, &message));
+            } else {
+                labels.push(secondary(*name_span, &message));
+            }
+            if let Some(&use_span) = binding_cycle.uses.get(index) {
+                let message = format!("{:?} usada aqui", next);
+                labels.push(secondary(use_span, &message));
+            }
+        }
+    } else {
+        labels.push(primary(span, ""));
+    }
+
+    labels
+}
+
+/// Formata todos os erros de um diagnóstico, separados por linhas em branco.
+///
+/// - `source`: código-fonte do programa compilado
+/// - `diagnostics`: erros encontrados na compilação
+/// - `options`: opções de formatação
+pub fn render_diagnostics(
+    source: &str,
+    diagnostics: &Diagnostics,
+    options: &RenderOptions,
+) -> String {
+    let mut output = String::new();
+
+    for (index, error) in diagnostics.iter().enumerate() {
+        if index > 0 {
+            output.push('\n');
+        }
+        output.push_str(&render_error(source, error, options));
+    }
+
+    output
+}
+
+/// Formata um erro: a mensagem, a localização e as linhas do código-fonte
+/// com os trechos destacados por [`labels`], sublinhados. Linhas não
+/// consecutivas são separadas por `...`.
+///
+/// Erros sem localização mostram só a mensagem (e o arquivo, se houver).
+/// Erros de arquivos importados mostram o arquivo importado e a localização
+/// nele, sem as linhas do código-fonte.
+///
+/// - `source`: código-fonte do programa compilado
+/// - `error`: erro a ser formatado
+/// - `options`: opções de formatação
+pub fn render_error(
+    source: &str,
+    error: &Error,
+    options: &RenderOptions,
+) -> String {
+    let paint = |style: &str, text: &str| {
+        if options.colored && !text.is_empty() {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_owned()
+        }
+    };
+
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "{}{}",
+        paint(ERROR_STYLE, "erro"),
+        paint(MESSAGE_STYLE, &format!(": {}", error.cause()))
+    );
+
+    if let Some(in_file) = error.cause().downcast_ref::<InFile>() {
+        let location = match error.span() {
+            Some(span) => format!(
+                "{}, linha {}, coluna {}",
+                in_file.path,
+                span.start.line,
+                span.start.column.max(1)
+            ),
+            None => in_file.path.clone(),
+        };
+        let _ = writeln!(output, "{} {}", paint(NOTE_STYLE, "-->"), location);
+        return output;
+    }
+
+    let mut labels = labels(error);
+    let lines = source.lines().collect::<Vec<_>>();
+    labels.retain(|label| label.span.start.line <= lines.len());
+
+    let location = labels.iter().find(|label| label.primary).map(|label| {
+        let start = label.span.start;
+        format!("linha {}, coluna {}", start.line, start.column.max(1))
+    });
+    let location = match (&options.file, location) {
+        (Some(file), Some(location)) => format!("{}, {}", file, location),
+        (Some(file), None) => file.clone(),
+        (None, Some(location)) => location,
+        (None, None) => return output,
+    };
+
+    labels.sort_by_key(|label| label.span.start);
+    let width = labels
+        .last()
+        .map_or(0, |label| label.span.start.line.to_string().len());
+    let gutter = " ".repeat(width);
+    let bar = paint(NOTE_STYLE, "|");
+
+    let _ =
+        writeln!(output, "{}{} {}", gutter, paint(NOTE_STYLE, "-->"), location);
+    if labels.is_empty() {
+        return output;
+    }
+    let _ = writeln!(output, "{} {}", gutter, bar);
+
+    let mut previous_line = None;
+
+    for label in &labels {
+        let line_number = label.span.start.line.max(1);
+        let line = lines[line_number - 1];
+
+        if previous_line != Some(line_number) {
+            let skipped = previous_line
+                .is_some_and(|previous| previous + 1 < line_number);
+            if skipped {
+                let _ = writeln!(output, "{}", paint(NOTE_STYLE, "..."));
+            }
+            let number = format!("{:>width$}", line_number, width = width);
+            let _ = writeln!(
+                output,
+                "{} {} {}",
+                paint(NOTE_STYLE, &number),
+                bar,
+                line
+            );
+            previous_line = Some(line_number);
+        }
+
+        let start = label.span.start.column.max(1);
+        let line_length = line.chars().count() + 1;
+        let end = if label.span.end.line == line_number {
+            label.span.end.column.min(line_length)
+        } else {
+            line_length
+        };
+        let (marker, style) =
+            if label.primary { ("^", ERROR_STYLE) } else { ("-", NOTE_STYLE) };
+        let mut underline = marker.repeat(end.saturating_sub(start).max(1));
+        if !label.message.is_empty() {
+            underline.push(' ');
+            underline.push_str(&label.message);
+        }
+
+        let _ = writeln!(
+            output,
+            "{} {} {}{}",
+            gutter,
+            bar,
+            " ".repeat(start - 1),
+            paint(style, &underline)
+        );
+    }
+
+    output
+}
+
+/// Cria o destaque da localização de um erro.
+///
+/// - `span`: trecho destacado
+/// - `message`: explicação do trecho
+fn primary(span: Span, message: &str) -> Label {
+    Label { span, message: message.to_owned(), primary: true }
+}
+
+/// Cria um destaque secundário de um erro.
+///
+/// - `span`: trecho destacado
+/// - `message`: explicação do trecho
+fn secondary(span: Span, message: &str) -> Label {
+    Label { span, message: message.to_owned(), primary: false }
+}
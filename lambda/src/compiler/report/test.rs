@@ -0,0 +1,152 @@
+use super::{labels, render_diagnostics, render_error, RenderOptions};
+use crate::compiler::compile;
+use crate::compiler::import::error::InFile;
+use crate::compiler::lexer::generate_tokens;
+use pinguim_language::error::{Diagnostics, Error};
+
+#[test]
+fn render_unmatched_paren() {
+    let source = "let id = \\x. x;\nin (id y";
+    let diagnostics = compile(source).unwrap_err();
+    let error = diagnostics.iter().next().unwrap();
+
+    assert_eq!(
+        render_error(source, error, &RenderOptions::default()),
+        "erro: Parentesis aberto mas não fechado\n \
+         --> linha 2, coluna 4\n  \
+         |\n\
+         2 | in (id y\n  \
+         |    ^ parêntese aberto aqui\n"
+    );
+}
+
+#[test]
+fn render_secondary_label_with_file() {
+    let source = "let f = (\\x. x;\nin f";
+    let diagnostics = compile(source).unwrap_err();
+    let options =
+        RenderOptions { file: Some(String::from("main.lam")), colored: false };
+
+    assert_eq!(
+        render_diagnostics(source, &diagnostics, &options),
+        "erro: Parentesis aberto mas não fechado\n \
+         --> main.lam, linha 1, coluna 9\n  \
+         |\n\
+         1 | let f = (\\x. x;\n  \
+         |         ^ parêntese aberto aqui\n  \
+         |               - esperava-se \")\" aqui\n"
+    );
+}
+
+#[test]
+fn render_multi_char_span() {
+    let source = "let ab = c; c = ab; in ab";
+    let diagnostics = compile(source).unwrap_err();
+
+    assert_eq!(
+        render_diagnostics(source, &diagnostics, &RenderOptions::default()),
+        "erro: Definições cíclicas fora de um `let rec`: \"ab\" (linha 1, \
+         coluna 5) -> \"c\" (linha 1, coluna 13) -> \"ab\"\n \
+         --> linha 1, coluna 5\n  \
+         |\n\
+         1 | let ab = c; c = ab; in ab\n  \
+         |     ^^ \"ab\" depende de \"c\"\n  \
+         |          - \"c\" usada aqui\n  \
+         |             - \"c\" depende de \"ab\"\n  \
+         |                 -- \"ab\" usada aqui\n"
+    );
+}
+
+#[test]
+fn render_labels_in_distant_lines() {
+    let source = "let a = b;\n\n    b = a;\nin a";
+    let diagnostics = compile(source).unwrap_err();
+
+    assert_eq!(
+        render_diagnostics(source, &diagnostics, &RenderOptions::default()),
+        "erro: Definições cíclicas fora de um `let rec`: \"a\" (linha 1, \
+         coluna 5) -> \"b\" (linha 3, coluna 5) -> \"a\"\n \
+         --> linha 1, coluna 5\n  \
+         |\n\
+         1 | let a = b;\n  \
+         |     ^ \"a\" depende de \"b\"\n  \
+         |         - \"b\" usada aqui\n\
+         ...\n\
+         3 |     b = a;\n  \
+         |     - \"b\" depende de \"a\"\n  \
+         |         - \"a\" usada aqui\n"
+    );
+}
+
+#[test]
+fn render_colored() {
+    let source = "x)";
+    let diagnostics = compile(source).unwrap_err();
+    let error = diagnostics.iter().next().unwrap();
+    let options = RenderOptions { file: None, colored: true };
+
+    assert_eq!(
+        render_error(source, error, &options),
+        "\x1b[1;31merro\x1b[0m\x1b[1m: Parentesis fechado sobrando\x1b[0m\n \
+         \x1b[1;34m-->\x1b[0m linha 1, coluna 2\n  \
+         \x1b[1;34m|\x1b[0m\n\
+         \x1b[1;34m1\x1b[0m \x1b[1;34m|\x1b[0m x)\n  \
+         \x1b[1;34m|\x1b[0m  \x1b[1;31m^ nenhum parêntese aberto antes\
+         \x1b[0m\n"
+    );
+}
+
+#[test]
+fn render_without_span() {
+    let mut diagnostics = Diagnostics::new();
+    diagnostics.raise(Error::with_no_span(std::fmt::Error));
+    let error = diagnostics.iter().next().unwrap();
+
+    assert_eq!(
+        render_error("x", error, &RenderOptions::default()),
+        "erro: an error occurred when formatting an argument\n"
+    );
+
+    let options =
+        RenderOptions { file: Some(String::from("main.lam")), colored: false };
+    assert_eq!(
+        render_error("x", error, &options),
+        "erro: an error occurred when formatting an argument\n--> main.lam\n"
+    );
+    assert!(labels(error).is_empty());
+}
+
+#[test]
+fn render_error_in_imported_file() {
+    let mut diagnostics = Diagnostics::new();
+    let span = generate_tokens("\n  x", &mut diagnostics)[0].span;
+    let cause = InFile {
+        path: String::from("lib.lam"),
+        cause: String::from("Definição \"x\" não encontrada"),
+    };
+    diagnostics.raise(Error::new(cause.clone(), span));
+    diagnostics.raise(Error::with_no_span(cause));
+    let options =
+        RenderOptions { file: Some(String::from("main.lam")), colored: false };
+
+    assert_eq!(
+        render_diagnostics("x", &diagnostics, &options),
+        "erro: Em \"lib.lam\": Definição \"x\" não encontrada\n\
+         --> lib.lam, linha 2, coluna 3\n\
+         \n\
+         erro: Em \"lib.lam\": Definição \"x\" não encontrada\n\
+         --> lib.lam\n"
+    );
+}
+
+#[test]
+fn labels_of_unmatched_paren_at_end() {
+    let diagnostics = compile("(\\x. x").unwrap_err();
+    let error = diagnostics.iter().next().unwrap();
+    let labels = labels(error);
+
+    assert_eq!(labels.len(), 1);
+    assert!(labels[0].primary);
+    assert_eq!(labels[0].message, "parêntese aberto aqui");
+    assert_eq!(Some(labels[0].span), error.span());
+}
@@ -0,0 +1,115 @@
+use crate::compiler::expansor::LetLowering;
+use crate::compiler::{compile, compile_with_options, Options};
+use crate::interpreter::run_once;
+use crate::value::{NestedValue, Value};
+
+#[test]
+fn compile_program() {
+    let source_code = "let two = 2; id = \\x. x; in id two";
+
+    // (λx. x) (λf. λx. f (f x))
+    let expected = Value::Application {
+        function: NestedValue::new(Value::Lambda {
+            parameter: String::from("x"),
+            body: NestedValue::new(Value::Variable(String::from("x"))),
+        }),
+        argument: NestedValue::new(Value::church_numeral(2)),
+    };
+
+    assert_eq!(compile(source_code).ok(), Some(expected));
+}
+
+#[test]
+fn compile_alternative_lambda_syntaxes() {
+    let expected = compile("let k = \\x y. x; in k").unwrap();
+
+    for source_code in &[
+        "let k = λx y. x; in k",
+        "let k = \\x y -> x; in k",
+        "let k = λx y → x; in k",
+        "let k = λação β → ação; in k",
+    ] {
+        let value = compile(source_code).unwrap();
+        assert!(value.beta_equiv(&expected), "{}", source_code);
+    }
+}
+
+#[test]
+fn compile_recursive_binding() {
+    let source_code =
+        "let rec fact = \\n. if (is_zero n) 1 (mult n (fact (pred n)));\n\
+         in fact 3";
+    let value = run_once(compile(source_code).unwrap());
+    assert_eq!(value.church_numeral_to_int(), Some(6));
+}
+
+#[test]
+fn compile_mutually_recursive_bindings() {
+    let source_code = "let rec\n    \
+                       even = \\n. if (is_zero n) true (odd (pred n));\n    \
+                       odd = \\n. if (is_zero n) false (even (pred n));\n\
+                       in pair (even 3) (odd 3)";
+    let value = run_once(compile(source_code).unwrap());
+    let (even, odd) = value.church_pair_to_tuple().unwrap();
+
+    assert_eq!(even.church_boolean_to_bool(), Some(false));
+    assert_eq!(odd.church_boolean_to_bool(), Some(true));
+}
+
+#[test]
+fn compile_nested_let() {
+    let source_code = "(\\n. let sq = mult n n; in plus sq sq) 3";
+    let redex =
+        Options { let_lowering: LetLowering::Redex, ..Options::default() };
+
+    for options in &[Options::default(), redex] {
+        let value = compile_with_options(source_code, options).unwrap();
+        assert_eq!(run_once(value).church_numeral_to_int(), Some(18));
+    }
+}
+
+#[test]
+fn compile_nested_let_rec() {
+    let source_code = "let three = 3;\n\
+                       in (let rec fact = \\n. if (is_zero n) 1 \
+                       (mult n (fact (pred n))); in fact) three";
+    let value = run_once(compile(source_code).unwrap());
+    assert_eq!(value.church_numeral_to_int(), Some(6));
+}
+
+#[test]
+fn compile_stops_at_lexer() {
+    let diagnostics = compile("\\x. x $").unwrap_err();
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(errors, &["Caracter '$' é inválido, na linha 1 e coluna 7"]);
+}
+
+#[test]
+fn compile_stops_at_parser() {
+    let diagnostics = compile("(x").unwrap_err();
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        &["Parentesis aberto mas não fechado, na linha 1 e coluna 1"]
+    );
+}
+
+#[test]
+fn compile_stops_at_expansor() {
+    let diagnostics = compile("let a = a; in a").unwrap_err();
+
+    let errors =
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    assert_eq!(
+        errors,
+        &["Definições cíclicas fora de um `let rec`: \"a\" (linha 1, coluna \
+           5) -> \"a\", na linha 1 e coluna 5"]
+    );
+}
@@ -0,0 +1,598 @@
+//! Exporta a representação de termos Lambda com índices de De Bruijn.
+
+#[cfg(test)]
+mod test;
+
+use crate::value::{NestedValue, Value};
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::mem;
+
+/// Representação de um termo Lambda sem nomes de parâmetros. Variáveis
+/// ligadas são representadas pelo seu índice de De Bruijn: 1 se refere ao
+/// lambda mais próximo, 2 ao lambda que o envolve, e assim por diante.
+/// Variáveis livres mantém seu nome.
+///
+/// Como não há nomes de parâmetros, substituições não precisam renomear
+/// parâmetros para evitar captura de variáveis (ver [`Value::replace`]).
+///
+/// Termos são comparados sem considerar nomes de parâmetros, portanto a
+/// igualdade de [`DeBruijn`] equivale à alfa-equivalência de [`Value`].
+/// Assim como em [`Value`], clonagem e drop usam uma pilha explícita, e não
+/// estouram a pilha de chamadas em termos profundos.
+///
+/// # Exemplo
+///
+/// ```text
+/// λa. λb. a (λc. c b) z
+/// <=>
+/// λ. λ. 2 (λ. 1 2) z
+/// ```
+#[derive(Debug)]
+pub enum DeBruijn {
+    /// Uma variável ligada, com seu índice de De Bruijn.
+    Bound(u64),
+
+    /// Uma variável livre, com seu nome.
+    Free(String),
+
+    /// Uma aplicação de um argumento em uma função.
+    Application { function: Box<DeBruijn>, argument: Box<DeBruijn> },
+
+    /// Uma abstração Lambda. O nome original do parâmetro é guardado somente
+    /// como sugestão para a conversão de volta para [`Value`], e não é
+    /// considerado na comparação de termos.
+    Lambda { hint: String, body: Box<DeBruijn> },
+}
+
+impl DeBruijn {
+    /// Converte um termo com nomes para a representação com índices.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// fromValue :: Value -> DeBruijn
+    /// fromValue v =
+    ///   let fromValueWith (Variable s) ps = case elemIndex s ps of
+    ///         Just i -> Bound (i + 1)
+    ///         Nothing -> Free s
+    ///       fromValueWith (Application f a) ps =
+    ///         Application (fromValueWith f ps) (fromValueWith a ps)
+    ///       fromValueWith (Lambda p b) ps =
+    ///         Lambda p (fromValueWith b (p : ps))
+    ///   in fromValueWith v []
+    /// ```
+    pub fn from_value(value: &Value) -> Self {
+        /// Uma operação auxiliar de conversão.
+        enum Operation<'value> {
+            /// Inicia a conversão de um termo qualquer.
+            Convert(&'value Value),
+            /// Finaliza a conversão de um termo lambda, saindo do escopo do
+            /// parâmetro.
+            MakeLambda(&'value str),
+            /// Finaliza a conversão de um termo aplicação.
+            MakeApplication,
+        }
+
+        // Parâmetros em escopo, do mais externo para o mais interno.
+        let mut scope: Vec<&str> = Vec::new();
+        let mut operation_stack = vec![Operation::Convert(value)];
+        let mut output_stack: Vec<DeBruijn> = Vec::new();
+
+        while let Some(operation) = operation_stack.pop() {
+            match operation {
+                Operation::Convert(value) => match value {
+                    // Caso base
+                    Value::Variable(variable) => {
+                        let position = scope
+                            .iter()
+                            .rposition(|&param| param == variable.as_str());
+                        output_stack.push(match position {
+                            Some(index) => {
+                                DeBruijn::Bound((scope.len() - index) as u64)
+                            }
+                            None => DeBruijn::Free(variable.clone()),
+                        });
+                    }
+
+                    Value::Application { function, argument } => {
+                        // .pop() retorna elementos na ordem inversa do .push()
+                        operation_stack.push(Operation::MakeApplication);
+                        operation_stack.push(Operation::Convert(argument));
+                        operation_stack.push(Operation::Convert(function));
+                    }
+
+                    Value::Lambda { parameter, body } => {
+                        scope.push(parameter);
+                        operation_stack.push(Operation::MakeLambda(parameter));
+                        operation_stack.push(Operation::Convert(body));
+                    }
+                },
+
+                Operation::MakeLambda(parameter) => {
+                    scope.pop();
+                    let body = output_stack.pop().expect("debruijn body");
+                    output_stack.push(DeBruijn::Lambda {
+                        hint: parameter.to_owned(),
+                        body: Box::new(body),
+                    });
+                }
+
+                Operation::MakeApplication => {
+                    let argument =
+                        output_stack.pop().expect("debruijn argument");
+                    let function =
+                        output_stack.pop().expect("debruijn function");
+                    output_stack.push(DeBruijn::Application {
+                        function: Box::new(function),
+                        argument: Box::new(argument),
+                    });
+                }
+            }
+        }
+
+        output_stack.pop().expect("debruijn")
+    }
+
+    /// Converte este termo para a representação com nomes. Cada parâmetro
+    /// usa o nome sugerido pelo seu lambda, a não ser que ele conflite com uma
+    /// variável livre ou com um parâmetro em escopo. Nesse caso, `_` é
+    /// acrescentado ao nome até não haver conflito (`x_`, `x__`, ...), assim
+    /// como em [`Value::replace`].
+    ///
+    /// Índices que não se referem a nenhum lambda do termo são convertidos
+    /// para variáveis livres com nome `#i`, sendo `i` o índice relativo ao
+    /// lado de fora do termo.
+    pub fn to_value(&self) -> Value {
+        /// Uma operação auxiliar de conversão.
+        enum Operation<'term> {
+            /// Inicia a conversão de um termo qualquer.
+            Convert(&'term DeBruijn),
+            /// Finaliza a conversão de um termo lambda, saindo do escopo do
+            /// parâmetro.
+            MakeLambda,
+            /// Finaliza a conversão de um termo aplicação.
+            MakeApplication,
+        }
+
+        let free_vars: HashSet<&str> = self.free_vars().collect();
+
+        // Nomes escolhidos para os parâmetros em escopo, do mais externo para
+        // o mais interno.
+        let mut scope: Vec<String> = Vec::new();
+        let mut operation_stack = vec![Operation::Convert(self)];
+        let mut output_stack: Vec<Value> = Vec::new();
+
+        while let Some(operation) = operation_stack.pop() {
+            match operation {
+                Operation::Convert(term) => match term {
+                    // Casos base
+                    DeBruijn::Bound(index) => {
+                        let position = (scope.len() as u64).checked_sub(*index);
+                        output_stack.push(Value::Variable(match position {
+                            Some(position) => scope[position as usize].clone(),
+                            None => {
+                                format!("#{}", *index - scope.len() as u64)
+                            }
+                        }));
+                    }
+
+                    DeBruijn::Free(variable) => {
+                        output_stack.push(Value::Variable(variable.clone()));
+                    }
+
+                    DeBruijn::Application { function, argument } => {
+                        // .pop() retorna elementos na ordem inversa do .push()
+                        operation_stack.push(Operation::MakeApplication);
+                        operation_stack.push(Operation::Convert(argument));
+                        operation_stack.push(Operation::Convert(function));
+                    }
+
+                    DeBruijn::Lambda { hint, body } => {
+                        let hint =
+                            if hint.is_empty() { "x" } else { hint.as_str() };
+                        let is_taken = |name: &str| {
+                            free_vars.contains(name)
+                                || scope.iter().any(|param| param == name)
+                        };
+
+                        let mut name = hint.to_owned();
+                        while is_taken(&name) {
+                            name.push('_');
+                        }
+
+                        scope.push(name);
+                        operation_stack.push(Operation::MakeLambda);
+                        operation_stack.push(Operation::Convert(body));
+                    }
+                },
+
+                Operation::MakeLambda => {
+                    let parameter = scope.pop().expect("value parameter");
+                    let body = output_stack.pop().expect("value body");
+                    output_stack.push(Value::Lambda {
+                        parameter,
+                        body: NestedValue::new(body),
+                    });
+                }
+
+                Operation::MakeApplication => {
+                    let argument = output_stack.pop().expect("value argument");
+                    let function = output_stack.pop().expect("value function");
+                    output_stack.push(Value::Application {
+                        function: NestedValue::new(function),
+                        argument: NestedValue::new(argument),
+                    });
+                }
+            }
+        }
+
+        output_stack.pop().expect("value")
+    }
+
+    /// Cria um iterador sobre os nomes das variáveis livres deste termo.
+    /// Nomes podem aparecer mais de uma vez.
+    pub fn free_vars(&self) -> impl Iterator<Item = &str> {
+        let mut visit_stack = vec![self];
+
+        std::iter::from_fn(move || {
+            while let Some(term) = visit_stack.pop() {
+                match term {
+                    DeBruijn::Bound(_) => (),
+                    DeBruijn::Free(variable) => return Some(variable.as_str()),
+                    DeBruijn::Application { function, argument } => {
+                        visit_stack.push(argument);
+                        visit_stack.push(function);
+                    }
+                    DeBruijn::Lambda { hint: _, body } => {
+                        visit_stack.push(body);
+                    }
+                }
+            }
+
+            None
+        })
+    }
+
+    /// Soma `amount` a todos os índices que se referem a lambdas de fora deste
+    /// termo. Usado ao mover um termo para dentro (`amount` positivo) ou para
+    /// fora (`amount` negativo) de lambdas.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// shift :: Int -> DeBruijn -> DeBruijn
+    /// shift d t =
+    ///   let shiftWith (Bound i) c = if i > c then Bound (i + d) else Bound i
+    ///       shiftWith (Free s) c = Free s
+    ///       shiftWith (Application f a) c =
+    ///         Application (shiftWith f c) (shiftWith a c)
+    ///       shiftWith (Lambda h b) c = Lambda h (shiftWith b (c + 1))
+    ///   in shiftWith t 0
+    /// ```
+    pub fn shift(&mut self, amount: i64) {
+        // Pilha de termos junto com o número de lambdas que os envolvem
+        // dentro deste termo.
+        let mut visit_stack: Vec<(&mut DeBruijn, u64)> = vec![(self, 0)];
+
+        while let Some((term, depth)) = visit_stack.pop() {
+            match term {
+                DeBruijn::Bound(index) => {
+                    if *index > depth {
+                        *index = (*index as i64 + amount) as u64;
+                    }
+                }
+                DeBruijn::Free(_) => (),
+                DeBruijn::Application { function, argument } => {
+                    visit_stack.push((argument, depth));
+                    visit_stack.push((function, depth));
+                }
+                DeBruijn::Lambda { hint: _, body } => {
+                    visit_stack.push((body, depth + 1));
+                }
+            }
+        }
+    }
+
+    /// Substitui todas as ocorrências do índice `index` (relativo ao lado de
+    /// fora deste termo) por `new_term`. Não precisa lidar com captura de
+    /// variáveis: os índices livres de `new_term` são ajustados conforme ele
+    /// entra em lambdas.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// substitute :: Int -> DeBruijn -> DeBruijn -> DeBruijn
+    /// substitute j n t =
+    ///   let substWith (Bound i) c = if i == j + c then shift c n else Bound i
+    ///       substWith (Free s) c = Free s
+    ///       substWith (Application f a) c =
+    ///         Application (substWith f c) (substWith a c)
+    ///       substWith (Lambda h b) c = Lambda h (substWith b (c + 1))
+    ///   in substWith t 0
+    /// ```
+    pub fn substitute(&mut self, index: u64, new_term: &DeBruijn) {
+        let mut visit_stack: Vec<(&mut DeBruijn, u64)> = vec![(self, 0)];
+
+        while let Some((term, depth)) = visit_stack.pop() {
+            match term {
+                DeBruijn::Bound(bound) => {
+                    if *bound == index + depth {
+                        let mut replacement = new_term.clone();
+                        replacement.shift(depth as i64);
+                        *term = replacement;
+                    }
+                }
+                DeBruijn::Free(_) => (),
+                DeBruijn::Application { function, argument } => {
+                    visit_stack.push((argument, depth));
+                    visit_stack.push((function, depth));
+                }
+                DeBruijn::Lambda { hint: _, body } => {
+                    visit_stack.push((body, depth + 1));
+                }
+            }
+        }
+    }
+
+    /// Realiza a beta-redução de `(λ. self) argument`: substitui o índice do
+    /// parâmetro por `argument`, e decrementa os índices que se referiam a
+    /// lambdas de fora, já que o lambda deixa de existir.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// instantiate :: DeBruijn -> DeBruijn -> DeBruijn
+    /// instantiate b a = shift (-1) (substitute 1 (shift 1 a) b)
+    /// ```
+    pub fn instantiate(&mut self, argument: &DeBruijn) {
+        let mut visit_stack: Vec<(&mut DeBruijn, u64)> = vec![(self, 0)];
+
+        while let Some((term, depth)) = visit_stack.pop() {
+            match term {
+                DeBruijn::Bound(index) => {
+                    if *index == depth + 1 {
+                        let mut replacement = argument.clone();
+                        replacement.shift(depth as i64);
+                        *term = replacement;
+                    } else if *index > depth + 1 {
+                        *index -= 1;
+                    }
+                }
+                DeBruijn::Free(_) => (),
+                DeBruijn::Application { function, argument } => {
+                    visit_stack.push((argument, depth));
+                    visit_stack.push((function, depth));
+                }
+                DeBruijn::Lambda { hint: _, body } => {
+                    visit_stack.push((body, depth + 1));
+                }
+            }
+        }
+    }
+
+    /// Faz a redução de um único redex, mais externo, mais à esquerda.
+    /// Retorna se tal redex foi encontrado. Equivalente a
+    /// [`Value::reduce_one`], mas sem renomear parâmetros.
+    pub fn reduce_one(&mut self) -> bool {
+        let mut candidate_stack: Vec<&mut DeBruijn> = vec![self];
+
+        while let Some(candidate) = candidate_stack.pop() {
+            if let DeBruijn::Application { function, argument } = candidate {
+                if let DeBruijn::Lambda { hint: _, body } = &mut **function {
+                    body.instantiate(argument);
+                    *candidate = mem::replace(&mut **body, DeBruijn::Bound(0));
+                    return true;
+                }
+            }
+
+            match candidate {
+                DeBruijn::Bound(_) | DeBruijn::Free(_) => (),
+                DeBruijn::Application { function, argument } => {
+                    // A função é mais à esquerda, então é visitada antes
+                    // (lembra que a pilha inverte).
+                    candidate_stack.push(argument);
+                    candidate_stack.push(function);
+                }
+                DeBruijn::Lambda { hint: _, body } => {
+                    candidate_stack.push(body);
+                }
+            }
+        }
+
+        false
+    }
+}
+
+impl DeBruijn {
+    /// Retira os subtermos diretos deste termo, colocando-os na pilha e
+    /// deixando índices quaisquer no lugar. Usado pelo drop iterativo.
+    fn take_subterms(&mut self, stack: &mut Vec<DeBruijn>) {
+        match self {
+            DeBruijn::Bound(_) | DeBruijn::Free(_) => (),
+            DeBruijn::Application { function, argument } => {
+                stack.push(mem::replace(&mut **function, DeBruijn::Bound(0)));
+                stack.push(mem::replace(&mut **argument, DeBruijn::Bound(0)));
+            }
+            DeBruijn::Lambda { hint: _, body } => {
+                stack.push(mem::replace(&mut **body, DeBruijn::Bound(0)));
+            }
+        }
+    }
+}
+
+impl Clone for DeBruijn {
+    /// # Algoritmo recursivo
+    ///
+    /// ```haskell
+    /// cloneTerm :: DeBruijn -> DeBruijn
+    /// cloneTerm (Bound i) = Bound i
+    /// cloneTerm (Free s) = Free s
+    /// cloneTerm (Application f a) = Application (cloneTerm f) (cloneTerm a)
+    /// cloneTerm (Lambda h b) = Lambda h (cloneTerm b)
+    /// ```
+    fn clone(&self) -> Self {
+        /// Uma operação auxiliar de clonagem.
+        enum Operation<'term> {
+            /// Inicia a clonagem de um termo qualquer.
+            Clone(&'term DeBruijn),
+            /// Finaliza a clonagem de um termo lambda.
+            MakeLambda(String),
+            /// Finaliza a clonagem de um termo aplicação.
+            MakeApplication,
+        }
+
+        let mut operation_stack = vec![Operation::Clone(self)];
+        let mut output_stack: Vec<DeBruijn> = Vec::new();
+
+        while let Some(operation) = operation_stack.pop() {
+            match operation {
+                Operation::Clone(term) => match term {
+                    // Casos base
+                    DeBruijn::Bound(index) => {
+                        output_stack.push(DeBruijn::Bound(*index));
+                    }
+
+                    DeBruijn::Free(variable) => {
+                        output_stack.push(DeBruijn::Free(variable.clone()));
+                    }
+
+                    DeBruijn::Application { function, argument } => {
+                        // .pop() retorna elementos na ordem inversa do .push()
+                        operation_stack.push(Operation::MakeApplication);
+                        operation_stack.push(Operation::Clone(argument));
+                        operation_stack.push(Operation::Clone(function));
+                    }
+
+                    DeBruijn::Lambda { hint, body } => {
+                        operation_stack
+                            .push(Operation::MakeLambda(hint.clone()));
+                        operation_stack.push(Operation::Clone(body));
+                    }
+                },
+
+                Operation::MakeLambda(hint) => {
+                    let body = output_stack.pop().expect("clone debruijn body");
+                    output_stack
+                        .push(DeBruijn::Lambda { hint, body: Box::new(body) });
+                }
+
+                Operation::MakeApplication => {
+                    let argument =
+                        output_stack.pop().expect("clone debruijn argument");
+                    let function =
+                        output_stack.pop().expect("clone debruijn function");
+                    output_stack.push(DeBruijn::Application {
+                        function: Box::new(function),
+                        argument: Box::new(argument),
+                    });
+                }
+            }
+        }
+
+        output_stack.pop().expect("clone debruijn")
+    }
+}
+
+impl Drop for DeBruijn {
+    fn drop(&mut self) {
+        let mut drop_stack = Vec::new();
+        self.take_subterms(&mut drop_stack);
+
+        // Cada termo solto aqui já teve seus subtermos retirados, então o
+        // drop dele não desce mais que um nível.
+        while let Some(mut term) = drop_stack.pop() {
+            term.take_subterms(&mut drop_stack);
+        }
+    }
+}
+
+impl PartialEq for DeBruijn {
+    /// # Algoritmo recursivo
+    ///
+    /// ```haskell
+    /// instance Eq DeBruijn where
+    ///   (Bound i1) == (Bound i2) = i1 == i2
+    ///   (Free s1) == (Free s2) = s1 == s2
+    ///   (Application f1 a1) == (Application f2 a2) = f1 == f2 && a1 == a2
+    ///   (Lambda _ b1) == (Lambda _ b2) = b1 == b2
+    ///   _ == _ = False
+    /// ```
+    fn eq(&self, other: &Self) -> bool {
+        let mut compare_stack = vec![(self, other)];
+
+        while let Some((left, right)) = compare_stack.pop() {
+            match (left, right) {
+                (DeBruijn::Bound(left), DeBruijn::Bound(right)) => {
+                    if left != right {
+                        return false;
+                    }
+                }
+
+                (DeBruijn::Free(left), DeBruijn::Free(right)) => {
+                    if left != right {
+                        return false;
+                    }
+                }
+
+                (
+                    DeBruijn::Application {
+                        function: left_function,
+                        argument: left_argument,
+                    },
+                    DeBruijn::Application {
+                        function: right_function,
+                        argument: right_argument,
+                    },
+                ) => {
+                    compare_stack.push((left_argument, right_argument));
+                    compare_stack.push((left_function, right_function));
+                }
+
+                (
+                    DeBruijn::Lambda { hint: _, body: left_body },
+                    DeBruijn::Lambda { hint: _, body: right_body },
+                ) => {
+                    compare_stack.push((left_body, right_body));
+                }
+
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+impl Eq for DeBruijn {}
+
+impl Hash for DeBruijn {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        let mut visit_stack = vec![self];
+
+        while let Some(term) = visit_stack.pop() {
+            match term {
+                DeBruijn::Bound(index) => {
+                    state.write_u8(0);
+                    index.hash(state);
+                }
+                DeBruijn::Free(variable) => {
+                    state.write_u8(1);
+                    variable.hash(state);
+                }
+                DeBruijn::Application { function, argument } => {
+                    state.write_u8(2);
+                    visit_stack.push(argument);
+                    visit_stack.push(function);
+                }
+                DeBruijn::Lambda { hint: _, body } => {
+                    state.write_u8(3);
+                    visit_stack.push(body);
+                }
+            }
+        }
+    }
+}
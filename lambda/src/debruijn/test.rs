@@ -0,0 +1,167 @@
+use super::DeBruijn;
+use crate::compiler::compile;
+use crate::value::{NestedValue, Value};
+
+fn bound(index: u64) -> DeBruijn {
+    DeBruijn::Bound(index)
+}
+
+fn free(name: &str) -> DeBruijn {
+    DeBruijn::Free(String::from(name))
+}
+
+fn app(function: DeBruijn, argument: DeBruijn) -> DeBruijn {
+    DeBruijn::Application {
+        function: Box::new(function),
+        argument: Box::new(argument),
+    }
+}
+
+fn lambda(hint: &str, body: DeBruijn) -> DeBruijn {
+    DeBruijn::Lambda { hint: String::from(hint), body: Box::new(body) }
+}
+
+#[test]
+fn from_value() {
+    let value = compile("\\a. \\b. a (\\c. c b) z").unwrap();
+
+    // λ. λ. 2 (λ. 1 2) z
+    let expected = lambda(
+        "a",
+        lambda(
+            "b",
+            app(app(bound(2), lambda("c", app(bound(1), bound(2)))), free("z")),
+        ),
+    );
+
+    assert_eq!(DeBruijn::from_value(&value), expected);
+}
+
+#[test]
+fn from_value_shadowing() {
+    let value = compile("\\x. \\x. x").unwrap();
+
+    // λ. λ. 1
+    let expected = lambda("x", lambda("x", bound(1)));
+
+    assert_eq!(DeBruijn::from_value(&value), expected);
+}
+
+#[test]
+fn equality_ignores_names() {
+    let left = DeBruijn::from_value(&compile("\\x. \\y. x y").unwrap());
+    let right = DeBruijn::from_value(&compile("\\a. \\b. a b").unwrap());
+    let other = DeBruijn::from_value(&compile("\\a. \\b. b a").unwrap());
+
+    assert_eq!(left, right);
+    assert_ne!(left, other);
+}
+
+#[test]
+fn round_trip_keeps_names() {
+    let value = compile("\\f. \\x. f (g x) (\\y. y x)").unwrap();
+
+    assert_eq!(DeBruijn::from_value(&value).to_value(), value);
+}
+
+#[test]
+fn to_value_renames_shadowed() {
+    // λ. λ. 2
+    let term = lambda("y", lambda("y", bound(2)));
+
+    // λy. λy_. y
+    let expected = Value::Lambda {
+        parameter: String::from("y"),
+        body: NestedValue::new(Value::Lambda {
+            parameter: String::from("y_"),
+            body: NestedValue::new(Value::Variable(String::from("y"))),
+        }),
+    };
+
+    assert_eq!(term.to_value(), expected);
+}
+
+#[test]
+fn to_value_avoids_free() {
+    // λ. x 1 x_
+    let term = lambda("x", app(app(free("x"), bound(1)), free("x_")));
+
+    assert_eq!(term.to_value(), compile("\\x__. x x__ x_").unwrap());
+}
+
+#[test]
+fn shift() {
+    // λ. 1 2 3
+    let mut term = lambda("x", app(app(bound(1), bound(2)), bound(3)));
+    term.shift(2);
+
+    // λ. 1 4 5
+    assert_eq!(term, lambda("x", app(app(bound(1), bound(4)), bound(5))));
+
+    term.shift(-1);
+
+    // λ. 1 3 4
+    assert_eq!(term, lambda("x", app(app(bound(1), bound(3)), bound(4))));
+}
+
+#[test]
+fn substitute() {
+    // λ. 1 2
+    let mut term = lambda("x", app(bound(1), bound(2)));
+
+    // 1 := λ. 1 3
+    term.substitute(1, &lambda("y", app(bound(1), bound(3))));
+
+    // λ. 1 (λ. 1 4)
+    let expected =
+        lambda("x", app(bound(1), lambda("y", app(bound(1), bound(4)))));
+
+    assert_eq!(term, expected);
+}
+
+#[test]
+fn reduce_one_without_renaming() {
+    // (λx. λy. x) y
+    let mut term = DeBruijn::from_value(&compile("(\\x. \\y. x) y").unwrap());
+
+    assert!(term.reduce_one());
+    assert!(!term.reduce_one());
+
+    // λ. y
+    assert_eq!(term, lambda("y", free("y")));
+    assert_eq!(term.to_value(), compile("\\y_. y").unwrap());
+}
+
+#[test]
+fn reduce_to_normal_form() {
+    let value = compile("let mul = \\m n f. m (n f); in mul 2 3").unwrap();
+    let mut term = DeBruijn::from_value(&value);
+    while term.reduce_one() {}
+
+    assert_eq!(term, DeBruijn::from_value(&Value::church_numeral(6)));
+}
+
+#[test]
+fn reduce_same_steps_as_value() {
+    let mut value = compile("(\\x. x x) ((\\y. y) z)").unwrap();
+    let mut term = DeBruijn::from_value(&value);
+
+    loop {
+        let reduced = value.reduce_one();
+        assert_eq!(term.reduce_one(), reduced);
+        assert_eq!(term, DeBruijn::from_value(&value));
+        if !reduced {
+            break;
+        }
+    }
+}
+
+#[test]
+fn clone_and_drop_deep_term() {
+    let term = DeBruijn::from_value(&Value::church_numeral(200000));
+    let copy = term.clone();
+
+    assert_eq!(copy, term);
+    drop(term);
+    assert_eq!(copy.to_value().church_numeral_to_int(), Some(200000));
+}
@@ -0,0 +1,441 @@
+//! Exporta codificações de dados no cálculo Lambda (booleanos, pares, listas,
+//! numerais e Maybe), com funções para criar termos a partir dos dados e para
+//! ler os dados de volta a partir de termos.
+//!
+//! As leituras ignoram nomes de parâmetros: `λa. λb. a` e `λt. λf. t` são
+//! ambos lidos como o booleano verdadeiro.
+
+#[cfg(test)]
+mod test;
+
+use crate::debruijn::DeBruijn;
+use crate::value::Value;
+use std::fmt;
+
+/// Um dado lido a partir de um termo, junto da codificação usada.
+///
+/// Um mesmo termo pode ter várias leituras: `λa. λb. b`, por exemplo, é o
+/// numeral de Church 0, o booleano falso, a lista vazia, etc.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Description {
+    /// Numeral de Church: `λf. λx. f (f ... (f x))`.
+    ChurchNumeral(u32),
+    /// Booleano de Church: `λt. λf. t` ou `λt. λf. f`.
+    ChurchBoolean(bool),
+    /// Par de Church: `λp. p a b`.
+    ChurchPair(Value, Value),
+    /// Lista de Church (fold à direita): `λc. λn. c a (c b ... n)`.
+    ChurchList(Vec<Value>),
+    /// Numeral de Scott: `λs. λz. z` é 0 e `λs. λz. s n` é o sucessor de `n`.
+    ScottNumeral(u32),
+    /// Lista de Scott: `λc. λn. n` é a lista vazia e `λc. λn. c a as` é a
+    /// lista com cabeça `a` e cauda `as`.
+    ScottList(Vec<Value>),
+    /// Maybe: `λj. λn. n` é `Nothing` e `λj. λn. j a` é `Just a`.
+    Maybe(Option<Value>),
+}
+
+impl fmt::Display for Description {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Description::ChurchNumeral(number) => {
+                write!(formatter, "numeral de Church {}", number)
+            }
+            Description::ChurchBoolean(true) => {
+                write!(formatter, "booleano de Church verdadeiro")
+            }
+            Description::ChurchBoolean(false) => {
+                write!(formatter, "booleano de Church falso")
+            }
+            Description::ChurchPair(first, second) => {
+                write!(formatter, "par de Church ({}, {})", first, second)
+            }
+            Description::ChurchList(elements) => {
+                write!(formatter, "lista de Church ")?;
+                write_list(formatter, elements)
+            }
+            Description::ScottNumeral(number) => {
+                write!(formatter, "numeral de Scott {}", number)
+            }
+            Description::ScottList(elements) => {
+                write!(formatter, "lista de Scott ")?;
+                write_list(formatter, elements)
+            }
+            Description::Maybe(Some(element)) => {
+                write!(formatter, "Maybe: Just ({})", element)
+            }
+            Description::Maybe(None) => write!(formatter, "Maybe: Nothing"),
+        }
+    }
+}
+
+/// Escreve uma lista de termos entre colchetes, separados por vírgulas.
+fn write_list(
+    formatter: &mut fmt::Formatter,
+    elements: &[Value],
+) -> fmt::Result {
+    write!(formatter, "[")?;
+    for (index, element) in elements.iter().enumerate() {
+        if index > 0 {
+            write!(formatter, ", ")?;
+        }
+        write!(formatter, "{}", element)?;
+    }
+    write!(formatter, "]")
+}
+
+impl Value {
+    /// Cria um booleano de Church: `λt. λf. t` para verdadeiro e
+    /// `λt. λf. f` para falso.
+    pub fn church_boolean(value: bool) -> Self {
+        let index = if value { 2 } else { 1 };
+        two_lambdas("t", "f", DeBruijn::Bound(index)).to_value()
+    }
+
+    /// Se esse termo for um booleano de Church, converte-o para um booleano
+    /// do Rust. Se não for, retorna `None`.
+    pub fn church_boolean_to_bool(&self) -> Option<bool> {
+        match strip_two_lambdas(&DeBruijn::from_value(self))? {
+            DeBruijn::Bound(2) => Some(true),
+            DeBruijn::Bound(1) => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Cria um par de Church: `λp. p first second`.
+    pub fn church_pair(first: &Value, second: &Value) -> Self {
+        let body = application(
+            application(DeBruijn::Bound(1), DeBruijn::from_value(first)),
+            DeBruijn::from_value(second),
+        );
+        lambda("p", body).to_value()
+    }
+
+    /// Se esse termo for um par de Church, retorna seus dois elementos. Se
+    /// não for, retorna `None`.
+    pub fn church_pair_to_tuple(&self) -> Option<(Value, Value)> {
+        let term = DeBruijn::from_value(self);
+        let body = match &term {
+            DeBruijn::Lambda { hint: _, body } => body,
+            _ => None?,
+        };
+
+        match body.as_ref() {
+            DeBruijn::Application { function, argument: second } => {
+                match function.as_ref() {
+                    DeBruijn::Application { function, argument: first }
+                        if **function == DeBruijn::Bound(1)
+                            && !refers_to(first, 1)
+                            && !refers_to(second, 1) =>
+                    {
+                        Some((lower(first, 1), lower(second, 1)))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Cria uma lista de Church: `λc. λn. c a (c b ... n)`.
+    pub fn church_list(elements: &[Value]) -> Self {
+        let mut body = DeBruijn::Bound(1);
+        for element in elements.iter().rev() {
+            let head =
+                application(DeBruijn::Bound(2), DeBruijn::from_value(element));
+            body = application(head, body);
+        }
+        two_lambdas("c", "n", body).to_value()
+    }
+
+    /// Se esse termo for uma lista de Church, retorna seus elementos. Se não
+    /// for, retorna `None`.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// churchListToVec :: DeBruijn -> Maybe [Value]
+    ///
+    /// churchListToVec (Lambda _ (Lambda _ b)) =
+    ///   let elems (Bound 1) = Just []
+    ///       elems (Application (Application (Bound 2) x) r)
+    ///         | not (refersTo x 2) = fmap (lower x 2 :) (elems r)
+    ///       elems _ = Nothing
+    ///   in elems b
+    /// churchListToVec _ = Nothing
+    /// ```
+    pub fn church_list_to_vec(&self) -> Option<Vec<Value>> {
+        let term = DeBruijn::from_value(self);
+        let mut body = strip_two_lambdas(&term)?;
+        let mut elements = Vec::new();
+
+        loop {
+            match body {
+                DeBruijn::Bound(1) => break Some(elements),
+                DeBruijn::Application { function, argument: rest } => {
+                    match function.as_ref() {
+                        DeBruijn::Application {
+                            function,
+                            argument: element,
+                        } if **function == DeBruijn::Bound(2)
+                            && !refers_to(element, 2) =>
+                        {
+                            elements.push(lower(element, 2));
+                            body = rest;
+                        }
+                        _ => break None,
+                    }
+                }
+                _ => break None,
+            }
+        }
+    }
+
+    /// Cria um numeral de Scott: `λs. λz. z` para 0 e `λs. λz. s n` para o
+    /// sucessor de `n`.
+    pub fn scott_numeral(number: u32) -> Self {
+        let mut term = two_lambdas("s", "z", DeBruijn::Bound(1));
+        for _ in 0..number {
+            let body = application(DeBruijn::Bound(2), term);
+            term = two_lambdas("s", "z", body);
+        }
+        term.to_value()
+    }
+
+    /// Se esse termo for um numeral de Scott, converte-o para um inteiro do
+    /// Rust. Se não for, retorna `None`.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// scottNumToInt :: DeBruijn -> Maybe Int
+    ///
+    /// scottNumToInt (Lambda _ (Lambda _ (Bound 1))) = Just 0
+    /// scottNumToInt (Lambda _ (Lambda _ (Application (Bound 2) n)))
+    ///   | not (refersTo n 2) = fmap (+ 1) (scottNumToInt n)
+    /// scottNumToInt _ = Nothing
+    /// ```
+    pub fn scott_numeral_to_int(&self) -> Option<u32> {
+        let term = DeBruijn::from_value(self);
+        let mut current = &term;
+        let mut number = 0;
+
+        loop {
+            match strip_two_lambdas(current)? {
+                DeBruijn::Bound(1) => break Some(number),
+                DeBruijn::Application { function, argument: predecessor }
+                    if **function == DeBruijn::Bound(2)
+                        && !refers_to(predecessor, 2) =>
+                {
+                    number += 1;
+                    current = predecessor;
+                }
+                _ => break None,
+            }
+        }
+    }
+
+    /// Cria uma lista de Scott: `λc. λn. n` para a lista vazia e
+    /// `λc. λn. c a as` para a lista com cabeça `a` e cauda `as`.
+    pub fn scott_list(elements: &[Value]) -> Self {
+        let mut term = two_lambdas("c", "n", DeBruijn::Bound(1));
+        for element in elements.iter().rev() {
+            let head =
+                application(DeBruijn::Bound(2), DeBruijn::from_value(element));
+            term = two_lambdas("c", "n", application(head, term));
+        }
+        term.to_value()
+    }
+
+    /// Se esse termo for uma lista de Scott, retorna seus elementos. Se não
+    /// for, retorna `None`.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// scottListToVec :: DeBruijn -> Maybe [Value]
+    ///
+    /// scottListToVec = go 2
+    ///   where
+    ///     go _ (Lambda _ (Lambda _ (Bound 1))) = Just []
+    ///     go d (Lambda _ (Lambda _ (Application (Application (Bound 2) x) r)))
+    ///       | not (refersTo x 2 || refersTo r 2) =
+    ///           fmap (lower x d :) (go (d + 2) r)
+    ///     go _ _ = Nothing
+    /// ```
+    pub fn scott_list_to_vec(&self) -> Option<Vec<Value>> {
+        let term = DeBruijn::from_value(self);
+        let mut current = &term;
+        // Quantidade de lambdas entre a raiz e a lista atual.
+        let mut depth = 0;
+        let mut elements = Vec::new();
+
+        loop {
+            depth += 2;
+            match strip_two_lambdas(current)? {
+                DeBruijn::Bound(1) => break Some(elements),
+                DeBruijn::Application { function, argument: tail }
+                    if !refers_to(tail, 2) =>
+                {
+                    match function.as_ref() {
+                        DeBruijn::Application { function, argument: head }
+                            if **function == DeBruijn::Bound(2)
+                                && !refers_to(head, 2) =>
+                        {
+                            elements.push(lower(head, depth));
+                            current = tail;
+                        }
+                        _ => break None,
+                    }
+                }
+                _ => break None,
+            }
+        }
+    }
+
+    /// Cria um Maybe: `λj. λn. n` para `None` e `λj. λn. j a` para
+    /// `Some(a)`.
+    pub fn maybe(element: Option<&Value>) -> Self {
+        let body = match element {
+            Some(element) => {
+                application(DeBruijn::Bound(2), DeBruijn::from_value(element))
+            }
+            None => DeBruijn::Bound(1),
+        };
+        two_lambdas("j", "n", body).to_value()
+    }
+
+    /// Se esse termo for um Maybe, converte-o para um [`Option`]. Se não for,
+    /// retorna `None`.
+    pub fn maybe_to_option(&self) -> Option<Option<Value>> {
+        match strip_two_lambdas(&DeBruijn::from_value(self))? {
+            DeBruijn::Bound(1) => Some(None),
+            DeBruijn::Application { function, argument: element }
+                if **function == DeBruijn::Bound(2)
+                    && !refers_to(element, 2) =>
+            {
+                Some(Some(lower(element, 2)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Tenta ler esse termo com cada uma das codificações conhecidas,
+    /// retornando todas as leituras bem sucedidas (possivelmente nenhuma).
+    ///
+    /// # Exemplo
+    ///
+    /// ```
+    /// use lambda::compiler::compile;
+    /// use lambda::encoding::Description;
+    ///
+    /// let value = compile("\\t f. t").unwrap();
+    /// assert_eq!(value.describe(), &[Description::ChurchBoolean(true)]);
+    /// ```
+    pub fn describe(&self) -> Vec<Description> {
+        let mut descriptions = Vec::new();
+
+        if let Some(number) = self.church_numeral_to_int() {
+            descriptions.push(Description::ChurchNumeral(number));
+        }
+        if let Some(boolean) = self.church_boolean_to_bool() {
+            descriptions.push(Description::ChurchBoolean(boolean));
+        }
+        if let Some((first, second)) = self.church_pair_to_tuple() {
+            descriptions.push(Description::ChurchPair(first, second));
+        }
+        if let Some(elements) = self.church_list_to_vec() {
+            descriptions.push(Description::ChurchList(elements));
+        }
+        if let Some(number) = self.scott_numeral_to_int() {
+            descriptions.push(Description::ScottNumeral(number));
+        }
+        if let Some(elements) = self.scott_list_to_vec() {
+            descriptions.push(Description::ScottList(elements));
+        }
+        if let Some(element) = self.maybe_to_option() {
+            descriptions.push(Description::Maybe(element));
+        }
+
+        descriptions
+    }
+}
+
+/// Cria um lambda com o nome de parâmetro sugerido.
+fn lambda(hint: &str, body: DeBruijn) -> DeBruijn {
+    DeBruijn::Lambda { hint: String::from(hint), body: Box::new(body) }
+}
+
+/// Cria dois lambdas aninhados com os nomes de parâmetro sugeridos.
+fn two_lambdas(outer: &str, inner: &str, body: DeBruijn) -> DeBruijn {
+    lambda(outer, lambda(inner, body))
+}
+
+/// Cria uma aplicação.
+fn application(function: DeBruijn, argument: DeBruijn) -> DeBruijn {
+    DeBruijn::Application {
+        function: Box::new(function),
+        argument: Box::new(argument),
+    }
+}
+
+/// Se o termo começar com dois lambdas aninhados, retorna o corpo do lambda
+/// mais interno.
+fn strip_two_lambdas(term: &DeBruijn) -> Option<&DeBruijn> {
+    match term {
+        DeBruijn::Lambda { hint: _, body } => match body.as_ref() {
+            DeBruijn::Lambda { hint: _, body } => Some(body),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Testa se o termo usa algum dos `count` parâmetros mais próximos de fora
+/// dele, isto é, se tem índices livres de 1 até `count`.
+///
+/// # Algoritmo Recursivo
+///
+/// ```haskell
+/// refersTo :: DeBruijn -> Int -> Bool
+///
+/// refersTo t count = go t 0
+///   where
+///     go (Bound i) d = i > d && i - d <= count
+///     go (Free _) _ = False
+///     go (Application f a) d = go f d || go a d
+///     go (Lambda _ b) d = go b (d + 1)
+/// ```
+fn refers_to(term: &DeBruijn, count: u64) -> bool {
+    // Pilha de termos junto com o número de lambdas que os envolvem dentro
+    // do termo original.
+    let mut visit_stack = vec![(term, 0)];
+
+    while let Some((term, depth)) = visit_stack.pop() {
+        match term {
+            DeBruijn::Bound(index) => {
+                if *index > depth && *index - depth <= count {
+                    return true;
+                }
+            }
+            DeBruijn::Free(_) => (),
+            DeBruijn::Application { function, argument } => {
+                visit_stack.push((argument, depth));
+                visit_stack.push((function, depth));
+            }
+            DeBruijn::Lambda { hint: _, body } => {
+                visit_stack.push((body, depth + 1));
+            }
+        }
+    }
+
+    false
+}
+
+/// Converte um elemento que está dentro de `count` lambdas da codificação
+/// (sem usar seus parâmetros) para um termo com nomes, fora desses lambdas.
+fn lower(term: &DeBruijn, count: u64) -> Value {
+    let mut term = term.clone();
+    term.shift(-(count as i64));
+    term.to_value()
+}
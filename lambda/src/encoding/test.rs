@@ -0,0 +1,147 @@
+use super::Description;
+use crate::compiler::compile;
+use crate::debruijn::DeBruijn;
+use crate::interpreter::run_once;
+use crate::value::Value;
+
+fn var(name: &str) -> Value {
+    Value::Variable(String::from(name))
+}
+
+fn normalize(source: &str) -> Value {
+    run_once(compile(source).unwrap())
+}
+
+/// Representação sem nomes de parâmetros, para comparar termos a menos de
+/// renomeação.
+fn alpha(value: &Value) -> DeBruijn {
+    DeBruijn::from_value(value)
+}
+
+#[test]
+fn church_boolean_round_trip() {
+    assert_eq!(
+        Value::church_boolean(true).church_boolean_to_bool(),
+        Some(true)
+    );
+    assert_eq!(
+        Value::church_boolean(false).church_boolean_to_bool(),
+        Some(false)
+    );
+    assert_eq!(normalize("\\a b. a").church_boolean_to_bool(), Some(true));
+    assert_eq!(normalize("\\a b. a b").church_boolean_to_bool(), None);
+    assert_eq!(normalize("\\a. a").church_boolean_to_bool(), None);
+}
+
+#[test]
+fn church_pair_round_trip() {
+    let pair = Value::church_pair(&var("x"), &Value::church_numeral(1));
+    let (first, second) = pair.church_pair_to_tuple().unwrap();
+    assert_eq!(first, var("x"));
+    assert_eq!(alpha(&second), alpha(&Value::church_numeral(1)));
+
+    let source = "let pair = \\a b f. f a b; in pair x y";
+    assert_eq!(
+        normalize(source).church_pair_to_tuple(),
+        Some((var("x"), var("y")))
+    );
+}
+
+#[test]
+fn church_pair_avoids_capture() {
+    let pair = Value::church_pair(&var("p"), &var("q"));
+    assert_eq!(pair.church_pair_to_tuple(), Some((var("p"), var("q"))));
+    assert_eq!(pair.unbound_vars().count(), 2);
+}
+
+#[test]
+fn church_pair_rejects_parameter_use() {
+    assert_eq!(normalize("\\f. f f y").church_pair_to_tuple(), None);
+    assert_eq!(normalize("\\f. g x y").church_pair_to_tuple(), None);
+}
+
+#[test]
+fn church_list_round_trip() {
+    let elements = vec![var("a"), Value::church_numeral(2), var("b")];
+    let list = Value::church_list(&elements);
+    assert_eq!(list.church_list_to_vec(), Some(elements));
+    assert_eq!(Value::church_list(&[]).church_list_to_vec(), Some(Vec::new()));
+
+    let source = "let nil = \\c n. n; cons = \\h t c n. c h (t c n); \
+                  in cons x (cons y nil)";
+    assert_eq!(
+        normalize(source).church_list_to_vec(),
+        Some(vec![var("x"), var("y")])
+    );
+    assert_eq!(normalize("\\c n. c n n").church_list_to_vec(), None);
+}
+
+#[test]
+fn scott_numeral_round_trip() {
+    for number in 0..6 {
+        let numeral = Value::scott_numeral(number);
+        assert_eq!(numeral.scott_numeral_to_int(), Some(number));
+    }
+
+    let source = "let zero = \\s z. z; succ = \\n s z. s n; \
+                  in succ (succ (succ zero))";
+    assert_eq!(normalize(source).scott_numeral_to_int(), Some(3));
+    assert_eq!(normalize("\\s z. s s").scott_numeral_to_int(), None);
+    assert_eq!(Value::church_numeral(2).scott_numeral_to_int(), None);
+}
+
+#[test]
+fn scott_list_round_trip() {
+    let elements = vec![var("a"), Value::scott_numeral(1)];
+    let list = Value::scott_list(&elements);
+    assert_eq!(list.scott_list_to_vec(), Some(elements));
+
+    let source = "let nil = \\c n. n; cons = \\h t c n. c h t; \
+                  in cons x (cons y (cons z nil))";
+    assert_eq!(
+        normalize(source).scott_list_to_vec(),
+        Some(vec![var("x"), var("y"), var("z")])
+    );
+    assert_eq!(normalize("\\c n. c x n").scott_list_to_vec(), None);
+}
+
+#[test]
+fn maybe_round_trip() {
+    assert_eq!(Value::maybe(None).maybe_to_option(), Some(None));
+    let just = Value::maybe(Some(&var("x")));
+    assert_eq!(just.maybe_to_option(), Some(Some(var("x"))));
+    assert_eq!(normalize("\\j n. j n").maybe_to_option(), None);
+}
+
+#[test]
+fn describe_ambiguous_term() {
+    let descriptions = normalize("\\a b. b").describe();
+    assert_eq!(
+        descriptions,
+        &[
+            Description::ChurchNumeral(0),
+            Description::ChurchBoolean(false),
+            Description::ChurchList(Vec::new()),
+            Description::ScottNumeral(0),
+            Description::ScottList(Vec::new()),
+            Description::Maybe(None),
+        ]
+    );
+}
+
+#[test]
+fn describe_unknown_term() {
+    assert_eq!(normalize("\\x. x").describe(), &[]);
+}
+
+#[test]
+fn display_descriptions() {
+    let pair = Description::ChurchPair(var("x"), var("y"));
+    assert_eq!(pair.to_string(), "par de Church (x, y)");
+    let list = Description::ScottList(vec![var("a"), var("b")]);
+    assert_eq!(list.to_string(), "lista de Scott [a, b]");
+    let boolean = Description::ChurchBoolean(true);
+    assert_eq!(boolean.to_string(), "booleano de Church verdadeiro");
+    let maybe = Description::Maybe(Some(var("x")));
+    assert_eq!(maybe.to_string(), "Maybe: Just (x)");
+}
@@ -3,7 +3,11 @@
 #[cfg(test)]
 mod test;
 
-use crate::value::Value;
+use crate::encoding::Description;
+use crate::strategy::Strategy;
+use crate::value::{Direction, Value};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 /// Reduz o termo até a sua forma normal, se existir. Se não existir, entra em loop infinito.
 pub fn run_once(input: Value) -> Value {
@@ -12,7 +16,89 @@ pub fn run_once(input: Value) -> Value {
     interpreter.finish()
 }
 
-/// Dados do interpretador, tal como passos dados, entrada original e termo atual.
+/// Reduz o termo até a sua forma normal, parando antes se algum dos limites for atingido.
+/// Retorna o resultado da execução junto do termo (possivelmente parcial) alcançado.
+pub fn run_bounded(input: Value, limits: &Limits) -> (Outcome, Value) {
+    let mut interpreter = Interpreter::new(input);
+    let outcome = interpreter.run_bounded(limits);
+    (outcome, interpreter.finish())
+}
+
+/// Limites para uma execução do interpretador. Limites `None` não são verificados.
+///
+/// # Exemplo
+///
+/// ```
+/// use lambda::interpreter::Limits;
+///
+/// let limits = Limits { max_steps: Some(10000), ..Limits::default() };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Limits {
+    /// Número máximo de passos dados na execução.
+    pub max_steps: Option<u64>,
+    /// Tamanho máximo do termo atual, em número de nós (ver [`Value::size`]).
+    pub max_size: Option<usize>,
+    /// Tempo máximo da execução.
+    ///
+    /// Usa [`Instant`], que não está disponível em `wasm32-unknown-unknown`: nesse alvo, use somente os outros limites.
+    pub timeout: Option<Duration>,
+}
+
+/// Resultado de uma execução limitada do interpretador.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Outcome {
+    /// O termo chegou à forma normal (da estratégia de avaliação usada).
+    NormalForm,
+    /// O limite de passos foi atingido antes da forma normal.
+    StepsExhausted,
+    /// O termo ficou maior que o tamanho máximo.
+    SizeExceeded,
+    /// O tempo máximo se esgotou antes da forma normal.
+    TimedOut,
+    /// O termo atual é beta-equivalente a um termo visto `period` passos
+    /// antes, então a redução se repete para sempre. Só ocorre com a detecção
+    /// de ciclos ativada.
+    Cycle { period: u64 },
+}
+
+/// Registro de um passo de redução.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /// Caminho da raiz do termo (antes do passo) até o redex contraído.
+    pub path: Vec<Direction>,
+    /// O redex, antes de ser contraído.
+    pub redex: Value,
+    /// O termo inteiro depois do passo.
+    pub output: Value,
+}
+
+/// Histórico dos termos já vistos pelo interpretador, usado para detectar ciclos.
+#[derive(Debug, Clone, Default)]
+struct History {
+    /// Termos vistos, junto do passo em que foram vistos, agrupados pelo hash que ignora nomes de parâmetros.
+    seen: HashMap<u64, Vec<(u64, Value)>>,
+    /// Período do primeiro ciclo detectado.
+    period: Option<u64>,
+}
+
+impl History {
+    /// Registra o termo alcançado no passo dado, detectando se um termo beta-equivalente já foi visto.
+    fn record(&mut self, step: u64, value: &Value) {
+        let entries = self.seen.entry(value.nameless_hash()).or_default();
+
+        match entries.iter().find(|(_, seen)| seen.beta_equiv(value)) {
+            Some((seen_step, _)) => {
+                if self.period.is_none() {
+                    self.period = Some(step - seen_step);
+                }
+            }
+            None => entries.push((step, value.clone())),
+        }
+    }
+}
+
+/// Dados do interpretador, tal como passos dados, entrada original, termo atual e estratégia de avaliação.
 #[derive(Debug, Clone)]
 pub struct Interpreter {
     /// Passos dados desde o início da interpretação.
@@ -21,18 +107,38 @@ pub struct Interpreter {
     input: Value,
     /// Termo atual.
     current: Value,
+    /// Estratégia usada para escolher o redex de cada passo.
+    strategy: Strategy,
+    /// Termos já vistos, somente se a detecção de ciclos estiver ativada.
+    history: Option<History>,
 }
 
 impl Interpreter {
     /// Cria um interpretador a partir do termo de entrada, com passos zerados.
+    /// Usa a estratégia de ordem normal.
     pub fn new(input: Value) -> Self {
-        Self { current: input.clone(), input, steps: 0 }
+        Self::with_strategy(input, Strategy::default())
+    }
+
+    /// Cria um interpretador a partir do termo de entrada e da estratégia de avaliação, com passos zerados.
+    pub fn with_strategy(input: Value, strategy: Strategy) -> Self {
+        Self {
+            current: input.clone(),
+            input,
+            steps: 0,
+            strategy,
+            history: None,
+        }
     }
 
     /// Reseta o status do interpretador para o início.
     pub fn reset(&mut self) {
         self.steps = 0;
         self.current = self.input.clone();
+        if self.history.is_some() {
+            self.history = Some(History::default());
+            self.record_current();
+        }
     }
 
     /// Altera a entrada original, resetando o status do interpretador.
@@ -41,23 +147,87 @@ impl Interpreter {
         self.reset();
     }
 
-    /// Roda um passo da redução.
+    /// Altera a estratégia de avaliação usada nos próximos passos, sem resetar o interpretador.
+    pub fn set_strategy(&mut self, strategy: Strategy) {
+        self.strategy = strategy;
+    }
+
+    /// Retorna a estratégia de avaliação usada pelo interpretador.
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Ativa ou desativa a detecção de ciclos.
+    /// Com ela ativada, todo termo alcançado é guardado, e o interpretador detecta quando o termo atual é beta-equivalente a um termo anterior.
+    pub fn set_cycle_detection(&mut self, enabled: bool) {
+        if !enabled {
+            self.history = None;
+        } else if self.history.is_none() {
+            self.history = Some(History::default());
+            self.record_current();
+        }
+    }
+
+    /// Retorna o período do ciclo detectado, isto é, quantos passos atrás estava o termo beta-equivalente ao atual.
+    /// Retorna `None` se nenhum ciclo foi detectado ou se a detecção de ciclos estiver desativada.
+    pub fn cycle_period(&self) -> Option<u64> {
+        self.history.as_ref().and_then(|history| history.period)
+    }
+
+    /// Registra o termo atual no histórico, se a detecção de ciclos estiver ativada.
+    fn record_current(&mut self) {
+        if let Some(history) = &mut self.history {
+            history.record(self.steps, &self.current);
+        }
+    }
+
+    /// Roda um passo da redução, usando a estratégia de avaliação atual.
     /// Retorna `true` se houve redução.
     pub fn run_step(&mut self) -> bool {
-        if self.current.reduce_one() {
+        if self.current.reduce_one_with(self.strategy) {
             self.steps += 1;
+            self.record_current();
             true
         } else {
             false
         }
     }
 
+    /// Roda um passo da redução, registrando qual redex foi contraído.
+    /// Retorna `None` se não houve redução.
+    pub fn run_step_traced(&mut self) -> Option<Step> {
+        let path = self.current.find_redex(self.strategy)?;
+        let redex = self.current.subterm(&path)?.clone();
+
+        if self.current.reduce_at(&path) {
+            self.steps += 1;
+            self.record_current();
+            Some(Step { path, redex, output: self.current.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Roda um determinado número de passos de redução, registrando cada passo.
+    /// Retorna a derivação com os passos executados, que pode ter menos passos que o limite se não houver mais reduções.
+    pub fn run_steps_traced(&mut self, max_steps: u32) -> Vec<Step> {
+        let mut derivation = Vec::new();
+        for _ in 0..max_steps {
+            match self.run_step_traced() {
+                Some(step) => derivation.push(step),
+                None => break,
+            }
+        }
+        derivation
+    }
+
     /// Roda um determinado número de passos de redução.
     /// Retorna `true` se todos os passos indicados foram executados.
     /// Retorna `false` se todas as reduções possíveis foram feitas antes de chegar no limite de passos.
+    /// Se a detecção de ciclos estiver ativada, também para e retorna `false` ao detectar um ciclo.
     pub fn run_steps(&mut self, max_steps: u32) -> bool {
         for _ in 0..max_steps {
-            if !self.run_step() {
+            if self.cycle_period().is_some() || !self.run_step() {
                 return false;
             }
         }
@@ -65,9 +235,47 @@ impl Interpreter {
     }
 
     /// Tenta realizar todas as reduções possíveis, até a forma normal.
-    /// Se não houver forma normal, entra em loop infinito.
+    /// Se a detecção de ciclos estiver ativada, para ao detectar um ciclo.
+    /// Se não houver forma normal nem ciclo, entra em loop infinito.
     pub fn run_all(&mut self) {
-        while self.run_step() {}
+        while self.run_step() && self.cycle_period().is_none() {}
+    }
+
+    /// Tenta realizar todas as reduções possíveis, até a forma normal, parando antes se algum dos limites for atingido.
+    /// O limite de passos conta somente os passos dados nesta chamada.
+    /// O termo alcançado continua disponível em [`Interpreter::output`].
+    pub fn run_bounded(&mut self, limits: &Limits) -> Outcome {
+        // Só consulta o relógio se houver limite de tempo.
+        let deadline = limits.timeout.map(|timeout| Instant::now() + timeout);
+        let mut steps = 0;
+
+        loop {
+            if limits.max_size.is_some_and(|max| self.current.size() > max) {
+                break Outcome::SizeExceeded;
+            }
+
+            if limits.max_steps.is_some_and(|max| steps >= max) {
+                // O último passo permitido pode ter levado à forma normal.
+                break match self.current.find_redex(self.strategy) {
+                    Some(_) => Outcome::StepsExhausted,
+                    None => Outcome::NormalForm,
+                };
+            }
+
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break Outcome::TimedOut;
+            }
+
+            if !self.run_step() {
+                break Outcome::NormalForm;
+            }
+
+            steps += 1;
+
+            if let Some(period) = self.cycle_period() {
+                break Outcome::Cycle { period };
+            }
+        }
     }
 
     /// Retorna quantos passos foram dados.
@@ -85,6 +293,12 @@ impl Interpreter {
         &self.current
     }
 
+    /// Tenta ler a saída atual com cada codificação conhecida de dados (veja
+    /// [`Value::describe`]).
+    pub fn describe_output(&self) -> Vec<Description> {
+        self.current.describe()
+    }
+
     /// Consome o interpretador e retorna a saída final.
     pub fn finish(self) -> Value {
         self.current
@@ -1,7 +1,16 @@
+use super::run_bounded;
 use super::run_once;
 use super::Interpreter;
+use super::Limits;
+use super::Outcome;
+use super::Step;
+use crate::compiler::compile;
+use crate::encoding::Description;
+use crate::strategy::Strategy;
+use crate::value::Direction;
 use crate::value::NestedValue;
 use crate::value::Value;
+use std::time::Duration;
 
 #[test]
 fn capture() {
@@ -157,3 +166,220 @@ fn steps() {
     assert!(interpreter.output().beta_equiv(&output_value));
     assert_eq!(interpreter.steps(), 4);
 }
+
+#[test]
+fn steps_by_strategy() {
+    let input_value = compile("(\\x. x x) ((\\y. y) z)").unwrap();
+    let normal_form = compile("z z").unwrap();
+    let head_normal_form = compile("z ((\\y. y) z)").unwrap();
+
+    let expected = [
+        (Strategy::NormalOrder, 3, &normal_form),
+        (Strategy::ApplicativeOrder, 2, &normal_form),
+        (Strategy::CallByName, 2, &head_normal_form),
+        (Strategy::CallByValue, 2, &normal_form),
+        (Strategy::HeadReduction, 2, &head_normal_form),
+    ];
+
+    for &(strategy, steps, output_value) in &expected {
+        let mut interpreter =
+            Interpreter::with_strategy(input_value.clone(), strategy);
+        interpreter.run_all();
+        assert_eq!(interpreter.output(), output_value, "{:?}", strategy);
+        assert_eq!(interpreter.steps(), steps, "{:?}", strategy);
+    }
+}
+
+#[test]
+fn strategies_under_lambda() {
+    let input_value = compile("\\a. (\\x. x) a").unwrap();
+
+    let expected = [
+        (Strategy::NormalOrder, 1),
+        (Strategy::ApplicativeOrder, 1),
+        (Strategy::CallByName, 0),
+        (Strategy::CallByValue, 0),
+        (Strategy::HeadReduction, 1),
+    ];
+
+    for &(strategy, steps) in &expected {
+        let mut interpreter =
+            Interpreter::with_strategy(input_value.clone(), strategy);
+        interpreter.run_all();
+        assert_eq!(interpreter.steps(), steps, "{:?}", strategy);
+    }
+}
+
+#[test]
+fn applicative_order_diverges() {
+    // (λx. λy. y) ((λx. x x) (λx. x x))
+    let input_value = compile("(\\x. \\y. y) ((\\x. x x) (\\x. x x))").unwrap();
+
+    let mut interpreter =
+        Interpreter::with_strategy(input_value.clone(), Strategy::NormalOrder);
+    assert!(!interpreter.run_steps(10));
+    assert_eq!(interpreter.steps(), 1);
+
+    interpreter.set_input(input_value);
+    interpreter.set_strategy(Strategy::ApplicativeOrder);
+    assert!(interpreter.run_steps(10));
+    assert_eq!(interpreter.steps(), 10);
+}
+
+#[test]
+fn trace_normal_order() {
+    let input_value = compile("(\\x. x x) ((\\y. y) z)").unwrap();
+    let redex = compile("(\\y. y) z").unwrap();
+
+    let mut interpreter = Interpreter::new(input_value.clone());
+    let derivation = interpreter.run_steps_traced(10);
+
+    assert_eq!(
+        derivation,
+        &[
+            Step {
+                path: Vec::new(),
+                redex: input_value,
+                output: compile("(\\y. y) z ((\\y. y) z)").unwrap(),
+            },
+            Step {
+                path: vec![Direction::Function],
+                redex: redex.clone(),
+                output: compile("z ((\\y. y) z)").unwrap(),
+            },
+            Step {
+                path: vec![Direction::Argument],
+                redex,
+                output: compile("z z").unwrap(),
+            },
+        ]
+    );
+    assert_eq!(interpreter.steps(), 3);
+    assert_eq!(interpreter.run_step_traced(), None);
+}
+
+#[test]
+fn trace_applicative_order() {
+    let input_value = compile("(\\x. x x) ((\\y. y) z)").unwrap();
+
+    let mut interpreter =
+        Interpreter::with_strategy(input_value, Strategy::ApplicativeOrder);
+    let step = interpreter.run_step_traced().unwrap();
+
+    assert_eq!(step.path, &[Direction::Argument]);
+    assert_eq!(step.redex, compile("(\\y. y) z").unwrap());
+    assert_eq!(step.output, compile("(\\x. x x) z").unwrap());
+}
+
+#[test]
+fn bounded_by_steps() {
+    // (λx. x x) (λx. x x)
+    let input_value = compile("(\\x. x x) (\\x. x x)").unwrap();
+    let limits = Limits { max_steps: Some(100), ..Limits::default() };
+
+    let mut interpreter = Interpreter::new(input_value.clone());
+    assert_eq!(interpreter.run_bounded(&limits), Outcome::StepsExhausted);
+    assert_eq!(interpreter.steps(), 100);
+    assert_eq!(interpreter.output(), &input_value);
+}
+
+#[test]
+fn bounded_normal_form_at_last_step() {
+    let input_value = compile("(\\x. x) y").unwrap();
+    let limits = Limits { max_steps: Some(1), ..Limits::default() };
+
+    let (outcome, output_value) = run_bounded(input_value, &limits);
+    assert_eq!(outcome, Outcome::NormalForm);
+    assert_eq!(output_value, Value::Variable(String::from("y")));
+}
+
+#[test]
+fn bounded_by_size() {
+    // (λx. x x x) (λx. x x x)
+    let input_value = compile("(\\x. x x x) (\\x. x x x)").unwrap();
+    let limits = Limits {
+        max_steps: Some(1000),
+        max_size: Some(50),
+        ..Limits::default()
+    };
+
+    let mut interpreter = Interpreter::new(input_value);
+    assert_eq!(interpreter.run_bounded(&limits), Outcome::SizeExceeded);
+    assert!(interpreter.output().size() > 50);
+}
+
+#[test]
+fn bounded_by_time() {
+    // (λx. x x) (λx. x x)
+    let input_value = compile("(\\x. x x) (\\x. x x)").unwrap();
+    let limits = Limits {
+        timeout: Some(Duration::from_millis(10)),
+        ..Limits::default()
+    };
+
+    let (outcome, _) = run_bounded(input_value, &limits);
+    assert_eq!(outcome, Outcome::TimedOut);
+}
+
+#[test]
+fn detect_cycle_period_one() {
+    // (λx. x x) (λx. x x)
+    let input_value = compile("(\\x. x x) (\\x. x x)").unwrap();
+    let limits = Limits { max_steps: Some(100), ..Limits::default() };
+
+    let mut interpreter = Interpreter::new(input_value);
+    interpreter.set_cycle_detection(true);
+    assert_eq!(interpreter.run_bounded(&limits), Outcome::Cycle { period: 1 });
+    assert_eq!(interpreter.steps(), 1);
+}
+
+#[test]
+fn detect_cycle_period_two() {
+    // S S S, com S = λx. λy. y x y
+    // S S S => (λy. y S y) S => S S S
+    let input_value = compile("let s = \\x y. y x y; in s s s").unwrap();
+
+    let mut interpreter = Interpreter::new(input_value);
+    interpreter.set_cycle_detection(true);
+    interpreter.run_all();
+    assert_eq!(interpreter.cycle_period(), Some(2));
+    assert_eq!(interpreter.steps(), 2);
+
+    interpreter.reset();
+    assert_eq!(interpreter.cycle_period(), None);
+}
+
+#[test]
+fn run_steps_stops_at_cycle() {
+    // (λx. x x) (λx. x x)
+    let input_value = compile("(\\x. x x) (\\x. x x)").unwrap();
+
+    let mut interpreter = Interpreter::new(input_value);
+    interpreter.set_cycle_detection(true);
+    assert!(!interpreter.run_steps(10));
+    assert_eq!(interpreter.cycle_period(), Some(1));
+    assert_eq!(interpreter.steps(), 1);
+}
+
+#[test]
+fn no_cycle_without_detection() {
+    // (λx. x x) (λx. x x)
+    let input_value = compile("(\\x. x x) (\\x. x x)").unwrap();
+    let limits = Limits { max_steps: Some(100), ..Limits::default() };
+
+    let mut interpreter = Interpreter::new(input_value);
+    assert_eq!(interpreter.run_bounded(&limits), Outcome::StepsExhausted);
+    assert_eq!(interpreter.cycle_period(), None);
+}
+
+#[test]
+fn describe_output_pair_of_numerals() {
+    let source =
+        "let pair = \\a b p. p a b; two = \\f x. f (f x); in pair two z";
+    let mut interpreter = Interpreter::new(compile(source).unwrap());
+    interpreter.run_all();
+    let two = Value::church_numeral(2);
+    let z = Value::Variable(String::from("z"));
+    let descriptions = interpreter.describe_output();
+    assert_eq!(descriptions, &[Description::ChurchPair(two, z)]);
+}
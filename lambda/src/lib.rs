@@ -1,3 +1,8 @@
 pub mod value;
 pub mod compiler;
 pub mod interpreter;
+pub mod strategy;
+pub mod debruijn;
+pub mod machine;
+pub mod net;
+pub mod encoding;
@@ -0,0 +1,423 @@
+//! Exporta avaliadores baseados em máquinas abstratas com ambientes: a
+//! máquina de Krivine (chamada por nome) e a máquina CEK (chamada por valor).
+//!
+//! Diferente de [`Value::reduce_one`], essas máquinas não procuram o redex a
+//! partir da raiz a cada passo, nem copiam o argumento para cada ocorrência
+//! do parâmetro: o argumento é guardado em um ambiente, junto ao corpo do
+//! lambda, e só é consultado quando a variável é usada.
+
+#[cfg(test)]
+mod test;
+
+pub mod lazy;
+
+use crate::debruijn::DeBruijn;
+use crate::value::Value;
+use std::mem;
+use std::rc::Rc;
+
+/// Resultado da avaliação de um termo em uma máquina abstrata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation {
+    /// Forma normal do termo de entrada.
+    pub output: Value,
+    /// Número de beta-reduções feitas pela máquina.
+    pub steps: u64,
+}
+
+/// Reduz o termo até a sua forma normal usando a máquina de Krivine
+/// (chamada por nome). A forma normal fraca de cabeça é obtida pela máquina;
+/// em seguida, o corpo de lambdas e os argumentos de variáveis são
+/// normalizados da mesma forma. Chega à mesma forma normal que a ordem normal
+/// (ver [`run_once`](crate::interpreter::run_once)), e entra em loop infinito
+/// se ela não existir.
+pub fn run_krivine(input: &Value) -> Evaluation {
+    Machine::new(Mode::CallByName).run(input)
+}
+
+/// Reduz o termo até a sua forma normal usando a máquina CEK (chamada por
+/// valor). Argumentos são avaliados antes de serem passados para a função, e
+/// o corpo de lambdas só é normalizado no final. Pode entrar em loop infinito
+/// mesmo se a forma normal existir, como em `(λx. y) ((λx. x x) (λx. x x))`.
+pub fn run_cek(input: &Value) -> Evaluation {
+    Machine::new(Mode::CallByValue).run(input)
+}
+
+/// Modo de avaliação da máquina.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Máquina de Krivine.
+    CallByName,
+    /// Máquina CEK.
+    CallByValue,
+}
+
+/// Cabeça de um termo neutro, isto é, um termo que não pode mais ser
+/// reduzido até que a variável da cabeça seja conhecida.
+#[derive(Debug, Clone)]
+enum Head<'term> {
+    /// Parâmetro de um lambda sendo normalizado, identificado pelo nível de
+    /// De Bruijn (número de lambdas entre a raiz e o lambda do parâmetro).
+    Level(u64),
+    /// Variável livre do termo de entrada.
+    Free(&'term str),
+}
+
+/// Entrada de um ambiente, ou resultado de uma avaliação.
+#[derive(Debug, Clone)]
+enum Entry<'term> {
+    /// Um termo junto do ambiente de suas variáveis ligadas. Na máquina de
+    /// Krivine, o termo pode ser qualquer um (ainda não avaliado); na
+    /// máquina CEK, e como resultado de avaliações, é sempre um lambda.
+    Closure { term: &'term DeBruijn, env: Env<'term> },
+    /// Um termo neutro, compartilhado entre as cópias da entrada.
+    Neutral(Rc<Neutral<'term>>),
+}
+
+impl<'term> Entry<'term> {
+    /// Cria uma entrada com o termo neutro dado.
+    fn neutral(head: Head<'term>, spine: Vec<Entry<'term>>) -> Self {
+        Entry::Neutral(Rc::new(Neutral { head, spine }))
+    }
+}
+
+/// Uma variável não conhecida aplicada a argumentos (em ordem).
+#[derive(Debug, Clone)]
+struct Neutral<'term> {
+    head: Head<'term>,
+    spine: Vec<Entry<'term>>,
+}
+
+/// Ambiente: lista encadeada e compartilhada de entradas. A primeira entrada
+/// corresponde ao índice de De Bruijn 1.
+#[derive(Debug, Clone, Default)]
+struct Env<'term> {
+    node: Option<Rc<EnvNode<'term>>>,
+}
+
+/// Nodo da lista encadeada de um ambiente.
+#[derive(Debug)]
+struct EnvNode<'term> {
+    entry: Entry<'term>,
+    next: Env<'term>,
+}
+
+impl<'term> Env<'term> {
+    /// Cria um novo ambiente com a entrada dada no índice 1, seguida das
+    /// entradas deste ambiente.
+    fn push(&self, entry: Entry<'term>) -> Self {
+        let node = EnvNode { entry, next: self.clone() };
+        Self { node: Some(Rc::new(node)) }
+    }
+
+    /// Busca a entrada do índice de De Bruijn dado.
+    fn get(&self, index: u64) -> Option<&Entry<'term>> {
+        let mut node = self.node.as_ref()?;
+        for _ in 1..index {
+            node = node.next.node.as_ref()?;
+        }
+        Some(&node.entry)
+    }
+}
+
+/// Solta entradas e nodos de ambientes sem recursão. Ambientes e termos
+/// neutros podem se encadear em dezenas de milhares de níveis (por exemplo,
+/// ao normalizar um numeral grande), então o drop padrão estouraria a pilha.
+/// Somente o que não é compartilhado é desmontado.
+fn release<'term>(
+    mut entries: Vec<Entry<'term>>,
+    mut nodes: Vec<Rc<EnvNode<'term>>>,
+) {
+    loop {
+        if let Some(node) = nodes.pop() {
+            if let Ok(EnvNode { entry, mut next }) = Rc::try_unwrap(node) {
+                nodes.extend(next.node.take());
+                entries.push(entry);
+            }
+        } else if let Some(entry) = entries.pop() {
+            match entry {
+                Entry::Closure { term: _, mut env } => {
+                    nodes.extend(env.node.take());
+                }
+                Entry::Neutral(neutral) => {
+                    if let Ok(mut neutral) = Rc::try_unwrap(neutral) {
+                        entries.append(&mut neutral.spine);
+                    }
+                }
+            }
+        } else {
+            break;
+        }
+    }
+}
+
+impl Drop for Env<'_> {
+    fn drop(&mut self) {
+        if let Some(node) = self.node.take() {
+            release(Vec::new(), vec![node]);
+        }
+    }
+}
+
+impl Drop for Neutral<'_> {
+    fn drop(&mut self) {
+        release(mem::take(&mut self.spine), Vec::new());
+    }
+}
+
+/// Continuação pendente da máquina CEK.
+enum Frame<'term> {
+    /// Avaliar o argumento de uma aplicação, cuja função está sendo avaliada.
+    Argument { term: &'term DeBruijn, env: Env<'term> },
+    /// Aplicar a função já avaliada ao argumento sendo avaliado.
+    Call(Entry<'term>),
+}
+
+/// Estado de uma máquina abstrata.
+#[derive(Debug)]
+struct Machine {
+    /// Modo de avaliação.
+    mode: Mode,
+    /// Beta-reduções feitas até agora.
+    steps: u64,
+}
+
+impl Machine {
+    /// Cria uma máquina no modo dado, com passos zerados.
+    fn new(mode: Mode) -> Self {
+        Self { mode, steps: 0 }
+    }
+
+    /// Normaliza o termo de entrada.
+    fn run(mut self, input: &Value) -> Evaluation {
+        let term = DeBruijn::from_value(input);
+        let entry = Entry::Closure { term: &term, env: Env::default() };
+        let output = self.normalize(entry).to_value();
+        Evaluation { output, steps: self.steps }
+    }
+
+    /// Avalia uma entrada até um lambda ou um termo neutro.
+    fn eval<'term>(&mut self, entry: Entry<'term>) -> Entry<'term> {
+        match entry {
+            Entry::Closure { term, env } => match self.mode {
+                Mode::CallByName => self.eval_by_name(term, env),
+                Mode::CallByValue => self.eval_by_value(term, env),
+            },
+            neutral => neutral,
+        }
+    }
+
+    /// Avalia um termo até a forma normal fraca de cabeça com a máquina de
+    /// Krivine. Argumentos são empilhados sem serem avaliados.
+    fn eval_by_name<'term>(
+        &mut self,
+        mut term: &'term DeBruijn,
+        mut env: Env<'term>,
+    ) -> Entry<'term> {
+        // Argumentos pendentes; o topo é o próximo a ser aplicado.
+        let mut stack: Vec<Entry<'term>> = Vec::new();
+
+        loop {
+            match term {
+                DeBruijn::Application { function, argument } => {
+                    stack.push(Entry::Closure {
+                        term: argument.as_ref(),
+                        env: env.clone(),
+                    });
+                    term = function.as_ref();
+                }
+
+                DeBruijn::Lambda { hint: _, body } => match stack.pop() {
+                    Some(argument) => {
+                        self.steps += 1;
+                        env = env.push(argument);
+                        term = body.as_ref();
+                    }
+                    None => break Entry::Closure { term, env },
+                },
+
+                DeBruijn::Bound(index) => {
+                    let entry = env.get(*index).expect("índice ligado").clone();
+                    match entry {
+                        Entry::Closure { term: entry_term, env: entry_env } => {
+                            term = entry_term;
+                            env = entry_env;
+                        }
+                        Entry::Neutral(neutral) => {
+                            let mut spine = neutral.spine.clone();
+                            spine.extend(stack.drain(..).rev());
+                            break Entry::neutral(neutral.head.clone(), spine);
+                        }
+                    }
+                }
+
+                DeBruijn::Free(name) => {
+                    stack.reverse();
+                    let head = Head::Free(name);
+                    break Entry::neutral(head, stack);
+                }
+            }
+        }
+    }
+
+    /// Avalia um termo até um valor (lambda ou termo neutro) com a máquina
+    /// CEK. Argumentos são avaliados antes de serem guardados no ambiente.
+    fn eval_by_value<'term>(
+        &mut self,
+        mut term: &'term DeBruijn,
+        mut env: Env<'term>,
+    ) -> Entry<'term> {
+        let mut frames: Vec<Frame<'term>> = Vec::new();
+
+        loop {
+            // Avalia o termo atual até obter um valor.
+            let mut value = match term {
+                DeBruijn::Application { function, argument } => {
+                    frames.push(Frame::Argument {
+                        term: argument.as_ref(),
+                        env: env.clone(),
+                    });
+                    term = function.as_ref();
+                    continue;
+                }
+                DeBruijn::Lambda { .. } => {
+                    Entry::Closure { term, env: env.clone() }
+                }
+                DeBruijn::Bound(index) => {
+                    env.get(*index).expect("índice ligado").clone()
+                }
+                DeBruijn::Free(name) => {
+                    Entry::neutral(Head::Free(name), Vec::new())
+                }
+            };
+
+            // Entrega o valor para as continuações, até alguma precisar
+            // avaliar outro termo.
+            loop {
+                match frames.pop() {
+                    None => return value,
+
+                    Some(Frame::Argument { term: argument, env: arg_env }) => {
+                        frames.push(Frame::Call(value));
+                        term = argument;
+                        env = arg_env;
+                        break;
+                    }
+
+                    Some(Frame::Call(function)) => match function {
+                        Entry::Closure {
+                            term: DeBruijn::Lambda { hint: _, body },
+                            env: closure_env,
+                        } => {
+                            self.steps += 1;
+                            env = closure_env.push(value);
+                            term = body.as_ref();
+                            break;
+                        }
+                        Entry::Closure { .. } => {
+                            unreachable!("valores da máquina CEK são lambdas")
+                        }
+                        Entry::Neutral(mut neutral) => {
+                            Rc::make_mut(&mut neutral).spine.push(value);
+                            value = Entry::Neutral(neutral);
+                        }
+                    },
+                }
+            }
+        }
+    }
+
+    /// Normaliza uma entrada: avalia-a e, então, normaliza o corpo de lambdas
+    /// (com o parâmetro como termo neutro) e os argumentos de termos neutros.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// normalize :: Int -> Entry -> DeBruijn
+    /// normalize d e = case eval e of
+    ///   Closure (Lambda h b) env ->
+    ///     Lambda h (normalize (d + 1) (Closure b (Neutral (Level d) [] : env)))
+    ///   Neutral h as ->
+    ///     foldl Application (readHead d h) (map (normalize d) as)
+    ///
+    /// readHead :: Int -> Head -> DeBruijn
+    /// readHead d (Level l) = Bound (d - l)
+    /// readHead d (Free s) = Free s
+    /// ```
+    fn normalize(&mut self, entry: Entry) -> DeBruijn {
+        /// Uma operação auxiliar da normalização.
+        enum Operation<'term> {
+            /// Normaliza uma entrada, dada a quantidade de lambdas envolvendo
+            /// a entrada no resultado.
+            Normalize(Entry<'term>, u64),
+            /// Finaliza a normalização do corpo de um lambda.
+            MakeLambda(&'term str),
+            /// Finaliza a normalização do argumento de uma aplicação.
+            MakeApplication,
+        }
+
+        let mut operation_stack = vec![Operation::Normalize(entry, 0)];
+        let mut output_stack: Vec<DeBruijn> = Vec::new();
+
+        while let Some(operation) = operation_stack.pop() {
+            match operation {
+                Operation::Normalize(entry, depth) => match self.eval(entry) {
+                    Entry::Closure {
+                        term: DeBruijn::Lambda { hint, body },
+                        env,
+                    } => {
+                        let parameter =
+                            Entry::neutral(Head::Level(depth), Vec::new());
+                        let body = Entry::Closure {
+                            term: body.as_ref(),
+                            env: env.push(parameter),
+                        };
+                        operation_stack.push(Operation::MakeLambda(hint));
+                        operation_stack
+                            .push(Operation::Normalize(body, depth + 1));
+                    }
+
+                    Entry::Closure { .. } => {
+                        unreachable!("avaliação termina em lambdas")
+                    }
+
+                    Entry::Neutral(neutral) => {
+                        let head = match neutral.head {
+                            Head::Level(level) => {
+                                DeBruijn::Bound(depth - level)
+                            }
+                            Head::Free(name) => DeBruijn::Free(name.to_owned()),
+                        };
+                        output_stack.push(head);
+
+                        // .pop() retorna elementos na ordem inversa do .push()
+                        for argument in neutral.spine.iter().rev().cloned() {
+                            operation_stack.push(Operation::MakeApplication);
+                            operation_stack
+                                .push(Operation::Normalize(argument, depth));
+                        }
+                    }
+                },
+
+                Operation::MakeLambda(hint) => {
+                    let body = output_stack.pop().expect("normal body");
+                    output_stack.push(DeBruijn::Lambda {
+                        hint: hint.to_owned(),
+                        body: Box::new(body),
+                    });
+                }
+
+                Operation::MakeApplication => {
+                    let argument = output_stack.pop().expect("normal argument");
+                    let function = output_stack.pop().expect("normal function");
+                    output_stack.push(DeBruijn::Application {
+                        function: Box::new(function),
+                        argument: Box::new(argument),
+                    });
+                }
+            }
+        }
+
+        output_stack.pop().expect("normal")
+    }
+}
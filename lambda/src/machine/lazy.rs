@@ -0,0 +1,449 @@
+//! Exporta um avaliador com compartilhamento (chamada por necessidade),
+//! baseado na máquina de Krivine com atualização de argumentos.
+//!
+//! Cada argumento vira um nodo compartilhado (thunk) entre todas as
+//! ocorrências do parâmetro. Na primeira vez em que o nodo é usado, ele é
+//! avaliado e sobrescrito com o resultado, então as outras ocorrências não
+//! repetem o trabalho.
+
+#[cfg(test)]
+mod test;
+
+use super::Head;
+use crate::debruijn::DeBruijn;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+
+/// Resultado da avaliação de um termo com compartilhamento.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LazyEvaluation {
+    /// Forma normal do termo de entrada.
+    pub output: Value,
+    /// Número de beta-reduções feitas de fato, compartilhando argumentos.
+    pub shared_steps: u64,
+    /// Número de beta-reduções que a ordem normal (sem compartilhamento)
+    /// faria para chegar na mesma forma normal. Igual a
+    /// [`Interpreter::steps`](crate::interpreter::Interpreter::steps) depois
+    /// de [`run_all`](crate::interpreter::Interpreter::run_all).
+    pub normal_order_steps: u64,
+}
+
+/// Reduz o termo até a sua forma normal usando chamada por necessidade:
+/// argumentos só são avaliados quando necessário, e no máximo uma vez. Chega
+/// à mesma forma normal que a ordem normal, e entra em loop infinito se ela
+/// não existir.
+///
+/// Os passos da ordem normal são calculados sem refazer o trabalho: cada
+/// argumento avaliado guarda quantos passos a ordem normal gastaria para
+/// avaliá-lo, e esse custo é somado toda vez que o resultado é reaproveitado.
+///
+/// # Exemplo
+///
+/// ```
+/// use lambda::compiler::compile;
+/// use lambda::machine::lazy::run_lazy;
+///
+/// let input = compile("(\\x. x x x) ((\\y. y) z)").unwrap();
+/// let evaluation = run_lazy(&input);
+///
+/// assert_eq!(evaluation.shared_steps, 2);
+/// assert_eq!(evaluation.normal_order_steps, 4);
+/// ```
+pub fn run_lazy(input: &Value) -> LazyEvaluation {
+    let term = DeBruijn::from_value(input);
+    let mut machine = Machine { shared_steps: 0, normal_order_steps: 0 };
+    let output = machine.normalize(&term).to_value();
+
+    LazyEvaluation {
+        output,
+        shared_steps: machine.shared_steps,
+        normal_order_steps: machine.normal_order_steps,
+    }
+}
+
+/// Resultado da avaliação de um nodo: forma normal fraca de cabeça.
+#[derive(Debug, Clone)]
+enum Whnf<'term> {
+    /// Um lambda junto do ambiente de suas variáveis livres.
+    Closure { term: &'term DeBruijn, env: Env<'term> },
+    /// Uma variável não conhecida aplicada a argumentos (em ordem).
+    Neutral { head: Head<'term>, spine: Vec<ThunkRef<'term>> },
+}
+
+/// Estado de um nodo compartilhado.
+#[derive(Debug)]
+enum Thunk<'term> {
+    /// Ainda não avaliado: um termo junto do ambiente de suas variáveis.
+    Delayed { term: &'term DeBruijn, env: Env<'term> },
+    /// Sendo avaliado agora.
+    Evaluating,
+    /// Já avaliado, junto de quantos passos a ordem normal gastaria na
+    /// avaliação.
+    Done { value: Whnf<'term>, cost: u64 },
+}
+
+/// Referência compartilhada para um nodo.
+#[derive(Debug, Clone)]
+struct ThunkRef<'term>(Rc<RefCell<Thunk<'term>>>);
+
+impl<'term> ThunkRef<'term> {
+    /// Cria um nodo com o estado dado.
+    fn new(thunk: Thunk<'term>) -> Self {
+        Self(Rc::new(RefCell::new(thunk)))
+    }
+}
+
+/// Retira o estado de um nodo, deixando-o marcado como sendo avaliado.
+fn take_state<'term>(thunk: &ThunkRef<'term>) -> Thunk<'term> {
+    mem::replace(&mut *thunk.0.borrow_mut(), Thunk::Evaluating)
+}
+
+/// Sobrescreve um nodo com o resultado de sua avaliação.
+fn finish<'term>(thunk: &ThunkRef<'term>, value: Whnf<'term>, cost: u64) {
+    *thunk.0.borrow_mut() = Thunk::Done { value, cost };
+}
+
+/// Ambiente: lista encadeada e compartilhada de nodos. O primeiro nodo
+/// corresponde ao índice de De Bruijn 1.
+#[derive(Debug, Clone, Default)]
+struct Env<'term> {
+    node: Option<Rc<EnvNode<'term>>>,
+}
+
+/// Nodo da lista encadeada de um ambiente.
+#[derive(Debug)]
+struct EnvNode<'term> {
+    thunk: ThunkRef<'term>,
+    next: Env<'term>,
+}
+
+impl<'term> Env<'term> {
+    /// Cria um novo ambiente com o nodo dado no índice 1, seguido dos nodos
+    /// deste ambiente.
+    fn push(&self, thunk: ThunkRef<'term>) -> Self {
+        let node = EnvNode { thunk, next: self.clone() };
+        Self { node: Some(Rc::new(node)) }
+    }
+
+    /// Busca o nodo do índice de De Bruijn dado.
+    fn get(&self, index: u64) -> Option<&ThunkRef<'term>> {
+        let mut node = self.node.as_ref()?;
+        for _ in 1..index {
+            node = node.next.node.as_ref()?;
+        }
+        Some(&node.thunk)
+    }
+}
+
+/// Solta estados de nodos e nodos de ambientes sem recursão. Ambientes e
+/// nodos podem se encadear em dezenas de milhares de níveis (por exemplo, ao
+/// normalizar um numeral grande), então o drop padrão estouraria a pilha.
+/// Somente o que não é compartilhado é desmontado.
+fn release<'term>(
+    mut states: Vec<Thunk<'term>>,
+    mut nodes: Vec<Rc<EnvNode<'term>>>,
+) {
+    loop {
+        if let Some(node) = nodes.pop() {
+            if let Ok(EnvNode { thunk, mut next }) = Rc::try_unwrap(node) {
+                nodes.extend(next.node.take());
+                if Rc::strong_count(&thunk.0) == 1 {
+                    states.push(take_state(&thunk));
+                }
+            }
+        } else if let Some(state) = states.pop() {
+            match state {
+                Thunk::Delayed { term: _, mut env }
+                | Thunk::Done {
+                    value: Whnf::Closure { term: _, mut env },
+                    cost: _,
+                } => nodes.extend(env.node.take()),
+                Thunk::Done {
+                    value: Whnf::Neutral { head: _, spine },
+                    cost: _,
+                } => {
+                    for thunk in spine {
+                        if Rc::strong_count(&thunk.0) == 1 {
+                            states.push(take_state(&thunk));
+                        }
+                    }
+                }
+                Thunk::Evaluating => (),
+            }
+        } else {
+            break;
+        }
+    }
+}
+
+impl Drop for Env<'_> {
+    fn drop(&mut self) {
+        if let Some(node) = self.node.take() {
+            release(Vec::new(), vec![node]);
+        }
+    }
+}
+
+impl Drop for ThunkRef<'_> {
+    fn drop(&mut self) {
+        if Rc::strong_count(&self.0) == 1 {
+            release(vec![take_state(self)], Vec::new());
+        }
+    }
+}
+
+/// Item da pilha da máquina.
+enum Frame<'term> {
+    /// Argumento pendente, a ser passado para o próximo lambda.
+    Argument(ThunkRef<'term>),
+    /// Marca de atualização: quando a forma normal fraca de cabeça for
+    /// alcançada, o nodo é sobrescrito com ela. Guarda os passos da ordem
+    /// normal no início da avaliação, para calcular o custo.
+    Update { thunk: ThunkRef<'term>, start: u64 },
+}
+
+/// Estado da máquina com compartilhamento.
+#[derive(Debug)]
+struct Machine {
+    /// Beta-reduções feitas até agora.
+    shared_steps: u64,
+    /// Beta-reduções que a ordem normal teria feito até agora.
+    normal_order_steps: u64,
+}
+
+impl Machine {
+    /// Cria um nodo já avaliado para o parâmetro de um lambda sendo
+    /// normalizado.
+    fn parameter<'term>(level: u64) -> ThunkRef<'term> {
+        let value =
+            Whnf::Neutral { head: Head::Level(level), spine: Vec::new() };
+        ThunkRef::new(Thunk::Done { value, cost: 0 })
+    }
+
+    /// Força a avaliação de um nodo, reaproveitando o resultado se ele já
+    /// tiver sido avaliado.
+    fn force<'term>(&mut self, thunk: &ThunkRef<'term>) -> Whnf<'term> {
+        match take_state(thunk) {
+            Thunk::Done { value, cost } => {
+                self.normal_order_steps += cost;
+                finish(thunk, value.clone(), cost);
+                value
+            }
+            Thunk::Delayed { term, env } => {
+                let start = self.normal_order_steps;
+                let value = self.eval(term, env);
+                finish(thunk, value.clone(), self.normal_order_steps - start);
+                value
+            }
+            Thunk::Evaluating => {
+                unreachable!("nodo não depende de si mesmo")
+            }
+        }
+    }
+
+    /// Avalia um termo até a forma normal fraca de cabeça, atualizando os
+    /// nodos avaliados no caminho.
+    fn eval<'term>(
+        &mut self,
+        mut term: &'term DeBruijn,
+        mut env: Env<'term>,
+    ) -> Whnf<'term> {
+        let mut stack: Vec<Frame<'term>> = Vec::new();
+
+        loop {
+            // Um termo neutro, se a cabeça do termo atual for uma variável
+            // não conhecida.
+            let (head, mut spine) = match term {
+                DeBruijn::Application { function, argument } => {
+                    // Variáveis já têm um nodo, que é compartilhado.
+                    let thunk = match argument.as_ref() {
+                        DeBruijn::Bound(index) => {
+                            env.get(*index).expect("índice ligado").clone()
+                        }
+                        argument => ThunkRef::new(Thunk::Delayed {
+                            term: argument,
+                            env: env.clone(),
+                        }),
+                    };
+                    stack.push(Frame::Argument(thunk));
+                    term = function.as_ref();
+                    continue;
+                }
+
+                DeBruijn::Lambda { hint: _, body } => match stack.pop() {
+                    Some(Frame::Argument(argument)) => {
+                        self.shared_steps += 1;
+                        self.normal_order_steps += 1;
+                        env = env.push(argument);
+                        term = body.as_ref();
+                        continue;
+                    }
+                    Some(Frame::Update { thunk, start }) => {
+                        let value = Whnf::Closure { term, env: env.clone() };
+                        finish(&thunk, value, self.normal_order_steps - start);
+                        continue;
+                    }
+                    None => break Whnf::Closure { term, env },
+                },
+
+                DeBruijn::Bound(index) => {
+                    let thunk = env.get(*index).expect("índice ligado").clone();
+                    match take_state(&thunk) {
+                        Thunk::Delayed { term: thunk_term, env: thunk_env } => {
+                            let start = self.normal_order_steps;
+                            stack.push(Frame::Update { thunk, start });
+                            term = thunk_term;
+                            env = thunk_env;
+                            continue;
+                        }
+
+                        Thunk::Done { value, cost } => {
+                            self.normal_order_steps += cost;
+                            finish(&thunk, value.clone(), cost);
+
+                            match value {
+                                Whnf::Closure {
+                                    term: closure_term,
+                                    env: closure_env,
+                                } => {
+                                    term = closure_term;
+                                    env = closure_env;
+                                    continue;
+                                }
+                                Whnf::Neutral { head, spine } => (head, spine),
+                            }
+                        }
+
+                        Thunk::Evaluating => {
+                            unreachable!("nodo não depende de si mesmo")
+                        }
+                    }
+                }
+
+                DeBruijn::Free(name) => (Head::Free(name), Vec::new()),
+            };
+
+            // Aplica o termo neutro aos argumentos pendentes, atualizando os
+            // nodos no caminho.
+            while let Some(frame) = stack.pop() {
+                match frame {
+                    Frame::Argument(argument) => spine.push(argument),
+                    Frame::Update { thunk, start } => {
+                        let value = Whnf::Neutral {
+                            head: head.clone(),
+                            spine: spine.clone(),
+                        };
+                        finish(&thunk, value, self.normal_order_steps - start);
+                    }
+                }
+            }
+
+            break Whnf::Neutral { head, spine };
+        }
+    }
+
+    /// Normaliza um termo: avalia-o e, então, normaliza o corpo de lambdas
+    /// (com o parâmetro como termo neutro) e os argumentos de termos neutros.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// normalize :: Int -> Whnf -> DeBruijn
+    /// normalize d (Closure (Lambda h b) env) =
+    ///   Lambda h (normalize (d + 1) (eval b (parameter d : env)))
+    /// normalize d (Neutral h as) =
+    ///   foldl Application (readHead d h) (map (normalize d . force) as)
+    ///
+    /// readHead :: Int -> Head -> DeBruijn
+    /// readHead d (Level l) = Bound (d - l)
+    /// readHead d (Free s) = Free s
+    /// ```
+    fn normalize(&mut self, term: &DeBruijn) -> DeBruijn {
+        /// Uma operação auxiliar da normalização.
+        enum Operation<'term> {
+            /// Normaliza um termo em um ambiente, dada a quantidade de
+            /// lambdas envolvendo o termo no resultado.
+            NormalizeTerm(&'term DeBruijn, Env<'term>, u64),
+            /// Normaliza um nodo, dada a quantidade de lambdas envolvendo o
+            /// nodo no resultado.
+            NormalizeThunk(ThunkRef<'term>, u64),
+            /// Finaliza a normalização do corpo de um lambda.
+            MakeLambda(&'term str),
+            /// Finaliza a normalização do argumento de uma aplicação.
+            MakeApplication,
+        }
+
+        let mut operation_stack =
+            vec![Operation::NormalizeTerm(term, Env::default(), 0)];
+        let mut output_stack: Vec<DeBruijn> = Vec::new();
+
+        while let Some(operation) = operation_stack.pop() {
+            let (value, depth) = match operation {
+                Operation::NormalizeTerm(term, env, depth) => {
+                    (self.eval(term, env), depth)
+                }
+
+                Operation::NormalizeThunk(thunk, depth) => {
+                    (self.force(&thunk), depth)
+                }
+
+                Operation::MakeLambda(hint) => {
+                    let body = output_stack.pop().expect("normal body");
+                    output_stack.push(DeBruijn::Lambda {
+                        hint: hint.to_owned(),
+                        body: Box::new(body),
+                    });
+                    continue;
+                }
+
+                Operation::MakeApplication => {
+                    let argument = output_stack.pop().expect("normal argument");
+                    let function = output_stack.pop().expect("normal function");
+                    output_stack.push(DeBruijn::Application {
+                        function: Box::new(function),
+                        argument: Box::new(argument),
+                    });
+                    continue;
+                }
+            };
+
+            match value {
+                Whnf::Closure {
+                    term: DeBruijn::Lambda { hint, body },
+                    env,
+                } => {
+                    let env = env.push(Self::parameter(depth));
+                    operation_stack.push(Operation::MakeLambda(hint));
+                    operation_stack.push(Operation::NormalizeTerm(
+                        body,
+                        env,
+                        depth + 1,
+                    ));
+                }
+
+                Whnf::Closure { .. } => {
+                    unreachable!("avaliação termina em lambdas")
+                }
+
+                Whnf::Neutral { head, spine } => {
+                    let head = match head {
+                        Head::Level(level) => DeBruijn::Bound(depth - level),
+                        Head::Free(name) => DeBruijn::Free(name.to_owned()),
+                    };
+                    output_stack.push(head);
+
+                    // .pop() retorna elementos na ordem inversa do .push()
+                    for argument in spine.into_iter().rev() {
+                        operation_stack.push(Operation::MakeApplication);
+                        operation_stack
+                            .push(Operation::NormalizeThunk(argument, depth));
+                    }
+                }
+            }
+        }
+
+        output_stack.pop().expect("normal")
+    }
+}
@@ -0,0 +1,86 @@
+use super::run_lazy;
+use crate::compiler::compile;
+use crate::interpreter::{run_once, Interpreter};
+
+#[test]
+fn shares_argument() {
+    let input = compile("(\\x. x x x) ((\\y. y) z)").unwrap();
+    let evaluation = run_lazy(&input);
+
+    assert_eq!(evaluation.shared_steps, 2);
+    assert_eq!(evaluation.normal_order_steps, 4);
+    assert_eq!(evaluation.output, compile("z z z").unwrap());
+}
+
+#[test]
+fn shares_argument_inside_argument() {
+    let input = compile("(\\x. x (x z)) ((\\y. \\w. w) a)").unwrap();
+    let evaluation = run_lazy(&input);
+
+    assert_eq!(evaluation.shared_steps, 4);
+    assert_eq!(evaluation.normal_order_steps, 5);
+    assert_eq!(evaluation.output, compile("z").unwrap());
+}
+
+#[test]
+fn discards_divergent_argument() {
+    let input = compile("(\\x. y) ((\\x. x x) (\\x. x x))").unwrap();
+    let evaluation = run_lazy(&input);
+
+    assert_eq!(evaluation.shared_steps, 1);
+    assert_eq!(evaluation.normal_order_steps, 1);
+    assert_eq!(evaluation.output, compile("y").unwrap());
+}
+
+#[test]
+fn same_as_normal_order() {
+    let sources = [
+        "let mul = \\m n f. m (n f); in mul 2 3",
+        "let add = \\m n f x. m f (n f x); in add 3 (add 1 2)",
+        "(\\f. \\x. f (f x)) (\\y. y y)",
+        "\\y. (\\x. \\y. x) y",
+        "z (\\x. (\\y. y) x) ((\\y. y) w)",
+        "(\\x. x (x z)) ((\\y. \\w. w) a)",
+        "let two = 2; in two two",
+    ];
+
+    for source in sources.iter() {
+        let input = compile(source).unwrap();
+        let evaluation = run_lazy(&input);
+
+        let mut interpreter = Interpreter::new(input);
+        interpreter.run_all();
+
+        assert_eq!(
+            evaluation.normal_order_steps,
+            interpreter.steps(),
+            "{}",
+            source
+        );
+        assert!(
+            evaluation.output.beta_equiv(interpreter.output()),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn fewer_shared_steps() {
+    let input = compile("let two = 2; in two two two").unwrap();
+    let evaluation = run_lazy(&input);
+
+    assert!(evaluation.shared_steps < evaluation.normal_order_steps);
+    assert_eq!(evaluation.output.church_numeral_to_int(), Some(16));
+    assert!(evaluation.output.beta_equiv(&run_once(input)));
+}
+
+#[test]
+fn deep_normal_form() {
+    // O numeral resultante e as cadeias de nodos têm dezenas de milhares de
+    // níveis.
+    let input = compile("let two = 2; in two two two two").unwrap();
+    let evaluation = run_lazy(&input);
+
+    assert_eq!(evaluation.output.church_numeral_to_int(), Some(65536));
+}
@@ -0,0 +1,104 @@
+use super::{run_cek, run_krivine};
+use crate::compiler::compile;
+use crate::interpreter::{run_once, Interpreter};
+use crate::value::Value;
+
+/// Fatorial com numerais de Church, iterando sobre pares `(i, i!)`.
+const FACTORIAL: &str = "
+    let pair = \\a b f. f a b;
+        fst = \\p. p (\\a b. a);
+        snd = \\p. p (\\a b. b);
+        succ = \\n f x. f (n f x);
+        mul = \\m n f. m (n f);
+        next = \\p. pair (succ (fst p)) (mul (succ (fst p)) (snd p));
+        fact = \\n. snd (n next (pair 0 1));
+    in fact 4
+";
+
+#[test]
+fn krivine_normal_form() {
+    let input = compile(FACTORIAL).unwrap();
+    let evaluation = run_krivine(&input);
+
+    assert_eq!(evaluation.output.church_numeral_to_int(), Some(24));
+}
+
+#[test]
+fn cek_normal_form() {
+    let input = compile(FACTORIAL).unwrap();
+    let evaluation = run_cek(&input);
+
+    assert_eq!(evaluation.output.church_numeral_to_int(), Some(24));
+}
+
+#[test]
+fn same_normal_form_as_run_once() {
+    let sources = [
+        "let mul = \\m n f. m (n f); in mul 2 3",
+        "(\\x. x x) ((\\y. y) z)",
+        "\\x. (\\y. \\x. y x) x",
+        "(\\f. \\x. f (f x)) (\\y. y y)",
+        "z (\\x. (\\y. y) x) ((\\y. y) w)",
+    ];
+
+    for source in sources.iter() {
+        let input = compile(source).unwrap();
+        let expected = run_once(input.clone());
+
+        assert!(run_krivine(&input).output.beta_equiv(&expected), "{}", source);
+        assert!(run_cek(&input).output.beta_equiv(&expected), "{}", source);
+    }
+}
+
+#[test]
+fn krivine_steps_match_normal_order() {
+    let input = compile("(\\x. x x) ((\\y. y) z)").unwrap();
+    let evaluation = run_krivine(&input);
+
+    let mut interpreter = Interpreter::new(input);
+    interpreter.run_all();
+
+    assert_eq!(evaluation.steps, 3);
+    assert_eq!(evaluation.steps, interpreter.steps());
+    assert_eq!(&evaluation.output, interpreter.output());
+}
+
+#[test]
+fn cek_evaluates_argument_once() {
+    let input = compile("(\\x. x x) ((\\y. y) z)").unwrap();
+    let evaluation = run_cek(&input);
+
+    assert_eq!(evaluation.steps, 2);
+    assert_eq!(evaluation.output, compile("z z").unwrap());
+}
+
+#[test]
+fn krivine_discards_divergent_argument() {
+    let input = compile("(\\x. y) ((\\x. x x) (\\x. x x))").unwrap();
+    let evaluation = run_krivine(&input);
+
+    assert_eq!(evaluation.steps, 1);
+    assert_eq!(evaluation.output, Value::Variable(String::from("y")));
+}
+
+#[test]
+fn readback_avoids_capture() {
+    // λy. (λx. λy. x) y  ->  λy. λy_. y
+    let input = compile("\\y. (\\x. \\y. x) y").unwrap();
+    let expected = compile("\\y. \\y_. y").unwrap();
+
+    assert_eq!(run_krivine(&input).output, expected);
+    assert_eq!(run_cek(&input).output, expected);
+}
+
+#[test]
+fn deep_normal_form() {
+    // O numeral resultante e os ambientes têm dezenas de milhares de níveis.
+    let input = compile("let two = 2; in two two two two").unwrap();
+
+    let evaluation = run_krivine(&input);
+    assert_eq!(evaluation.output.church_numeral_to_int(), Some(65536));
+
+    let evaluation = run_cek(&input);
+    assert_eq!(evaluation.output.church_numeral_to_int(), Some(65536));
+}
@@ -0,0 +1,597 @@
+//! Exporta um avaliador por redução ótima (no sentido de Lévy), baseado em
+//! redes de interação: o algoritmo abstrato de Lamping, sem o oráculo.
+//!
+//! O termo é traduzido para uma rede em que lambdas e aplicações são nodos
+//! construtores, variáveis usadas mais de uma vez passam por nodos
+//! duplicadores, e variáveis não usadas terminam em nodos apagadores. A rede
+//! é reduzida por regras locais, que duplicam os termos aos poucos e só
+//! quando necessário, de forma que um mesmo redex nunca é reduzido duas vezes
+//! em cópias diferentes.
+//!
+//! # Limitações
+//!
+//! Sem o oráculo, o algoritmo só é garantido para termos tipáveis na lógica
+//! afim elementar (EAL), o que inclui a aritmética com numerais de Church,
+//! inclusive exponenciação como `2 2 2 2`. Para outros termos (por exemplo,
+//! alguns usos do combinador Y), a leitura da rede pode falhar ou mesmo
+//! produzir um termo errado.
+
+#[cfg(test)]
+mod test;
+
+use crate::debruijn::DeBruijn;
+use crate::interpreter::Limits;
+use crate::value::Value;
+use std::rc::Rc;
+use std::time::Instant;
+
+/// Resultado da avaliação de um termo por redução ótima.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetEvaluation {
+    /// Forma normal do termo de entrada.
+    pub output: Value,
+    /// Número de interações (reescritas da rede) feitas, incluindo
+    /// duplicações e apagamentos.
+    pub interactions: u64,
+    /// Número de interações entre lambda e aplicação, isto é, de
+    /// beta-reduções. Nunca é maior que o número de passos da ordem normal
+    /// (ver [`Interpreter::steps`](crate::interpreter::Interpreter::steps)).
+    pub beta_steps: u64,
+}
+
+/// Reduz o termo até a sua forma normal por redução ótima. Somente as
+/// interações necessárias para a leitura do resultado são feitas.
+///
+/// Retorna `None` se algum dos limites for atingido, ou se a rede não puder
+/// ser lida como um termo, o que só ocorre com termos fora do fragmento
+/// suportado (ver as limitações no módulo). Nesses termos, a redução e a
+/// leitura podem não terminar mesmo que a ordem normal termine. O limite de
+/// passos conta as interações, e o limite de tamanho vale tanto para o
+/// número de nodos da rede quanto para a profundidade da leitura (nodos no
+/// caminho da raiz até o ponto lido).
+pub fn run_net(input: &Value, limits: &Limits) -> Option<NetEvaluation> {
+    let mut net = Net::from_value(input);
+    let term = net.normalize(limits)?;
+
+    Some(NetEvaluation {
+        output: term.to_value(),
+        interactions: net.interactions,
+        beta_steps: net.beta_steps,
+    })
+}
+
+/// Endereço de uma porta: `3 * nodo + slot`. O slot 0 é a porta principal;
+/// 1 e 2 são as portas auxiliares.
+type Port = usize;
+
+/// Calcula o endereço da porta de um nodo.
+fn port(node: usize, slot: usize) -> Port {
+    node * 3 + slot
+}
+
+/// Nodo de uma porta.
+fn node_of(port: Port) -> usize {
+    port / 3
+}
+
+/// Slot de uma porta.
+fn slot_of(port: Port) -> usize {
+    port % 3
+}
+
+/// Tipo de um nodo da rede.
+#[derive(Debug, Clone)]
+enum Kind {
+    /// Raiz da rede: a porta 0 é ligada ao termo inteiro.
+    Root,
+    /// Lambda ou aplicação. Lambda: 0 é o próprio lambda, 1 é a variável e
+    /// 2 é o corpo. Aplicação: 0 é a função, 1 é o argumento e 2 é o
+    /// resultado. Guarda o nome do parâmetro para a leitura, se for lambda.
+    Constructor(Rc<str>),
+    /// Duplicador: 0 é o termo duplicado e 1 e 2 são as cópias.
+    /// Duplicadores só se anulam se tiverem o mesmo rótulo.
+    Duplicator(u64),
+    /// Apagador: a porta 0 é ligada ao termo apagado.
+    Eraser,
+    /// Variável livre do termo de entrada, ligada pela porta 0.
+    Free(Rc<str>),
+}
+
+/// Rede de interação.
+#[derive(Debug, Clone)]
+struct Net {
+    /// Tipo de cada nodo.
+    kinds: Vec<Kind>,
+    /// Porta ligada a cada porta.
+    ports: Vec<Port>,
+    /// Nodos liberados, que podem ser reaproveitados.
+    reusable: Vec<usize>,
+    /// Último rótulo de duplicador usado.
+    last_label: u64,
+    /// Interações feitas até agora.
+    interactions: u64,
+    /// Interações entre construtores feitas até agora.
+    beta_steps: u64,
+}
+
+impl Net {
+    /// Traduz um termo para uma rede. Cada ocorrência de variável livre vira
+    /// um nodo separado.
+    fn from_value(value: &Value) -> Self {
+        /// Uma operação auxiliar da tradução.
+        enum Operation<'value> {
+            /// Traduz um termo, ligando-o à porta dada.
+            Build(&'value Value, Port),
+            /// Finaliza o escopo do último lambda, ligando sua variável às
+            /// ocorrências.
+            Bind,
+        }
+
+        let mut net = Self {
+            kinds: vec![Kind::Root],
+            ports: vec![0, 1, 2],
+            reusable: Vec::new(),
+            last_label: 0,
+            interactions: 0,
+            beta_steps: 0,
+        };
+
+        // Parâmetros em escopo, junto do nodo do lambda e das portas em que
+        // o parâmetro é usado.
+        let mut scope: Vec<(&str, usize, Vec<Port>)> = Vec::new();
+        let mut operation_stack = vec![Operation::Build(value, port(0, 0))];
+
+        while let Some(operation) = operation_stack.pop() {
+            match operation {
+                Operation::Build(value, parent) => match value {
+                    Value::Variable(variable) => {
+                        let binder = scope
+                            .iter_mut()
+                            .rev()
+                            .find(|(param, _, _)| *param == variable.as_str());
+                        match binder {
+                            Some((_, _, uses)) => uses.push(parent),
+                            None => {
+                                let kind = Kind::Free(variable.as_str().into());
+                                let free = net.new_node(kind);
+                                net.link(parent, port(free, 0));
+                            }
+                        }
+                    }
+
+                    Value::Application { function, argument } => {
+                        let app = net.new_node(Kind::Constructor("".into()));
+                        net.link(parent, port(app, 2));
+                        // .pop() retorna elementos na ordem inversa do .push()
+                        operation_stack
+                            .push(Operation::Build(argument, port(app, 1)));
+                        operation_stack
+                            .push(Operation::Build(function, port(app, 0)));
+                    }
+
+                    Value::Lambda { parameter, body } => {
+                        let kind = Kind::Constructor(parameter.as_str().into());
+                        let lambda = net.new_node(kind);
+                        net.link(parent, port(lambda, 0));
+                        scope.push((parameter.as_str(), lambda, Vec::new()));
+                        operation_stack.push(Operation::Bind);
+                        operation_stack
+                            .push(Operation::Build(body, port(lambda, 2)));
+                    }
+                },
+
+                Operation::Bind => {
+                    let (_, lambda, uses) = scope.pop().expect("lambda scope");
+                    net.bind(port(lambda, 1), &uses);
+                }
+            }
+        }
+
+        net
+    }
+
+    /// Liga a porta de variável de um lambda às suas ocorrências: a um
+    /// apagador se não houver ocorrências, diretamente se houver uma, e por
+    /// uma cadeia de duplicadores se houver mais.
+    fn bind(&mut self, variable: Port, uses: &[Port]) {
+        match uses.split_last() {
+            None => {
+                let eraser = self.new_node(Kind::Eraser);
+                self.link(variable, port(eraser, 0));
+            }
+
+            Some((&last, init)) => {
+                let mut source = variable;
+                for &use_port in init {
+                    self.last_label += 1;
+                    let kind = Kind::Duplicator(self.last_label);
+                    let duplicator = self.new_node(kind);
+                    self.link(source, port(duplicator, 0));
+                    self.link(port(duplicator, 1), use_port);
+                    source = port(duplicator, 2);
+                }
+                self.link(source, last);
+            }
+        }
+    }
+
+    /// Cria um nodo com as portas desligadas (ligadas a si mesmas).
+    fn new_node(&mut self, kind: Kind) -> usize {
+        let node = match self.reusable.pop() {
+            Some(node) => {
+                self.kinds[node] = kind;
+                node
+            }
+            None => {
+                self.kinds.push(kind);
+                self.ports.extend_from_slice(&[0, 0, 0]);
+                self.kinds.len() - 1
+            }
+        };
+
+        for slot in 0..3 {
+            self.ports[port(node, slot)] = port(node, slot);
+        }
+
+        node
+    }
+
+    /// Número de nodos em uso.
+    fn size(&self) -> usize {
+        self.kinds.len() - self.reusable.len()
+    }
+
+    /// Libera um nodo para ser reaproveitado.
+    fn free_node(&mut self, node: usize) {
+        self.reusable.push(node);
+    }
+
+    /// Porta ligada à porta dada.
+    fn enter(&self, port: Port) -> Port {
+        self.ports[port]
+    }
+
+    /// Liga duas portas.
+    fn link(&mut self, left: Port, right: Port) {
+        self.ports[left] = right;
+        self.ports[right] = left;
+    }
+
+    /// Testa se a saída de um nodo pela porta principal, chegando na porta
+    /// dada, forma um par ativo que deve ser reduzido. Aplicações de
+    /// variáveis livres não são reduzidas.
+    fn interacts(&self, node: usize, partner: Port) -> bool {
+        let kinds = (&self.kinds[node], &self.kinds[node_of(partner)]);
+
+        slot_of(partner) == 0
+            && !matches!(
+                kinds,
+                (_, Kind::Root) | (Kind::Constructor(_), Kind::Free(_))
+            )
+    }
+
+    /// Reduz o par ativo formado pelos nodos dados.
+    fn rewrite(&mut self, left: usize, right: usize) {
+        self.interactions += 1;
+
+        match (self.kinds[left].clone(), self.kinds[right].clone()) {
+            (Kind::Constructor(_), Kind::Constructor(_)) => {
+                self.beta_steps += 1;
+                self.annihilate(left, right);
+            }
+
+            (Kind::Duplicator(left_label), Kind::Duplicator(right_label))
+                if left_label == right_label =>
+            {
+                self.annihilate(left, right);
+            }
+
+            (Kind::Eraser, Kind::Eraser)
+            | (Kind::Eraser, Kind::Free(_))
+            | (Kind::Free(_), Kind::Eraser) => (),
+
+            (Kind::Eraser, _) => self.erase(right),
+            (_, Kind::Eraser) => self.erase(left),
+
+            (Kind::Duplicator(_), Kind::Free(name)) => {
+                self.copy_free(left, name)
+            }
+            (Kind::Free(name), Kind::Duplicator(_)) => {
+                self.copy_free(right, name)
+            }
+
+            (Kind::Constructor(_), Kind::Duplicator(_))
+            | (Kind::Duplicator(_), Kind::Constructor(_))
+            | (Kind::Duplicator(_), Kind::Duplicator(_)) => {
+                self.commute(left, right)
+            }
+
+            _ => unreachable!("par sem regra de interação"),
+        }
+
+        self.free_node(left);
+        self.free_node(right);
+    }
+
+    /// Anula dois nodos do mesmo tipo, ligando suas portas auxiliares
+    /// correspondentes. Entre lambda e aplicação, é a beta-redução: a
+    /// variável é ligada ao argumento, e o corpo ao resultado.
+    fn annihilate(&mut self, left: usize, right: usize) {
+        // As portas são relidas depois de cada ligação, pois a primeira
+        // ligação pode alterar a segunda.
+        for slot in 1..3 {
+            let left_partner = self.enter(port(left, slot));
+            let right_partner = self.enter(port(right, slot));
+            self.link(left_partner, right_partner);
+        }
+    }
+
+    /// Apaga um nodo, propagando apagadores para suas portas auxiliares.
+    fn erase(&mut self, node: usize) {
+        for slot in 1..3 {
+            let eraser = self.new_node(Kind::Eraser);
+            let partner = self.enter(port(node, slot));
+            self.link(port(eraser, 0), partner);
+        }
+    }
+
+    /// Duplica uma variável livre, ligando uma cópia a cada porta auxiliar
+    /// do duplicador.
+    fn copy_free(&mut self, duplicator: usize, name: Rc<str>) {
+        for slot in 1..3 {
+            let free = self.new_node(Kind::Free(name.clone()));
+            let partner = self.enter(port(duplicator, slot));
+            self.link(port(free, 0), partner);
+        }
+    }
+
+    /// Comuta dois nodos de tipos diferentes: cada um é copiado para as
+    /// portas auxiliares do outro, e as cópias são ligadas entre si.
+    fn commute(&mut self, left: usize, right: usize) {
+        let left_kind = self.kinds[left].clone();
+        let right_kind = self.kinds[right].clone();
+
+        let left_copies =
+            [self.new_node(left_kind.clone()), self.new_node(left_kind)];
+        let right_copies =
+            [self.new_node(right_kind.clone()), self.new_node(right_kind)];
+
+        for (index, &copy) in left_copies.iter().enumerate() {
+            let partner = self.enter(port(right, index + 1));
+            self.link(port(copy, 0), partner);
+        }
+        for (index, &copy) in right_copies.iter().enumerate() {
+            let partner = self.enter(port(left, index + 1));
+            self.link(port(copy, 0), partner);
+        }
+
+        for (left_index, &left_copy) in left_copies.iter().enumerate() {
+            for (right_index, &right_copy) in right_copies.iter().enumerate() {
+                self.link(
+                    port(left_copy, right_index + 1),
+                    port(right_copy, left_index + 1),
+                );
+            }
+        }
+    }
+
+    /// Percorre a rede a partir da raiz, lendo o termo representado e
+    /// reduzindo os pares ativos encontrados no caminho da leitura. Ao sair
+    /// de um nodo pela porta principal e chegar na porta principal de outro,
+    /// esse par ativo é reduzido antes de continuar. Retorna `None` se a rede
+    /// não puder ser lida como um termo.
+    ///
+    /// Ao atravessar um duplicador de uma cópia para o termo duplicado, a
+    /// cópia é empilhada; ao chegar em um duplicador pelo termo duplicado, a
+    /// leitura volta pela cópia empilhada mais recente de mesmo rótulo.
+    ///
+    /// Depois de uma redução, a leitura não recomeça da raiz: só os nodos
+    /// ligados ao par ativo mudam, então ela volta ao nodo de onde chegou no
+    /// par. Se ela saiu desse nodo pela porta principal, ele pode ter formado
+    /// um novo par ativo, e a leitura volta mais um nodo, e assim por diante.
+    ///
+    /// Retorna `None` também se algum dos limites for atingido (ver
+    /// [`run_net`]).
+    fn normalize(&mut self, limits: &Limits) -> Option<DeBruijn> {
+        /// Uma operação auxiliar da leitura.
+        enum Operation {
+            /// Lê o termo ligado à porta dada, com os lambdas envolvendo o
+            /// termo e as cópias empilhadas nos duplicadores. A profundidade
+            /// é o número de nodos no caminho da raiz até a porta.
+            Visit {
+                from: Port,
+                binders: Vec<usize>,
+                exits: Vec<(u64, usize)>,
+                depth: usize,
+            },
+            /// Finaliza a leitura do corpo de um lambda.
+            MakeLambda(Rc<str>),
+            /// Finaliza a leitura do argumento de uma aplicação.
+            MakeApplication,
+        }
+
+        /// Uma visita no caminho da raiz até o ponto atual da leitura,
+        /// guardada para que a leitura possa voltar a ela.
+        struct Frame {
+            from: Port,
+            binders: Vec<usize>,
+            exits: Vec<(u64, usize)>,
+            /// Tamanho da pilha de operações quando a visita começou.
+            operations: usize,
+            /// Tamanho da pilha de saída quando a visita começou.
+            outputs: usize,
+        }
+
+        let mut operation_stack = vec![Operation::Visit {
+            from: port(0, 0),
+            binders: Vec::new(),
+            exits: Vec::new(),
+            depth: 0,
+        }];
+        let mut output_stack: Vec<DeBruijn> = Vec::new();
+        let mut path: Vec<Frame> = Vec::new();
+
+        // Só consulta o relógio se houver limite de tempo.
+        let deadline = limits.timeout.map(|timeout| Instant::now() + timeout);
+        if limits.max_size.is_some_and(|max| self.size() > max) {
+            return None;
+        }
+
+        while let Some(operation) = operation_stack.pop() {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return None;
+            }
+
+            let (from, mut binders, mut exits, depth) = match operation {
+                Operation::Visit { from, binders, exits, depth } => {
+                    (from, binders, exits, depth)
+                }
+
+                Operation::MakeLambda(hint) => {
+                    let body = output_stack.pop().expect("net body");
+                    output_stack.push(DeBruijn::Lambda {
+                        hint: hint.to_string(),
+                        body: Box::new(body),
+                    });
+                    continue;
+                }
+
+                Operation::MakeApplication => {
+                    let argument = output_stack.pop().expect("net argument");
+                    let function = output_stack.pop().expect("net function");
+                    output_stack.push(DeBruijn::Application {
+                        function: Box::new(function),
+                        argument: Box::new(argument),
+                    });
+                    continue;
+                }
+            };
+
+            // Sem interações, a leitura de uma rede que não representa um
+            // termo finito se aprofunda para sempre.
+            if limits.max_size.is_some_and(|max| depth > max) {
+                return None;
+            }
+
+            path.truncate(depth);
+            path.push(Frame {
+                from,
+                binders: binders.clone(),
+                exits: exits.clone(),
+                operations: operation_stack.len(),
+                outputs: output_stack.len(),
+            });
+
+            let arrival = self.enter(from);
+            let node = node_of(arrival);
+            let mut active = None;
+
+            match (&self.kinds[node], slot_of(arrival)) {
+                // Chegando em um lambda por cima.
+                (Kind::Constructor(hint), 0) => {
+                    binders.push(node);
+                    operation_stack.push(Operation::MakeLambda(hint.clone()));
+                    operation_stack.push(Operation::Visit {
+                        from: port(node, 2),
+                        binders,
+                        exits,
+                        depth: depth + 1,
+                    });
+                }
+
+                // Chegando na variável de um lambda.
+                (Kind::Constructor(_), 1) => {
+                    let index =
+                        binders.iter().rev().position(|&lam| lam == node)?;
+                    output_stack.push(DeBruijn::Bound(index as u64 + 1));
+                }
+
+                // Chegando em uma aplicação pelo resultado.
+                (Kind::Constructor(_), _) => {
+                    let function = self.enter(port(node, 0));
+                    if self.interacts(node, function) {
+                        active = Some((node, node_of(function)));
+                    } else {
+                        // .pop() retorna elementos na ordem inversa do
+                        // .push()
+                        operation_stack.push(Operation::MakeApplication);
+                        operation_stack.push(Operation::Visit {
+                            from: port(node, 1),
+                            binders: binders.clone(),
+                            exits: exits.clone(),
+                            depth: depth + 1,
+                        });
+                        operation_stack.push(Operation::Visit {
+                            from: port(node, 0),
+                            binders,
+                            exits,
+                            depth: depth + 1,
+                        });
+                    }
+                }
+
+                // Chegando em um duplicador pelo termo duplicado.
+                (Kind::Duplicator(label), 0) => {
+                    let position =
+                        exits.iter().rposition(|(exit, _)| exit == label)?;
+                    let slot = exits.remove(position).1;
+                    operation_stack.push(Operation::Visit {
+                        from: port(node, slot),
+                        binders,
+                        exits,
+                        depth: depth + 1,
+                    });
+                }
+
+                // Chegando em um duplicador por uma cópia.
+                (Kind::Duplicator(label), slot) => {
+                    let principal = self.enter(port(node, 0));
+                    if self.interacts(node, principal) {
+                        active = Some((node, node_of(principal)));
+                    } else {
+                        exits.push((*label, slot));
+                        operation_stack.push(Operation::Visit {
+                            from: port(node, 0),
+                            binders,
+                            exits,
+                            depth: depth + 1,
+                        });
+                    }
+                }
+
+                (Kind::Free(name), 0) => {
+                    output_stack.push(DeBruijn::Free(name.to_string()));
+                }
+
+                _ => return None,
+            }
+
+            if let Some((left, right)) = active {
+                self.rewrite(left, right);
+
+                if limits.max_steps.is_some_and(|max| self.interactions > max)
+                    || limits.max_size.is_some_and(|max| self.size() > max)
+                {
+                    return None;
+                }
+
+                let mut resume = depth;
+                while resume > 0 && slot_of(path[resume].from) == 0 {
+                    resume -= 1;
+                }
+                path.truncate(resume + 1);
+                let frame = path.pop().expect("net frame");
+                operation_stack.truncate(frame.operations);
+                output_stack.truncate(frame.outputs);
+                operation_stack.push(Operation::Visit {
+                    from: frame.from,
+                    binders: frame.binders,
+                    exits: frame.exits,
+                    depth: resume,
+                });
+            }
+        }
+
+        output_stack.pop()
+    }
+}
@@ -0,0 +1,116 @@
+use super::run_net;
+use crate::compiler::compile;
+use crate::interpreter::{Interpreter, Limits};
+
+const LIMITS: Limits =
+    Limits { max_steps: Some(100000), max_size: Some(1000000), timeout: None };
+
+#[test]
+fn single_beta() {
+    let input = compile("(\\x. x) y").unwrap();
+    let evaluation = run_net(&input, &LIMITS).unwrap();
+
+    assert_eq!(evaluation.output, compile("y").unwrap());
+    assert_eq!(evaluation.interactions, 1);
+    assert_eq!(evaluation.beta_steps, 1);
+}
+
+#[test]
+fn duplicate_lambda() {
+    let input = compile("(\\x. x x) (\\y. y)").unwrap();
+    let evaluation = run_net(&input, &LIMITS).unwrap();
+
+    assert_eq!(evaluation.output, compile("\\y. y").unwrap());
+    assert_eq!(evaluation.interactions, 4);
+    assert_eq!(evaluation.beta_steps, 2);
+}
+
+#[test]
+fn duplicate_free_variable() {
+    let input = compile("(\\x. x x) f").unwrap();
+    let evaluation = run_net(&input, &LIMITS).unwrap();
+
+    assert_eq!(evaluation.output, compile("f f").unwrap());
+    assert_eq!(evaluation.interactions, 2);
+    assert_eq!(evaluation.beta_steps, 1);
+}
+
+#[test]
+fn discards_divergent_argument() {
+    let input = compile("(\\x. y) ((\\x. x x) (\\x. x x))").unwrap();
+    let evaluation = run_net(&input, &LIMITS).unwrap();
+
+    assert_eq!(evaluation.output, compile("y").unwrap());
+    assert_eq!(evaluation.beta_steps, 1);
+}
+
+#[test]
+fn church_exponentiation() {
+    let input = compile("let two = 2; in two two two").unwrap();
+    let evaluation = run_net(&input, &LIMITS).unwrap();
+
+    assert_eq!(evaluation.output.church_numeral_to_int(), Some(16));
+
+    let mut interpreter = Interpreter::new(input);
+    interpreter.run_all();
+
+    assert!(evaluation.beta_steps < interpreter.steps());
+}
+
+#[test]
+fn church_tower_of_exponents() {
+    let input = compile("let two = 2; in two two two two").unwrap();
+    let evaluation = run_net(&input, &LIMITS).unwrap();
+
+    assert_eq!(evaluation.output.church_numeral_to_int(), Some(65536));
+    assert!(evaluation.beta_steps < 1000, "{}", evaluation.beta_steps);
+}
+
+#[test]
+fn same_as_normal_order() {
+    let sources = [
+        "let mul = \\m n f. m (n f); in mul 2 3",
+        "let add = \\m n f x. m f (n f x); in add 3 (add 1 2)",
+        "\\y. (\\x. \\y. x) y",
+        "z (\\x. (\\y. y) x) ((\\y. y) w)",
+        "let two = 2; in two two",
+    ];
+
+    for source in sources.iter() {
+        let input = compile(source).unwrap();
+        let evaluation = run_net(&input, &LIMITS).unwrap();
+
+        let mut interpreter = Interpreter::new(input);
+        interpreter.run_all();
+
+        assert!(
+            evaluation.output.beta_equiv(interpreter.output()),
+            "{}",
+            source
+        );
+        assert!(evaluation.beta_steps <= interpreter.steps(), "{}", source);
+    }
+}
+
+#[test]
+fn stops_at_limits() {
+    // Fora do fragmento suportado, a leitura destes termos não termina.
+    let sources = [
+        "(\\z. z z) ((\\y_. y_) \\z. z \\x_. a z)",
+        "(\\y_. y_ \\z. y_ (y_ y_)) \\y_. y_ ((\\x. x x) (y_ b \\x. x))",
+    ];
+    let limits =
+        Limits { max_steps: Some(10000), max_size: Some(1000), timeout: None };
+
+    for source in sources.iter() {
+        let input = compile(source).unwrap();
+        assert_eq!(run_net(&input, &limits), None, "{}", source);
+    }
+
+    let input = compile("let two = 2; in two two two").unwrap();
+    let limits = Limits { max_steps: Some(10), ..LIMITS };
+    assert_eq!(run_net(&input, &limits), None);
+
+    let limits = Limits { max_size: Some(10), ..LIMITS };
+    assert_eq!(run_net(&input, &limits), None);
+}
@@ -0,0 +1,188 @@
+//! Exporta as estratégias de avaliação, que decidem qual redex é reduzido a cada passo.
+
+use crate::value::{Direction, Value};
+
+/// Estratégia de avaliação de um termo lambda.
+///
+/// # Exemplo
+///
+/// Com o termo `(λx. x x) ((λy. y) z)`:
+///
+/// ```text
+/// Ordem normal:       3 passos, z z
+/// Ordem aplicativa:   2 passos, z z
+/// Chamada por nome:   2 passos, z ((λy. y) z)
+/// Chamada por valor:  2 passos, z z
+/// Redução de cabeça:  2 passos, z ((λy. y) z)
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Strategy {
+    /// Reduz o redex mais externo, mais à esquerda, inclusive dentro de
+    /// lambdas. Sempre chega à forma normal, se ela existir. É o padrão.
+    #[default]
+    NormalOrder,
+
+    /// Reduz o redex mais interno, mais à esquerda, inclusive dentro de
+    /// lambdas. Argumentos são reduzidos à forma normal antes de serem
+    /// passados para a função.
+    ApplicativeOrder,
+
+    /// Reduz o redex mais externo, mais à esquerda, sem entrar em lambdas nem
+    /// em argumentos. Para na forma normal de cabeça fraca.
+    CallByName,
+
+    /// Reduz o redex mais interno, mais à esquerda, sem entrar em lambdas.
+    /// Argumentos são reduzidos antes de serem passados para a função.
+    CallByValue,
+
+    /// Reduz somente o redex de cabeça, inclusive dentro de lambdas, sem
+    /// entrar em argumentos. Para na forma normal de cabeça.
+    HeadReduction,
+}
+
+impl Strategy {
+    /// Testa se essa estratégia procura redexes dentro do corpo de lambdas.
+    fn reduces_under_lambda(self) -> bool {
+        match self {
+            Strategy::NormalOrder
+            | Strategy::ApplicativeOrder
+            | Strategy::HeadReduction => true,
+            Strategy::CallByName | Strategy::CallByValue => false,
+        }
+    }
+
+    /// Procura o próximo redex a ser reduzido, de acordo com essa estratégia.
+    /// Retorna o caminho da raiz do termo até o redex, se houver redex.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// findRedex :: Strategy -> Value -> Maybe [Direction]
+    ///
+    /// findRedex s (Variable _) = Nothing
+    ///
+    /// findRedex s (Lambda p b) =
+    ///   if reducesUnderLambda s
+    ///     then fmap (Body :) (findRedex s b)
+    ///     else Nothing
+    ///
+    /// findRedex s (Application f a)
+    ///   | s `elem` [NormalOrder, CallByName, HeadReduction]
+    ///   , isRedex (Application f a) = Just []
+    ///
+    /// findRedex NormalOrder (Application f a) =
+    ///   fmap (Function :) (findRedex NormalOrder f)
+    ///     <|> fmap (Argument :) (findRedex NormalOrder a)
+    ///
+    /// findRedex s (Application f a) | s `elem` [CallByName, HeadReduction] =
+    ///   fmap (Function :) (findRedex CallByName f)
+    ///
+    /// findRedex s (Application f a) =
+    ///   fmap (Function :) (findRedex s f)
+    ///     <|> fmap (Argument :) (findRedex s a)
+    ///     <|> if isRedex (Application f a) then Just [] else Nothing
+    /// ```
+    pub fn find_redex(self, value: &Value) -> Option<Vec<Direction>> {
+        /// Uma operação/passo da procura por um redex.
+        enum Operation<'value> {
+            /// Procura um redex dentro do termo, usando a estratégia dada.
+            Visit(&'value Value, Strategy),
+            /// Testa se o próprio termo é um redex, depois de seus subtermos
+            /// já terem sido visitados.
+            Test(&'value Value),
+            /// Desce para um subtermo, registrando a direção no caminho.
+            Enter(Direction),
+            /// Volta de um subtermo para o termo que o contém.
+            Leave,
+        }
+
+        let mut path = Vec::new();
+        let mut operation_stack = vec![Operation::Visit(value, self)];
+
+        while let Some(operation) = operation_stack.pop() {
+            match operation {
+                Operation::Enter(direction) => path.push(direction),
+
+                Operation::Leave => {
+                    path.pop();
+                }
+
+                Operation::Test(value) => {
+                    if value.is_redex() {
+                        return Some(path);
+                    }
+                }
+
+                Operation::Visit(value, strategy) => match value {
+                    Value::Variable(_) => (),
+
+                    Value::Lambda { parameter: _, body } => {
+                        if strategy.reduces_under_lambda() {
+                            // .pop() retorna elementos na ordem inversa do .push()
+                            operation_stack.push(Operation::Leave);
+                            operation_stack
+                                .push(Operation::Visit(body, strategy));
+                            operation_stack
+                                .push(Operation::Enter(Direction::Body));
+                        }
+                    }
+
+                    Value::Application { function, argument } => {
+                        match strategy {
+                            Strategy::NormalOrder => {
+                                if value.is_redex() {
+                                    return Some(path);
+                                }
+                                operation_stack.push(Operation::Leave);
+                                operation_stack
+                                    .push(Operation::Visit(argument, strategy));
+                                operation_stack.push(Operation::Enter(
+                                    Direction::Argument,
+                                ));
+                            }
+
+                            Strategy::CallByName | Strategy::HeadReduction => {
+                                if value.is_redex() {
+                                    return Some(path);
+                                }
+                            }
+
+                            Strategy::ApplicativeOrder
+                            | Strategy::CallByValue => {
+                                // O redex só é testado depois da função e do
+                                // argumento serem visitados.
+                                operation_stack.push(Operation::Test(value));
+                                operation_stack.push(Operation::Leave);
+                                operation_stack
+                                    .push(Operation::Visit(argument, strategy));
+                                operation_stack.push(Operation::Enter(
+                                    Direction::Argument,
+                                ));
+                            }
+                        }
+
+                        // Na redução de cabeça, a função de uma aplicação é
+                        // reduzida até ser um lambda, mas sem entrar em
+                        // lambdas (i.e. chamada por nome).
+                        let function_strategy = match strategy {
+                            Strategy::HeadReduction => Strategy::CallByName,
+                            _ => strategy,
+                        };
+
+                        // A função é mais à esquerda, então é visitada antes
+                        // (lembra que a pilha inverte).
+                        operation_stack.push(Operation::Leave);
+                        operation_stack.push(Operation::Visit(
+                            function,
+                            function_strategy,
+                        ));
+                        operation_stack
+                            .push(Operation::Enter(Direction::Function));
+                    }
+                },
+            }
+        }
+
+        None
+    }
+}
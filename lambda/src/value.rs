@@ -3,8 +3,12 @@
 #[cfg(test)]
 mod test;
 
+use crate::strategy::Strategy;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hasher;
 use std::mem;
 use std::ops::{Deref, DerefMut};
 
@@ -306,6 +310,82 @@ impl Value {
         equals
     }
 
+    /// Calcula um hash do termo que ignora os nomes dos parâmetros, ou seja,
+    /// termos beta-equivalentes (segundo [`Value::beta_equiv`]) têm o mesmo
+    /// hash. Variáveis ligadas entram no hash por seus índices de De Bruijn, e
+    /// variáveis livres por seus nomes.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// nameless :: Value -> [Either Int String]
+    /// nameless v =
+    ///   let namelessWith (Variable s) ps = case deBruijnIndex s ps of
+    ///         Just i -> [Left 0, Left i]
+    ///         Nothing -> [Left 1, Right s]
+    ///       namelessWith (Application f a) ps =
+    ///         [Left 2] ++ namelessWith f ps ++ namelessWith a ps
+    ///       namelessWith (Lambda p b) ps = [Left 3] ++ namelessWith b (p : ps)
+    ///   in namelessWith v []
+    ///
+    /// namelessHash :: Value -> Int
+    /// namelessHash = hash . nameless
+    /// ```
+    pub fn nameless_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        let mut indices = ParamIndices::default();
+
+        /// Uma operação/passo para computar o hash.
+        enum Operation<'value> {
+            /// Adiciona um termo ao hash.
+            Visit(&'value Value),
+            /// Remove um parâmetro do mapeamento de indices. Restaurando um possível antigo valor.
+            PopIndex(&'value str, Option<u64>),
+        }
+
+        let mut operation_stack = vec![Operation::Visit(self)];
+
+        while let Some(operation) = operation_stack.pop() {
+            match operation {
+                Operation::Visit(value) => match value {
+                    Value::Variable(variable) => match indices.get(variable) {
+                        Some(index) => {
+                            hasher.write_u8(0);
+                            hasher.write_u64(index);
+                        }
+                        None => {
+                            hasher.write_u8(1);
+                            hasher.write(variable.as_bytes());
+                            // Separa nomes de variáveis livres consecutivas.
+                            hasher.write_u8(0xff);
+                        }
+                    },
+
+                    Value::Application { function, argument } => {
+                        hasher.write_u8(2);
+                        // .pop() retorna elementos na ordem inversa do .push()
+                        operation_stack.push(Operation::Visit(argument));
+                        operation_stack.push(Operation::Visit(function));
+                    }
+
+                    Value::Lambda { parameter, body } => {
+                        hasher.write_u8(3);
+                        let old_index = indices.push(parameter);
+                        operation_stack
+                            .push(Operation::PopIndex(parameter, old_index));
+                        operation_stack.push(Operation::Visit(body));
+                    }
+                },
+
+                Operation::PopIndex(param, old_index) => {
+                    indices.pop(param, old_index);
+                }
+            }
+        }
+
+        hasher.finish()
+    }
+
     /// Substitui todas as ocorrências da variável `target_var` pelo valor `new_value` dentro de `self`.
     /// Lida com a captura de variáveis.
     ///
@@ -437,10 +517,13 @@ impl Value {
 
         /// Uma operação/passo para realizar a substituição requisitada.
         enum Operation<'this> {
-            /// Performa todas as substituições do vetor de substituições dentro do tamanho máximo informado nesta operação.
-            Replace(&'this mut Value, usize),
-            /// Remove uma substituição a ser realizada do vetor de substituições, usando o índice informado.
-            DropReplacement(usize),
+            /// Performa todas as substituições ativas do vetor de substituições.
+            Replace(&'this mut Value),
+            /// Remove a última substituição do vetor de substituições.
+            DropReplacement,
+            /// Volta a permitir as substituições dos índices informados, que
+            /// estavam sombreadas pelo parâmetro de um lambda.
+            Unshadow(Vec<usize>),
         }
 
         // Argumentos de todas as substituições usadas pelas operações.
@@ -449,75 +532,74 @@ impl Value {
             new_value,
             new_val_unbound_vars: new_value.unbound_vars().collect(),
         }];
+        // Quantos lambdas envolvendo o termo atual têm como parâmetro a
+        // variável alvo de cada substituição. Uma substituição só está ativa
+        // se nenhum lambda a sombreia.
+        let mut shadowed = vec![0usize];
         // Operações: responsáveis por orquestrarem os passos a partir da
         // operação inicial, usando as substituições.
-        let mut operation_stack = vec![Operation::Replace(self, 1)];
+        let mut operation_stack = vec![Operation::Replace(self)];
 
         while let Some(operation) = operation_stack.pop() {
             match operation {
-                Operation::Replace(value, soft_size) => match value {
+                Operation::Replace(value) => match value {
                     Value::Variable(variable) => {
-                        if let Some(position) = replacements[..soft_size]
+                        // No máximo uma substituição ativa tem esta variável
+                        // como alvo: um lambda que a liga sombreia as demais.
+                        if let Some(position) = replacements
                             .iter()
-                            // Último replacement que satisfaz a nossa condição.
-                            .rposition(|replacement| {
-                                variable == replacement.target_var()
+                            .enumerate()
+                            .position(|(index, replacement)| {
+                                shadowed[index] == 0
+                                    && variable == replacement.target_var()
                             })
                         {
                             // Troca o conteúdo de value.
                             *value = replacements[position].clone_new_value();
-                            // Adiciona operação para tentar substituições que
-                            // vierem antes dessa, usando como alvo o mesmo
-                            // value.
-                            operation_stack
-                                .push(Operation::Replace(value, position));
                         }
                     }
 
                     Value::Application { function, argument } => {
-                        operation_stack
-                            .push(Operation::Replace(argument, soft_size));
-                        operation_stack
-                            .push(Operation::Replace(function, soft_size));
+                        operation_stack.push(Operation::Replace(argument));
+                        operation_stack.push(Operation::Replace(function));
                     }
 
                     Value::Lambda { parameter, body } => {
                         // Vetor de substituições =
                         //  [
-                        //      substituir x por (f a),
-                        //      substituir y por (g (g b)),
-                        //      substituir z por (c (h c)),
-                        //      substituir w por (λf. f f),
-                        //      substituir v por (e),
+                        //      substituir x por (f z),
+                        //      renomear y para y_,
+                        //      renomear z para z_,
                         //  ]
                         //
-                        // Expressão atual = λz. (x y z w v)
-                        //
-                        // Note que a substituição de z (índice 2) entra em
-                        // conflito com o parâmetro λz, então a partir de z
-                        // todas substituições devem ser bloqueadas, porque a
-                        // substituição posterior é consequência da atual
-                        // (delimitadas por "soft size").
+                        // Expressão atual = λz. (x y z)
                         //
-                        // Portanto, somente as substituições x e y podem ser
-                        // feitas (índices 0 e 1), e 2 é o novo "soft size".
-                        let mut new_soft_size = replacements[..soft_size]
+                        // Dentro do lambda, `z` se refere ao parâmetro λz, e
+                        // não ao `z` renomeado para `z_`: só essa
+                        // substituição fica sombreada no corpo. As demais
+                        // continuam valendo, inclusive os renomeamentos feitos
+                        // por lambdas de fora.
+                        let shadowing = replacements
                             .iter()
-                            // Posição do primeiro replacement que satisfizer a
-                            // nossa condição.
-                            .position(|replacement| {
-                                parameter == replacement.target_var()
+                            .enumerate()
+                            .filter(|(index, replacement)| {
+                                shadowed[*index] == 0
+                                    && parameter == replacement.target_var()
                             })
-                            // Se não houver tal replacement, simplesmente use o
-                            // "soft size" de antes.
-                            .unwrap_or(soft_size);
+                            .map(|(index, _)| index)
+                            .collect::<Vec<_>>();
+                        for &index in &shadowing {
+                            shadowed[index] += 1;
+                        }
+                        operation_stack.push(Operation::Unshadow(shadowing));
 
                         // Função (closure) para testar se uma variável é livre
-                        // em algum replacement válido aqui dentro.
+                        // em alguma substituição ativa aqui dentro.
                         let is_unbound_var = |variable: &str| {
-                            replacements[..new_soft_size].iter().any(
-                                |replacement| {
-                                    replacement.is_unbound_var(variable)
+                            replacements.iter().enumerate().any(
+                                |(index, replacement)| {
+                                    shadowed[index] == 0
+                                        && replacement.is_unbound_var(variable)
                                 },
                             )
                         };
@@ -544,37 +626,33 @@ impl Value {
                                 mem::replace(parameter, renamed_var);
 
                             // Adiciona um renomeamento de variáveis para o
-                            // corpo do lambda.
-                            //
-                            // .insert() automaticamente abre espaço para
-                            // inserir no índice desejado.
-                            replacements.insert(
-                                new_soft_size,
-                                Replacement::Rename {
-                                    old_parameter,
-                                    new_parameter: parameter.as_str(),
-                                },
-                            );
+                            // corpo do lambda. Renomeamentos são removidos na
+                            // ordem inversa em que são adicionados, então
+                            // sempre ficam no final do vetor.
+                            replacements.push(Replacement::Rename {
+                                old_parameter,
+                                new_parameter: parameter.as_str(),
+                            });
+                            shadowed.push(0);
 
                             // Destruirá o renomeamento logo após passar pelo
                             // corpo do lambda.
-                            operation_stack.push(Operation::DropReplacement(
-                                new_soft_size,
-                            ));
-
-                            // Conta a nova substituição (o renomeamento).
-                            new_soft_size += 1;
+                            operation_stack.push(Operation::DropReplacement);
                         }
 
-                        operation_stack
-                            .push(Operation::Replace(body, new_soft_size));
+                        operation_stack.push(Operation::Replace(body));
                     }
                 },
 
-                Operation::DropReplacement(index) => {
-                    // .remove() automaticamente shifta elementos após o
-                    // elemento
-                    replacements.remove(index);
+                Operation::DropReplacement => {
+                    replacements.pop();
+                    shadowed.pop();
+                }
+
+                Operation::Unshadow(indices) => {
+                    for index in indices {
+                        shadowed[index] -= 1;
+                    }
                 }
             }
         }
@@ -657,6 +735,125 @@ impl Value {
         redex_found
     }
 
+    /// Faz a redução de um único redex, escolhido pela estratégia de avaliação
+    /// dada. Retorna se tal redex foi encontrado.
+    pub fn reduce_one_with(&mut self, strategy: Strategy) -> bool {
+        match self.find_redex(strategy) {
+            Some(path) => self.reduce_at(&path),
+            None => false,
+        }
+    }
+
+    /// Procura o próximo redex a ser reduzido pela estratégia de avaliação
+    /// dada. Retorna o caminho da raiz deste termo até o redex, se houver.
+    pub fn find_redex(&self, strategy: Strategy) -> Option<Vec<Direction>> {
+        strategy.find_redex(self)
+    }
+
+    /// Reduz o redex no fim do caminho dado. Retorna `false` se o caminho não
+    /// existir ou se não levar a um redex.
+    pub fn reduce_at(&mut self, path: &[Direction]) -> bool {
+        self.subterm_mut(path).is_some_and(Value::contract)
+    }
+
+    /// Testa se este termo é um redex, i.e. uma aplicação de um lambda.
+    pub fn is_redex(&self) -> bool {
+        match self {
+            Value::Application { function, argument: _ } => {
+                matches!(function.as_value(), Value::Lambda { .. })
+            }
+            _ => false,
+        }
+    }
+
+    /// Obtém uma referência para o subtermo no fim do caminho dado, partindo
+    /// deste termo. Retorna `None` se o caminho não existir.
+    pub fn subterm(&self, path: &[Direction]) -> Option<&Value> {
+        let mut value = self;
+
+        for direction in path {
+            value = match (direction, value) {
+                (Direction::Function, Value::Application { function, .. }) => {
+                    function.as_value()
+                }
+                (Direction::Argument, Value::Application { argument, .. }) => {
+                    argument.as_value()
+                }
+                (Direction::Body, Value::Lambda { body, .. }) => {
+                    body.as_value()
+                }
+                _ => return None,
+            };
+        }
+
+        Some(value)
+    }
+
+    /// Obtém uma referência mutável para o subtermo no fim do caminho dado,
+    /// partindo deste termo. Retorna `None` se o caminho não existir.
+    fn subterm_mut(&mut self, path: &[Direction]) -> Option<&mut Value> {
+        let mut value = self;
+
+        for direction in path {
+            value = match (direction, value) {
+                (Direction::Function, Value::Application { function, .. }) => {
+                    function.as_mut_value()
+                }
+                (Direction::Argument, Value::Application { argument, .. }) => {
+                    argument.as_mut_value()
+                }
+                (Direction::Body, Value::Lambda { body, .. }) => {
+                    body.as_mut_value()
+                }
+                _ => return None,
+            };
+        }
+
+        Some(value)
+    }
+
+    /// Contrai este termo, se ele for um redex. Retorna se houve contração.
+    fn contract(&mut self) -> bool {
+        if let Value::Application { function, argument } = self {
+            if let Value::Lambda { parameter, body } = function.as_mut_value() {
+                body.replace(parameter, argument);
+                *self = mem::replace(body.as_mut_value(), Value::dummy());
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Conta o número de nós (variáveis, aplicações e lambdas) deste termo.
+    ///
+    /// # Algoritmo Recursivo
+    ///
+    /// ```haskell
+    /// size :: Value -> Int
+    /// size (Variable s) = 1
+    /// size (Application f a) = 1 + size f + size a
+    /// size (Lambda p b) = 1 + size b
+    /// ```
+    pub fn size(&self) -> usize {
+        let mut size = 0;
+        let mut visit_stack: Vec<&Value> = vec![self];
+
+        while let Some(value) = visit_stack.pop() {
+            size += 1;
+            match value {
+                Value::Variable(_) => (),
+                Value::Application { function, argument } => {
+                    visit_stack.push(argument);
+                    visit_stack.push(function);
+                }
+                Value::Lambda { parameter: _, body } => visit_stack.push(body),
+            }
+        }
+
+        size
+    }
+
     /// Cria um iterador sobre as variáveis não-ligadas neste termo. Variáveis podem aparecer mais de uma vez.
     ///
     /// # Algoritmo Recursivo
@@ -815,6 +1012,128 @@ impl Clone for Value {
     }
 }
 
+impl fmt::Display for Value {
+    /// Escreve o termo como código aceito pelo parser, usando `\` para
+    /// lambdas, agrupando lambdas aninhados (`\x y. x`) e usando somente os
+    /// parênteses necessários.
+    ///
+    /// # Algoritmo recursivo
+    ///
+    /// ```haskell
+    /// toCode :: Value -> String
+    /// toCode v =
+    ///   let params (Lambda p b) = let (ps, b') = params b in (p : ps, b')
+    ///       params b = ([], b)
+    ///
+    ///       -- `tail` indica se o termo é o último de seu contexto, isto é,
+    ///       -- se nada é escrito depois dele antes de um ")" ou do fim.
+    ///       toCodeWith (Variable s) tail = s
+    ///
+    ///       toCodeWith (Lambda p b) tail =
+    ///         let (ps, b') = params (Lambda p b)
+    ///             code = "\\" ++ unwords ps ++ ". " ++ toCodeWith b' True
+    ///         in if tail then code else "(" ++ code ++ ")"
+    ///
+    ///       toCodeWith (Application f a) tail =
+    ///         let arg = case a of
+    ///               Application _ _ -> "(" ++ toCodeWith a True ++ ")"
+    ///               _ -> toCodeWith a tail
+    ///         in toCodeWith f False ++ " " ++ arg
+    ///
+    ///   in toCodeWith v True
+    /// ```
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        /// Uma operação auxiliar de escrita.
+        enum Operation<'value> {
+            /// Escreve um termo. O booleano indica se o termo é o último de
+            /// seu contexto (nada é escrito depois dele antes de um `)` ou do
+            /// fim do código).
+            Write(&'value Value, bool),
+
+            /// Escreve um texto fixo.
+            Text(&'static str),
+        }
+
+        let mut operation_stack = vec![Operation::Write(self, true)];
+
+        while let Some(operation) = operation_stack.pop() {
+            match operation {
+                Operation::Text(text) => formatter.write_str(text)?,
+
+                Operation::Write(value, tail) => match value {
+                    // Caso base
+                    Value::Variable(variable) => {
+                        formatter.write_str(variable)?
+                    }
+
+                    Value::Application { function, argument } => {
+                        // .pop() retorna elementos na ordem inversa do .push()
+                        match argument.as_value() {
+                            // Aplicação é associativa à esquerda, então uma
+                            // aplicação no argumento sempre precisa de
+                            // parênteses.
+                            Value::Application { .. } => {
+                                operation_stack.push(Operation::Text(")"));
+                                operation_stack
+                                    .push(Operation::Write(argument, true));
+                                operation_stack.push(Operation::Text("("));
+                            }
+                            _ => operation_stack
+                                .push(Operation::Write(argument, tail)),
+                        }
+                        operation_stack.push(Operation::Text(" "));
+                        // Algo sempre vem depois da função: o argumento.
+                        operation_stack.push(Operation::Write(function, false));
+                    }
+
+                    Value::Lambda { .. } if !tail => {
+                        // O corpo de um lambda vai até o fim do contexto,
+                        // então um lambda seguido de algo precisa de
+                        // parênteses.
+                        formatter.write_str("(")?;
+                        operation_stack.push(Operation::Text(")"));
+                        operation_stack.push(Operation::Write(value, true));
+                    }
+
+                    Value::Lambda { parameter, body } => {
+                        write!(formatter, "\\{}", parameter)?;
+
+                        // Agrupa lambdas aninhados: \x. \y. b => \x y. b
+                        let mut body = body.as_value();
+                        while let Value::Lambda { parameter, body: inner } =
+                            body
+                        {
+                            write!(formatter, " {}", parameter)?;
+                            body = inner.as_value();
+                        }
+
+                        formatter.write_str(". ")?;
+                        operation_stack.push(Operation::Write(body, true));
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Um passo no caminho da raiz de um termo até um de seus subtermos.
+///
+/// # Exemplo
+///
+/// Em `λx. (λy. y) x`, o redex `(λy. y) x` está no caminho `[Body]`, e o `y`
+/// mais interno no caminho `[Body, Function, Body]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    /// Desce para a função de uma aplicação.
+    Function,
+    /// Desce para o argumento de uma aplicação.
+    Argument,
+    /// Desce para o corpo de um lambda.
+    Body,
+}
+
 /// Iterador que produz nomes de variáveis não-ligadas em um termo lambda. Nome de variáveis podem repetir.
 #[derive(Debug, Clone)]
 pub struct UnboundVars<'value> {
@@ -1,5 +1,6 @@
 use super::NestedValue;
 use super::Value;
+use crate::compiler::compile;
 
 #[test]
 fn not_beta_equiv_by_var_both_bound() {
@@ -311,3 +312,121 @@ fn failing_bad_x_to_int() {
     };
     assert_eq!(target.church_numeral_to_int(), None);
 }
+
+#[test]
+fn display_church_numeral() {
+    assert_eq!(Value::church_numeral(2).to_string(), "\\f x. f (f x)");
+}
+
+#[test]
+fn display_lambda_in_function() {
+    // (λx. x) (λy. y) z
+    let value = Value::Application {
+        function: NestedValue::new(Value::Application {
+            function: NestedValue::new(Value::Lambda {
+                parameter: String::from("x"),
+                body: NestedValue::new(Value::Variable(String::from("x"))),
+            }),
+            argument: NestedValue::new(Value::Lambda {
+                parameter: String::from("y"),
+                body: NestedValue::new(Value::Variable(String::from("y"))),
+            }),
+        }),
+        argument: NestedValue::new(Value::Variable(String::from("z"))),
+    };
+
+    assert_eq!(value.to_string(), "(\\x. x) (\\y. y) z");
+}
+
+#[test]
+fn display_lambda_in_last_argument() {
+    // f (λx. x (y z))
+    let value = Value::Application {
+        function: NestedValue::new(Value::Variable(String::from("f"))),
+        argument: NestedValue::new(Value::Lambda {
+            parameter: String::from("x"),
+            body: NestedValue::new(Value::Application {
+                function: NestedValue::new(Value::Variable(String::from("x"))),
+                argument: NestedValue::new(Value::Application {
+                    function: NestedValue::new(Value::Variable(String::from(
+                        "y",
+                    ))),
+                    argument: NestedValue::new(Value::Variable(String::from(
+                        "z",
+                    ))),
+                }),
+            }),
+        }),
+    };
+
+    assert_eq!(value.to_string(), "f \\x. x (y z)");
+}
+
+/// Gerador pseudo-aleatório de termos (gerador congruencial linear), para que
+/// o teste de ida e volta seja determinístico.
+struct TermGenerator {
+    state: u64,
+}
+
+impl TermGenerator {
+    fn next(&mut self, bound: u64) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.state >> 33) % bound
+    }
+
+    fn generate(&mut self, depth: u32) -> Value {
+        let names = ["x", "y", "f", "a_", "foo"];
+        let choice = if depth == 0 { 0 } else { self.next(3) };
+
+        match choice {
+            0 => {
+                let name = names[self.next(names.len() as u64) as usize];
+                Value::Variable(String::from(name))
+            }
+            1 => Value::Application {
+                function: NestedValue::new(self.generate(depth - 1)),
+                argument: NestedValue::new(self.generate(depth - 1)),
+            },
+            _ => {
+                let name = names[self.next(names.len() as u64) as usize];
+                Value::Lambda {
+                    parameter: String::from(name),
+                    body: NestedValue::new(self.generate(depth - 1)),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn display_round_trip() {
+    let mut generator = TermGenerator { state: 42 };
+
+    for _ in 0..500 {
+        let value = generator.generate(6);
+        let code = value.to_string();
+        assert_eq!(compile(&code).ok(), Some(value), "código: {}", code);
+    }
+}
+
+#[test]
+fn replace_renames_uses_of_renamed_parameter() {
+    // (λx. λf. λx. f) f
+    let mut value = compile("(\\x. \\f. \\x. f) f").unwrap();
+
+    assert!(value.reduce_one());
+    assert_eq!(value.to_string(), "\\f_ x. f_");
+}
+
+#[test]
+fn replace_renames_around_free_variables() {
+    // λa_. λa. (λx. λa. x a a_) (λz. a) k
+    let mut value =
+        compile("\\a_. \\a. (\\x. \\a. x a a_) (\\z. a) k").unwrap();
+
+    assert!(value.reduce_one());
+    assert_eq!(value.to_string(), "\\a_ a. (\\a__. (\\z. a) a__ a_) k");
+}
@@ -1,4 +1,75 @@
-import { } from './common.js';
+import { init, loadCode } from './common.js';
+import { stringify } from './lambda.js';
+import { drawTerm, initSvgRoot } from './draw.js';
 import * as wasm from "lambda-wasm";
 
-wasm.greet();
+init(() => {
+    const svgTarget = document.getElementById('tree');
+    const termTarget = document.querySelector('.actual-term div');
+    const stepsTarget = document.querySelector('.execution-settings b');
+    const delayInput = document.querySelector('.execution-settings input[type="range"]');
+    const delayLabel = delayInput.previousElementSibling;
+    const [resetButton, stepButton, runButton, stopButton] =
+        document.querySelectorAll('.control_buttons button');
+
+    initSvgRoot(svgTarget);
+
+    let interpreter = null;
+    let runTimeout = null;
+
+    try {
+        interpreter = new wasm.Interpreter(loadCode() || '');
+    } catch (diagnostics) {
+        termTarget.classList.add('diagnostics');
+        termTarget.textContent = diagnostics
+            .map(({ rendered }) => rendered)
+            .join('\n');
+        return;
+    }
+
+    const render = () => {
+        const term = interpreter.output();
+        termTarget.textContent = stringify(term);
+        stepsTarget.textContent = `Passo: ${interpreter.steps()}`;
+        drawTerm(term, svgTarget);
+    };
+
+    const stop = () => {
+        if (runTimeout !== null) {
+            clearTimeout(runTimeout);
+            runTimeout = null;
+        }
+    };
+
+    const run = () => {
+        const reduced = interpreter.step();
+        render();
+        runTimeout = reduced ? setTimeout(run, Number(delayInput.value)) : null;
+    };
+
+    resetButton.addEventListener('click', () => {
+        stop();
+        interpreter.reset();
+        render();
+    });
+
+    stepButton.addEventListener('click', () => {
+        stop();
+        interpreter.step();
+        render();
+    });
+
+    runButton.addEventListener('click', () => {
+        stop();
+        run();
+    });
+
+    stopButton.addEventListener('click', stop);
+
+    delayInput.value = 0;
+    delayInput.addEventListener('input', () => {
+        delayLabel.textContent = `Espera entre passos (em ms): ${delayInput.value}`;
+    });
+
+    render();
+});